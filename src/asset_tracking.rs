@@ -2,7 +2,11 @@
 
 use std::collections::VecDeque;
 
-use bevy::prelude::*;
+use bevy::{
+    asset::{AssetPath, RecursiveDependencyLoadState},
+    image::{ImageLoaderSettings, ImageSampler},
+    prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<ResourceHandles>();
@@ -36,6 +40,14 @@ impl LoadResource for App {
     }
 }
 
+/// Loads a pixel art image with nearest sampling, so it stays crisp instead of getting blurry
+/// when scaled up. Use this for every sprite.
+pub fn load_pixel_image<'a>(assets: &AssetServer, path: impl Into<AssetPath<'a>>) -> Handle<Image> {
+    assets.load_with_settings(path, |settings: &mut ImageLoaderSettings| {
+        settings.sampler = ImageSampler::nearest();
+    })
+}
+
 /// A function that inserts a loaded resource.
 type InsertLoadedResource = fn(&mut World, &UntypedHandle);
 
@@ -45,12 +57,28 @@ pub struct ResourceHandles {
     // `finished` one at a time.
     waiting: VecDeque<(UntypedHandle, InsertLoadedResource)>,
     finished: Vec<UntypedHandle>,
+    failed: Vec<UntypedHandle>,
 }
 
 impl ResourceHandles {
     /// Returns true if all requested [`Asset`]s have finished loading and are available as [`Resource`]s.
     pub fn is_all_done(&self) -> bool {
-        self.waiting.is_empty()
+        self.waiting.is_empty() && self.failed.is_empty()
+    }
+
+    /// Returns true if any requested [`Asset`] (or one of its dependencies) failed to load.
+    /// Its resource will never be inserted.
+    pub fn has_failed(&self) -> bool {
+        !self.failed.is_empty()
+    }
+
+    /// The share of requested [`Asset`]s that finished loading, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        let total = self.waiting.len() + self.finished.len() + self.failed.len();
+        if total == 0 {
+            return 1.0;
+        }
+        self.finished.len() as f32 / total as f32
     }
 }
 
@@ -62,6 +90,12 @@ fn load_resource_assets(world: &mut World) {
                 if assets.is_loaded_with_dependencies(&handle) {
                     insert_fn(world, &handle);
                     resource_handles.finished.push(handle);
+                } else if matches!(
+                    assets.recursive_dependency_load_state(&handle),
+                    RecursiveDependencyLoadState::Failed(_)
+                ) {
+                    // The asset server already logged which asset failed and why.
+                    resource_handles.failed.push(handle);
                 } else {
                     resource_handles.waiting.push_back((handle, insert_fn));
                 }
@@ -1,6 +1,12 @@
-use bevy::prelude::*;
+use std::collections::VecDeque;
+
+use bevy::{platform::collections::HashMap, prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SoundEffectLimits>();
+    app.init_resource::<PlayingSoundEffects>();
+    app.add_observer(limit_sound_effects);
+    app.add_observer(forget_sound_effect);
     app.add_systems(
         Update,
         apply_global_volume.run_if(resource_changed::<GlobalVolume>),
@@ -26,13 +32,108 @@ pub fn music(handle: Handle<AudioSource>) -> impl Bundle {
 /// This can then be used to query for and operate on sounds in that category.
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
+#[require(SoundKind)]
 pub struct SoundEffect;
 
+/// What a sound effect is, so a flood of one kind of sound can be kept in check without
+/// cutting off the others. Add it next to [`sound_effect`], or it's [`SoundKind::Other`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect)]
+#[reflect(Component)]
+pub enum SoundKind {
+    Footstep,
+    Explosion,
+    Pickup,
+    /// Landings and bounces.
+    Impact,
+    /// Hovering and clicking buttons.
+    Interface,
+    #[default]
+    Other,
+}
+
+/// How many sound effects of each [`SoundKind`] can play at once. Starting one more stops the
+/// oldest one of that kind.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct SoundEffectLimits {
+    pub footstep: usize,
+    pub explosion: usize,
+    pub pickup: usize,
+    pub impact: usize,
+    pub interface: usize,
+    pub other: usize,
+}
+
+impl Default for SoundEffectLimits {
+    fn default() -> Self {
+        Self {
+            footstep: 2,
+            explosion: 4,
+            pickup: 3,
+            impact: 3,
+            interface: 2,
+            other: 8,
+        }
+    }
+}
+
+impl SoundEffectLimits {
+    pub fn limit(&self, kind: SoundKind) -> usize {
+        match kind {
+            SoundKind::Footstep => self.footstep,
+            SoundKind::Explosion => self.explosion,
+            SoundKind::Pickup => self.pickup,
+            SoundKind::Impact => self.impact,
+            SoundKind::Interface => self.interface,
+            SoundKind::Other => self.other,
+        }
+    }
+}
+
+/// The sound effects of each kind that are still around, from the oldest to the newest.
+#[derive(Resource, Debug, Default)]
+struct PlayingSoundEffects(HashMap<SoundKind, VecDeque<Entity>>);
+
 /// A sound effect audio instance.
 pub fn sound_effect(handle: Handle<AudioSource>) -> impl Bundle {
     (AudioPlayer(handle), PlaybackSettings::DESPAWN, SoundEffect)
 }
 
+/// Counts sound effects from when they're spawned rather than from when their [`AudioSink`]
+/// shows up, so a burst of them within a single frame is limited too.
+fn limit_sound_effects(
+    add: On<Add, SoundKind>,
+    mut commands: Commands,
+    limits: Res<SoundEffectLimits>,
+    mut playing: ResMut<PlayingSoundEffects>,
+    kinds: Query<&SoundKind>,
+) {
+    let Ok(&kind) = kinds.get(add.entity) else {
+        return;
+    };
+    let sounds = playing.0.entry(kind).or_default();
+    sounds.push_back(add.entity);
+    while sounds.len() > limits.limit(kind) {
+        let Some(oldest) = sounds.pop_front() else {
+            break;
+        };
+        commands.entity(oldest).try_despawn();
+    }
+}
+
+fn forget_sound_effect(
+    remove: On<Remove, SoundKind>,
+    mut playing: ResMut<PlayingSoundEffects>,
+    kinds: Query<&SoundKind>,
+) {
+    let Ok(kind) = kinds.get(remove.entity) else {
+        return;
+    };
+    if let Some(sounds) = playing.0.get_mut(kind) {
+        sounds.retain(|&sound| sound != remove.entity);
+    }
+}
+
 /// [`GlobalVolume`] doesn't apply to already-running audio entities, so this system will update them.
 fn apply_global_volume(
     global_volume: Res<GlobalVolume>,
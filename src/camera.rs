@@ -0,0 +1,198 @@
+//! The main camera, which follows the players around the level.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_tnua::prelude::TnuaController;
+
+use crate::{
+    AppSystems, Pause,
+    game::{
+        accessibility::ReducedMotion,
+        camera_bounds::CameraBounds,
+        level::LevelChanged,
+        player::{Look, Player},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<CameraPeekConfig>();
+    app.init_resource::<CameraPeek>();
+    app.init_resource::<PixelSnap>();
+    app.init_resource::<SmoothedCamera>();
+
+    app.add_systems(Startup, spawn_camera);
+    app.add_observer(snap_on_level_change);
+    app.add_observer(snap_on_player_spawn);
+    // The camera isn't pausable, so it keeps following the players while gameplay is frozen.
+    app.add_systems(
+        Update,
+        follow_camera
+            .in_set(AppSystems::RecordInput)
+            .run_if(in_state(Pause(false))),
+    );
+}
+
+fn spawn_camera(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Camera"),
+        Camera2d,
+        Transform::from_scale(Vec3::splat(0.3)),
+    ));
+}
+
+const CAMERA_DECAY_RATE: f32 = 4.;
+/// How close to the edge of the view the players may get while the camera is clamped.
+const CAMERA_FRAMING_MARGIN: Vec2 = Vec2::splat(24.0);
+
+/// How the camera peeks up or down while the players stand still and look there.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct CameraPeekConfig {
+    /// How long the players have to look before the camera starts moving.
+    pub delay: Duration,
+    /// How far the camera moves, in world units.
+    pub distance: f32,
+    /// The decay rate the camera eases in and out with.
+    pub speed: f32,
+}
+
+impl Default for CameraPeekConfig {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_millis(500),
+            distance: 48.0,
+            speed: 4.0,
+        }
+    }
+}
+
+/// The current vertical offset of the camera from peeking.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct CameraPeek {
+    /// How long the players have been looking the same way.
+    held: Duration,
+    /// The way they're looking, `1.0` for up and `-1.0` for down.
+    direction: f32,
+    offset: f32,
+}
+
+/// Whether the camera only moves in whole pixels of the art. This stops the pixel art from
+/// shimmering as the camera moves, at the cost of slightly choppier motion.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PixelSnap(pub bool);
+
+/// Where the camera would be without [`PixelSnap`]. Smoothing continues from here, so slow
+/// movement isn't rounded away.
+///
+/// `None` makes the camera jump straight to the players instead of smoothing over, e.g. so it
+/// doesn't swoop across a new level.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Resource)]
+struct SmoothedCamera(Option<Vec2>);
+
+fn snap_on_level_change(_: On<LevelChanged>, mut smoothed: ResMut<SmoothedCamera>) {
+    smoothed.0 = None;
+}
+
+/// This covers respawning, which spawns the players anew.
+fn snap_on_player_spawn(_: On<Add, Player>, mut smoothed: ResMut<SmoothedCamera>) {
+    smoothed.0 = None;
+}
+
+fn follow_camera(
+    camera: Single<(&mut Transform, &Camera), With<Camera2d>>,
+    players: Query<(&GlobalTransform, &Look, &TnuaController), (With<Player>, Without<Camera2d>)>,
+    new_players: Query<(), Added<Player>>,
+    peek_config: Res<CameraPeekConfig>,
+    mut peek: ResMut<CameraPeek>,
+    bounds: Res<CameraBounds>,
+    reduced_motion: Res<ReducedMotion>,
+    pixel_snap: Res<PixelSnap>,
+    mut smoothed: ResMut<SmoothedCamera>,
+    time: Res<Time>,
+) {
+    let (mut camera_transform, camera) = camera.into_inner();
+    if players.is_empty() {
+        return;
+    }
+    // New players aren't where they'll be drawn until their transforms are propagated, so
+    // snapping to them waits for that.
+    if smoothed.0.is_none() && !new_players.is_empty() {
+        return;
+    }
+
+    // Keep all players in frame by following their midpoint.
+    let sum: Vec3 = players
+        .iter()
+        .map(|(transform, ..)| transform.translation())
+        .sum();
+    let Vec3 { x, y, .. } = sum / players.iter().len() as f32;
+
+    // Only peek while every player stands on the ground looking the same way.
+    let mut looks = players.iter().map(|(_, &look, controller)| {
+        if controller.is_airborne().unwrap_or(true) {
+            Look::Ahead
+        } else {
+            look
+        }
+    });
+    let first_look = looks.next().unwrap_or_default();
+    let look = if looks.all(|look| look == first_look) {
+        first_look
+    } else {
+        Look::Ahead
+    };
+    let peek_direction = match look {
+        Look::Ahead => 0.0,
+        Look::Up => 1.0,
+        Look::Down => -1.0,
+    };
+
+    // Looking the other way starts the delay over.
+    if peek_direction == 0.0 || peek_direction != peek.direction || reduced_motion.0 {
+        peek.held = Duration::ZERO;
+    } else {
+        peek.held += time.delta();
+    }
+    peek.direction = peek_direction;
+    let target = if peek.held >= peek_config.delay {
+        peek_direction * peek_config.distance
+    } else {
+        0.0
+    };
+    peek.offset
+        .smooth_nudge(&target, peek_config.speed, time.delta_secs());
+
+    let mut target = Vec2::new(x, y + peek.offset);
+    if let (Some(bounds), Some(viewport)) = (bounds.active(), camera.logical_viewport_size()) {
+        let half_view = viewport * camera_transform.scale.truncate() / 2.0;
+        // Levels smaller than the view are centered instead.
+        let min = (bounds.min + half_view).min(bounds.center());
+        let max = (bounds.max - half_view).max(bounds.center());
+        target = target.clamp(min, max);
+        // The bounds may lag behind while moving to a new level, but never lose the players.
+        let midpoint = Vec2::new(x, y);
+        let margin = (half_view - CAMERA_FRAMING_MARGIN).max(Vec2::ZERO);
+        target = target.clamp(midpoint - margin, midpoint + margin);
+    }
+
+    // Applies a smooth effect to camera movement using stable interpolation
+    // between the camera position and the player position on the x and y axes.
+    let mut position = match smoothed.0 {
+        Some(mut position) => {
+            position.smooth_nudge(&target, CAMERA_DECAY_RATE, time.delta_secs());
+            position
+        }
+        None => target,
+    };
+    smoothed.0 = Some(position);
+
+    // Only the final position is snapped, so it doesn't interfere with the smoothing.
+    if pixel_snap.0 {
+        position = position.round();
+    }
+    camera_transform.translation = position.extend(camera_transform.translation.z);
+}
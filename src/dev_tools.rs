@@ -5,9 +5,16 @@ use bevy::{
     input::common_conditions::{input_just_pressed, input_toggle_active},
     prelude::*,
 };
+use bevy_ecs_ldtk::{
+    LevelSelection,
+    assets::{LdtkProject, LdtkProjectHandle},
+};
 use bevy_inspector_egui::{bevy_egui::EguiPlugin, quick::WorldInspectorPlugin};
 
-use crate::screens::Screen;
+use crate::{
+    game::level::{go_to_next_level, retreat_level},
+    screens::Screen,
+};
 
 pub(super) fn plugin(app: &mut App) {
     // Log `Screen` state transitions.
@@ -23,11 +30,44 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         toggle_debug_ui.run_if(input_just_pressed(TOGGLE_DEBUG_UI_KEY)),
     );
+
+    // Skip between levels without walking to the door.
+    app.add_systems(
+        Update,
+        (
+            next_level.run_if(input_just_pressed(NEXT_LEVEL_KEY)),
+            previous_level.run_if(input_just_pressed(PREVIOUS_LEVEL_KEY)),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
 }
 
 const TOGGLE_INSPECTOR_KEY: KeyCode = KeyCode::Backquote;
 const TOGGLE_DEBUG_UI_KEY: KeyCode = KeyCode::KeyU;
+const NEXT_LEVEL_KEY: KeyCode = KeyCode::KeyN;
+const PREVIOUS_LEVEL_KEY: KeyCode = KeyCode::KeyB;
 
 fn toggle_debug_ui(mut options: ResMut<UiDebugOptions>) {
     options.toggle();
 }
+
+fn next_level(
+    mut level_selection: ResMut<LevelSelection>,
+    ldtk_project: Single<&LdtkProjectHandle>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if let Some(ldtk_project) = ldtk_project_assets.get(*ldtk_project) {
+        go_to_next_level(&mut level_selection, ldtk_project, &mut next_screen);
+    }
+}
+
+fn previous_level(
+    mut level_selection: ResMut<LevelSelection>,
+    ldtk_project: Single<&LdtkProjectHandle>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+) {
+    if let Some(ldtk_project) = ldtk_project_assets.get(*ldtk_project) {
+        retreat_level(&mut level_selection, ldtk_project);
+    }
+}
@@ -0,0 +1,93 @@
+//! Accessibility options that change how the game is presented, but not how it plays.
+//!
+//! They are stored in the [`SaveData`], so they stay set between runs.
+
+use bevy::{color::palettes::css, prelude::*};
+
+use crate::{
+    AppSystems,
+    game::{grid_coords::GRID_SIZE, mine::Mine, spike::Spike},
+    save::SaveData,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ReducedMotion>();
+    app.init_resource::<HazardOutlines>();
+    app.add_systems(Startup, load_accessibility_settings);
+    app.add_systems(
+        Update,
+        save_accessibility_settings
+            .run_if(resource_changed::<ReducedMotion>.or(resource_changed::<HazardOutlines>)),
+    );
+    app.add_systems(
+        Update,
+        draw_hazard_outlines
+            .run_if(hazard_outlines_enabled)
+            .in_set(AppSystems::Update),
+    );
+}
+
+/// Tones down movement on screen for players who are sensitive to it.
+///
+/// While enabled:
+/// - the camera doesn't peek up or down when the players look there,
+/// - no particles are spawned for dust, debris or explosions,
+/// - the player sprite doesn't squash and stretch,
+/// - the camera jumps to a new level's bounds instead of gliding there,
+/// - the level name pops in and out instead of fading,
+/// - letterbox bars appear at once instead of sliding in,
+/// - toasts appear at once instead of sliding in,
+/// - players with spawn protection stay see-through instead of blinking,
+/// - respawning players appear at full size instead of growing in.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct ReducedMotion(pub bool);
+
+/// Draws a high-contrast frame around visible hazards, so they don't have to be told apart
+/// by their sprites alone.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct HazardOutlines(pub bool);
+
+pub fn hazard_outlines_enabled(hazard_outlines: Res<HazardOutlines>) -> bool {
+    hazard_outlines.0
+}
+
+fn load_accessibility_settings(
+    save_data: Res<SaveData>,
+    mut reduced_motion: ResMut<ReducedMotion>,
+    mut hazard_outlines: ResMut<HazardOutlines>,
+) {
+    reduced_motion.0 = save_data.reduced_motion;
+    hazard_outlines.0 = save_data.hazard_outlines;
+}
+
+fn save_accessibility_settings(
+    reduced_motion: Res<ReducedMotion>,
+    hazard_outlines: Res<HazardOutlines>,
+    mut save_data: ResMut<SaveData>,
+) {
+    if save_data.reduced_motion != reduced_motion.0
+        || save_data.hazard_outlines != hazard_outlines.0
+    {
+        save_data.reduced_motion = reduced_motion.0;
+        save_data.hazard_outlines = hazard_outlines.0;
+    }
+}
+
+/// Hidden hazards stay hidden, the outline only shows up along with them.
+fn draw_hazard_outlines(
+    mut gizmos: Gizmos,
+    hazards: Query<(&GlobalTransform, &InheritedVisibility), Or<(With<Spike>, With<Mine>)>>,
+) {
+    let size = Vec2::splat(GRID_SIZE as f32);
+    for (transform, visibility) in &hazards {
+        if !visibility.get() {
+            continue;
+        }
+        // A dark frame around a light one stands out against any background.
+        let position = transform.translation().truncate();
+        gizmos.rect_2d(position, size + 2.0, css::BLACK);
+        gizmos.rect_2d(position, size, css::YELLOW);
+    }
+}
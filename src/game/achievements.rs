@@ -0,0 +1,203 @@
+//! Achievements for playing well, unlocked by watching what already happens in a run.
+//!
+//! Unlocked achievements are kept in the [`SaveData`], and the menu lists them.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_ecs_ldtk::LevelSelection;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AppSystems,
+    game::{
+        coin::RunCoins,
+        level::LevelChanged,
+        objectives::{LevelObjectives, count_objectives},
+        player::{DeathCount, PracticeMode},
+        run_timer::{RunTimer, record_best_time},
+    },
+    save::SaveData,
+    screens::Screen,
+    theme::toast::ToastQueue,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LevelSplit>();
+    app.init_resource::<PracticedThisRun>();
+    app.add_observer(show_unlocked_achievement);
+    app.add_observer(check_level_split);
+    app.add_systems(OnEnter(Screen::Gameplay), reset_level_split);
+    app.add_systems(OnEnter(Screen::Title), reset_practiced_this_run);
+    app.add_systems(
+        OnEnter(Screen::Victory),
+        (check_final_level_split, check_flawless_run).after(record_best_time),
+    );
+    app.add_systems(
+        Update,
+        (
+            start_level_split,
+            track_practice_mode,
+            check_all_coins.after(count_objectives),
+        )
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Finishing a level faster than this unlocks [`Achievement::Speedrunner`].
+pub const SPEEDRUN_LEVEL_TIME: Duration = Duration::from_secs(20);
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect, Serialize, Deserialize,
+)]
+pub enum Achievement {
+    /// Win the game without dying once.
+    Flawless,
+    /// Pick up every coin of a level in one attempt.
+    CoinCollector,
+    /// Finish a level in under [`SPEEDRUN_LEVEL_TIME`].
+    Speedrunner,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 3] = [
+        Achievement::Flawless,
+        Achievement::CoinCollector,
+        Achievement::Speedrunner,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Achievement::Flawless => "Flawless",
+            Achievement::CoinCollector => "Coin Collector",
+            Achievement::Speedrunner => "Speedrunner",
+        }
+    }
+
+    pub fn description(self) -> String {
+        match self {
+            Achievement::Flawless => "Escape without dying".to_string(),
+            Achievement::CoinCollector => "Find every coin of a level at once".to_string(),
+            Achievement::Speedrunner => format!(
+                "Finish a level in under {} seconds",
+                SPEEDRUN_LEVEL_TIME.as_secs()
+            ),
+        }
+    }
+}
+
+/// Triggered the first time an [`Achievement`] is earned.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AchievementUnlocked(pub Achievement);
+
+/// Unlocks `achievement`, unless it already is.
+pub fn unlock(commands: &mut Commands, save_data: &mut SaveData, achievement: Achievement) {
+    if save_data.achievements.insert(achievement) {
+        commands.trigger(AchievementUnlocked(achievement));
+    }
+}
+
+/// When the current level was entered, according to the [`RunTimer`].
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+struct LevelSplit {
+    /// The level the split belongs to, once it started.
+    level: Option<LevelSelection>,
+    start: Duration,
+}
+
+fn reset_level_split(mut split: ResMut<LevelSplit>) {
+    *split = LevelSplit::default();
+}
+
+fn start_level_split(
+    mut split: ResMut<LevelSplit>,
+    level_selection: Res<LevelSelection>,
+    run_timer: Res<RunTimer>,
+) {
+    if split.level.is_none() {
+        split.level = Some(level_selection.clone());
+        split.start = run_timer.stopwatch.elapsed();
+    }
+}
+
+fn check_split(
+    commands: &mut Commands,
+    save_data: &mut SaveData,
+    split: &LevelSplit,
+    run_timer: &RunTimer,
+) {
+    if split.level.is_some()
+        && run_timer.stopwatch.elapsed().saturating_sub(split.start) < SPEEDRUN_LEVEL_TIME
+    {
+        unlock(commands, save_data, Achievement::Speedrunner);
+    }
+}
+
+fn check_level_split(
+    event: On<LevelChanged>,
+    mut commands: Commands,
+    mut save_data: ResMut<SaveData>,
+    mut split: ResMut<LevelSplit>,
+    run_timer: Res<RunTimer>,
+) {
+    // The selection also changes when a new run starts somewhere else than the last one ended.
+    if split.level.as_ref() == Some(&event.from) {
+        check_split(&mut commands, &mut save_data, &split, &run_timer);
+    }
+    split.level = Some(event.to.clone());
+    split.start = run_timer.stopwatch.elapsed();
+}
+
+/// The last level is left for the victory screen instead of another level.
+fn check_final_level_split(
+    mut commands: Commands,
+    mut save_data: ResMut<SaveData>,
+    split: Res<LevelSplit>,
+    run_timer: Res<RunTimer>,
+) {
+    check_split(&mut commands, &mut save_data, &split, &run_timer);
+}
+
+/// Whether [`PracticeMode`] was on at any point since leaving the title screen, like the
+/// [`DeathCount`] is counted.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+struct PracticedThisRun(bool);
+
+fn reset_practiced_this_run(mut practiced: ResMut<PracticedThisRun>) {
+    practiced.0 = false;
+}
+
+fn track_practice_mode(practice_mode: Res<PracticeMode>, mut practiced: ResMut<PracticedThisRun>) {
+    if practice_mode.0 && !practiced.0 {
+        practiced.0 = true;
+    }
+}
+
+fn check_flawless_run(
+    mut commands: Commands,
+    mut save_data: ResMut<SaveData>,
+    death_count: Res<DeathCount>,
+    practiced: Res<PracticedThisRun>,
+) {
+    // Practice mode doesn't count deaths at all, so a run that used it can't be flawless.
+    if death_count.0 == 0 && !practiced.0 {
+        unlock(&mut commands, &mut save_data, Achievement::Flawless);
+    }
+}
+
+fn check_all_coins(
+    mut commands: Commands,
+    mut save_data: ResMut<SaveData>,
+    run_coins: Res<RunCoins>,
+    objectives: Res<LevelObjectives>,
+) {
+    if run_coins.is_changed() && run_coins.current > 0 && objectives.coins_left == 0 {
+        unlock(&mut commands, &mut save_data, Achievement::CoinCollector);
+    }
+}
+
+fn show_unlocked_achievement(event: On<AchievementUnlocked>, mut toasts: ResMut<ToastQueue>) {
+    toasts.push(format!("Achievement unlocked: {}", event.event().0.name()));
+}
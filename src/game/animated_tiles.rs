@@ -0,0 +1,86 @@
+//! Level tiles that play an animation, like torches or dripping water.
+//!
+//! Tiles are only animated on layers registered with
+//! [`AnimatedTileAppExt::register_animated_tile_layer`]. In LDtk, give the first frame's tile
+//! custom data like `(frames: 4, interval_ms: 150)`. The other frames are the tiles following it
+//! in the tileset, from left to right.
+
+use std::time::Duration;
+
+use bevy::{platform::collections::HashSet, prelude::*};
+use bevy_ecs_ldtk::prelude::{LayerMetadata, TileMetadata};
+use bevy_ecs_tilemap::tiles::TileTextureIndex;
+use serde::Deserialize;
+
+use crate::{
+    AppSystems,
+    game::animation::{Animation, AnimationData, AnimationState, Repeat},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_animated_tile_layer("Details");
+    app.add_systems(Update, animate_tiles.in_set(AppSystems::Update));
+}
+
+pub trait AnimatedTileAppExt {
+    /// Animates the tiles of the LDtk layer with this identifier that have animation custom data.
+    fn register_animated_tile_layer(&mut self, layer_identifier: impl Into<String>) -> &mut Self;
+}
+
+impl AnimatedTileAppExt for App {
+    fn register_animated_tile_layer(&mut self, layer_identifier: impl Into<String>) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<AnimatedTileLayers>()
+            .0
+            .insert(layer_identifier.into());
+        self
+    }
+}
+
+/// The identifiers of the layers whose tiles may be animated.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+struct AnimatedTileLayers(HashSet<String>);
+
+/// A tile's custom data in LDtk.
+#[derive(Deserialize, Debug, Clone, Copy)]
+struct TileAnimation {
+    frames: usize,
+    interval_ms: u64,
+}
+
+fn animate_tiles(
+    mut commands: Commands,
+    animated_layers: Res<AnimatedTileLayers>,
+    tiles: Query<(Entity, &TileMetadata, &TileTextureIndex, &ChildOf), Added<TileMetadata>>,
+    layers: Query<&LayerMetadata>,
+) {
+    for (tile, metadata, texture_index, layer) in &tiles {
+        let is_animated_layer = layers
+            .get(layer.parent())
+            .is_ok_and(|layer| animated_layers.0.contains(&layer.identifier));
+        if !is_animated_layer {
+            continue;
+        }
+
+        let animation = match ron::from_str::<TileAnimation>(&metadata.data) {
+            Ok(animation) => animation,
+            Err(error) => {
+                warn!(
+                    "Ignoring invalid tile animation {:?}: {error}",
+                    metadata.data
+                );
+                continue;
+            }
+        };
+        commands
+            .entity(tile)
+            .insert(Animation::new(vec![AnimationData {
+                frames: animation.frames,
+                interval: Duration::from_millis(animation.interval_ms),
+                state: AnimationState::Looping,
+                atlas_index: texture_index.0 as usize,
+                repeat: Repeat::Loop,
+            }]));
+    }
+}
@@ -5,52 +5,123 @@
 //! - [Timers](https://github.com/bevyengine/bevy/blob/latest/examples/time/timers.rs)
 
 use bevy::prelude::*;
+use bevy_ecs_tilemap::tiles::TileTextureIndex;
 use bevy_tnua::{
     TnuaAction,
     builtins::TnuaBuiltinJumpState,
     prelude::{TnuaBuiltinJump, TnuaBuiltinWalk, TnuaController},
 };
-use std::time::Duration;
+use rand::Rng;
+use std::{fmt, time::Duration};
 
 use crate::{
-    AppSystems, PausableSystems,
+    AppSystems, PausableSystems, Pause,
     audio::sound_effect,
-    game::player::{Dead, PlayerAssets},
+    game::{
+        ground_pound::GroundPounding,
+        player::{Dead, Look, MovementConfig, PlayerAssets, Stunned},
+    },
+    gameplay_frozen,
 };
 
 pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<FixedAnimationTimestep>();
+
     // Animate and play sound effects based on controls.
     app.add_systems(
         Update,
         (
-            update_animation_timer.in_set(AppSystems::TickTimers),
+            update_animation_timer
+                .run_if(not(fixed_animation_timestep))
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+            update_non_pausable_animation_timer.in_set(AppSystems::TickTimers),
             (
-                handle_animating,
+                (handle_animating, trigger_death_sound_effect).in_set(PausableSystems),
+                // Timers of pausable animations don't tick while paused,
+                // so this only changes non-pausable ones then.
                 update_animation_atlas,
-                trigger_death_sound_effect,
             )
                 .chain()
                 .in_set(AppSystems::Update),
-        )
-            .in_set(PausableSystems),
+        ),
+    );
+    app.add_systems(
+        FixedUpdate,
+        update_animation_timer.run_if(
+            fixed_animation_timestep
+                .and(in_state(Pause(false)))
+                .and(not(gameplay_frozen)),
+        ),
     );
 }
 
-/// Update the animation timer.
-fn update_animation_timer(time: Res<Time>, mut query: Query<&mut Animation>) {
+/// Whether pausable animations advance with the fixed timestep of the physics, instead of
+/// once per frame. This keeps them in step with the simulation regardless of the frame rate,
+/// e.g. so a ghost's replay looks the same as the run it was recorded from.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct FixedAnimationTimestep(pub bool);
+
+pub fn fixed_animation_timestep(fixed_timestep: Res<FixedAnimationTimestep>) -> bool {
+    fixed_timestep.0
+}
+
+/// Marks an [`Animation`] that keeps playing while the game is paused, e.g. in menus.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct NonPausable;
+
+/// Update the animation timer. In `FixedUpdate`, [`Time`] is the fixed clock.
+fn update_animation_timer(time: Res<Time>, mut query: Query<&mut Animation, Without<NonPausable>>) {
+    for mut animation in &mut query {
+        animation.update_timer(time.delta());
+    }
+}
+
+/// Update the timer of animations that keep playing while paused.
+fn update_non_pausable_animation_timer(
+    time: Res<Time<Real>>,
+    mut query: Query<&mut Animation, With<NonPausable>>,
+) {
     for mut animation in &mut query {
         animation.update_timer(time.delta());
     }
 }
 
 /// Update the texture atlas to reflect changes in the animation.
-fn update_animation_atlas(mut query: Query<(&Animation, &mut Sprite)>) {
-    for (animation, mut sprite) in &mut query {
-        let Some(atlas) = sprite.texture_atlas.as_mut() else {
+/// Level tiles aren't sprites, so they get their texture index updated instead.
+fn update_animation_atlas(
+    mut query: Query<(
+        &mut Animation,
+        Option<&Facing>,
+        Option<&mut Sprite>,
+        Option<&mut TileTextureIndex>,
+    )>,
+) {
+    for (mut animation, facing, mut sprite, tile_texture) in &mut query {
+        let facing = facing.copied().unwrap_or_default();
+        // Only write through `Mut` on an actual change, so sprites aren't marked as changed
+        // every frame.
+        if let Some(sprite) = sprite.as_mut() {
+            let flip_x = animation.facing_mode == FacingMode::Flip && facing == Facing::Left;
+            if sprite.flip_x != flip_x {
+                sprite.flip_x = flip_x;
+            }
+        }
+
+        let index = animation.facing_atlas_index(facing);
+        if animation.shown_index == Some(index) {
             continue;
-        };
-        if animation.changed() {
-            atlas.index = animation.get_atlas_index();
+        }
+        animation.shown_index = Some(index);
+        if let Some(mut sprite) = sprite
+            && let Some(atlas) = sprite.texture_atlas.as_mut()
+        {
+            atlas.index = index;
+        }
+        if let Some(mut tile_texture) = tile_texture {
+            tile_texture.0 = index as u32;
         }
     }
 }
@@ -76,6 +147,34 @@ pub struct Animation {
     current: usize,
     animations: Vec<AnimationData>,
     finished: bool,
+    /// The atlas index last applied by `update_animation_atlas`.
+    ///
+    /// Comparing against it doesn't depend on when or how often the timer ticked, so it works
+    /// the same with [`FixedAnimationTimestep`], where the timer can tick several times a frame
+    /// or not at all.
+    shown_index: Option<usize>,
+    facing_mode: FacingMode,
+    variants: Vec<AnimationVariants>,
+    /// The variant playing instead of the current animation, as an index into its
+    /// [`AnimationVariants::animations`].
+    variant: Option<usize>,
+    /// Counts down until the next variant plays.
+    variant_timer: Timer,
+    /// How fast the animation plays, `1.0` being its own pace.
+    speed: f32,
+}
+
+/// Animations that now and then play once instead of the loop of a state, after it went on
+/// for a while, e.g. a blink while idling.
+#[derive(Reflect)]
+pub struct AnimationVariants {
+    pub state: AnimationState,
+    /// How long the state has to go on before a variant plays. Every wait is up to twice as
+    /// long, so the variants don't come like clockwork.
+    pub delay: Duration,
+    /// One of these is picked at random each time. They always play once, whatever their
+    /// `repeat` is.
+    pub animations: Vec<AnimationData>,
 }
 
 #[derive(Reflect)]
@@ -87,13 +186,49 @@ pub struct AnimationData {
     pub repeat: Repeat,
 }
 
-#[derive(Clone, Copy, Reflect, PartialEq)]
+/// How a sprite sheet is cut into equally sized frames, which are numbered row by row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub struct AtlasGrid {
+    pub frame_size: UVec2,
+    pub columns: u32,
+    pub rows: u32,
+}
+
+impl AtlasGrid {
+    pub const fn new(frame_size: UVec2, columns: u32, rows: u32) -> Self {
+        Self {
+            frame_size,
+            columns,
+            rows,
+        }
+    }
+
+    pub fn layout(&self) -> TextureAtlasLayout {
+        TextureAtlasLayout::from_grid(self.frame_size, self.columns, self.rows, None, None)
+    }
+
+    /// The number of frames on the grid.
+    pub fn frame_count(&self) -> usize {
+        (self.columns * self.rows) as usize
+    }
+}
+
+#[derive(Clone, Copy, Debug, Reflect, PartialEq)]
 pub enum AnimationState {
     Walking,
     Idle,
     Falling,
     Jumping,
+    Hurt,
+    /// Standing still while holding up.
+    LookUp,
+    /// Standing still while holding down.
+    LookDown,
+    /// Slamming down in a ground pound.
+    GroundPound,
     Dying,
+    /// The only animation of something that just loops, like a level tile.
+    Looping,
 }
 
 #[derive(Clone, Copy, Reflect, PartialEq)]
@@ -102,7 +237,105 @@ pub enum Repeat {
     Loop,
 }
 
+/// Which way a character is facing, shown according to the [`FacingMode`] of its
+/// [`Animation`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub enum Facing {
+    Left,
+    #[default]
+    Right,
+}
+
+/// How an [`Animation`] shows which way the character is facing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum FacingMode {
+    /// The frames face right and are mirrored to face left, which only works for symmetric art.
+    #[default]
+    Flip,
+    /// Every frame has a counterpart facing left, `left_offset` frames further into the atlas,
+    /// e.g. the width of the grid when the left frames are in the row below.
+    Frames { left_offset: usize },
+}
+
+/// Why a list of [`AnimationData`] doesn't fit its sprite sheet.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnimationError {
+    /// There has to be at least one animation to start with.
+    Empty,
+    /// The animation has no frames at all.
+    NoFrames(AnimationState),
+    /// The animation's frames run past the end of the atlas.
+    OutOfRange {
+        state: AnimationState,
+        last_index: usize,
+        atlas_len: usize,
+    },
+    /// More than one animation is meant for the same state, so only the first would play.
+    DuplicateState(AnimationState),
+}
+
+impl fmt::Display for AnimationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnimationError::Empty => write!(f, "there are no animations"),
+            AnimationError::NoFrames(state) => write!(f, "the {state:?} animation has no frames"),
+            AnimationError::OutOfRange {
+                state,
+                last_index,
+                atlas_len,
+            } => write!(
+                f,
+                "the {state:?} animation goes up to frame {last_index}, but the atlas only has \
+                 {atlas_len} frames"
+            ),
+            AnimationError::DuplicateState(state) => {
+                write!(f, "there is more than one {state:?} animation")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnimationError {}
+
+/// Checks that every animation fits an atlas of `atlas_len` frames, and that no two are for
+/// the same state.
+pub fn check_animations(
+    animations: &[AnimationData],
+    atlas_len: usize,
+) -> Result<(), AnimationError> {
+    if animations.is_empty() {
+        return Err(AnimationError::Empty);
+    }
+    for (i, animation) in animations.iter().enumerate() {
+        check_frames(animation, atlas_len)?;
+        if animations[..i]
+            .iter()
+            .any(|other| other.state == animation.state)
+        {
+            return Err(AnimationError::DuplicateState(animation.state));
+        }
+    }
+    Ok(())
+}
+
+fn check_frames(animation: &AnimationData, atlas_len: usize) -> Result<(), AnimationError> {
+    if animation.frames == 0 {
+        return Err(AnimationError::NoFrames(animation.state));
+    }
+    let last_index = animation.atlas_index + animation.frames - 1;
+    if last_index >= atlas_len {
+        return Err(AnimationError::OutOfRange {
+            state: animation.state,
+            last_index,
+            atlas_len,
+        });
+    }
+    Ok(())
+}
+
 impl Animation {
+    /// Trusts that the animations fit the sprite sheet, prefer [`Self::try_new`].
     pub fn new(animations: Vec<AnimationData>) -> Self {
         Self {
             timer: Timer::new(animations[0].interval, TimerMode::Repeating),
@@ -110,47 +343,205 @@ impl Animation {
             current: 0,
             animations,
             finished: false,
+            shown_index: None,
+            variants: Vec::new(),
+            variant: None,
+            variant_timer: Timer::default(),
+            facing_mode: FacingMode::Flip,
+            speed: 1.0,
+        }
+    }
+
+    /// Like [`Self::new`], but makes sure the animations fit an atlas of `atlas_len` frames
+    /// first, see [`check_animations`].
+    pub fn try_new(
+        animations: Vec<AnimationData>,
+        atlas_len: usize,
+    ) -> Result<Self, AnimationError> {
+        check_animations(&animations, atlas_len)?;
+        Ok(Self::new(animations))
+    }
+
+    /// Adds variants to break up the loops of some states, after making sure they fit an atlas
+    /// of `atlas_len` frames as well.
+    pub fn try_with_variants(
+        mut self,
+        variants: Vec<AnimationVariants>,
+        atlas_len: usize,
+    ) -> Result<Self, AnimationError> {
+        for variant in &variants {
+            for animation in &variant.animations {
+                check_frames(animation, atlas_len)?;
+            }
+        }
+        self.variants = variants;
+        self.reset_variant_timer();
+        Ok(self)
+    }
+
+    /// Shows which way the character is facing with `facing_mode`, after making sure the
+    /// frames facing left fit an atlas of `atlas_len` frames as well.
+    pub fn try_with_facing(
+        mut self,
+        facing_mode: FacingMode,
+        atlas_len: usize,
+    ) -> Result<Self, AnimationError> {
+        self.check_facing(facing_mode, atlas_len)?;
+        self.facing_mode = facing_mode;
+        Ok(self)
+    }
+
+    /// Assumes the frames themselves were already checked.
+    fn check_facing(
+        &self,
+        facing_mode: FacingMode,
+        atlas_len: usize,
+    ) -> Result<(), AnimationError> {
+        if let FacingMode::Frames { left_offset } = facing_mode {
+            let variants = self
+                .variants
+                .iter()
+                .flat_map(|variants| &variants.animations);
+            for animation in self.animations.iter().chain(variants) {
+                let last_index = animation.atlas_index + animation.frames - 1 + left_offset;
+                if last_index >= atlas_len {
+                    return Err(AnimationError::OutOfRange {
+                        state: animation.state,
+                        last_index,
+                        atlas_len,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether everything the animation can show fits an atlas of `atlas_len` frames, e.g.
+    /// after its sprite sheet changed.
+    pub fn check(&self, atlas_len: usize) -> Result<(), AnimationError> {
+        check_animations(&self.animations, atlas_len)?;
+        for animation in self
+            .variants
+            .iter()
+            .flat_map(|variants| &variants.animations)
+        {
+            check_frames(animation, atlas_len)?;
         }
+        self.check_facing(self.facing_mode, atlas_len)
+    }
+
+    /// Just the first frame of the sprite sheet, for when the real animations don't fit it.
+    pub fn still(state: AnimationState) -> Self {
+        Self::new(vec![AnimationData {
+            frames: 1,
+            interval: Duration::from_secs(1),
+            state,
+            atlas_index: 0,
+            repeat: Repeat::OneShot,
+        }])
+    }
+
+    /// Plays the animation faster or slower, e.g. to keep a walk cycle in step with the
+    /// player's speed.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
     }
 
     /// Update animation timers.
     pub fn update_timer(&mut self, delta: Duration) {
-        self.timer.tick(delta);
-        if !self.timer.is_finished() {
+        self.advance(delta.mul_f32(self.speed), delta);
+    }
+
+    /// Moves the frames along by `frame_delta`, and the wait for the next variant by `delta`.
+    fn advance(&mut self, frame_delta: Duration, delta: Duration) {
+        self.timer.tick(frame_delta);
+        if self.timer.is_finished() {
+            let frames = self.data().frames;
+            if self.variant.is_some() {
+                if self.frame + 1 >= frames {
+                    self.stop_variant();
+                } else {
+                    self.frame += 1;
+                }
+            } else if self.animations[self.current].repeat == Repeat::Loop {
+                self.frame = (self.frame + 1) % frames;
+            } else if self.frame + 1 >= frames {
+                self.finished = true;
+            } else {
+                self.frame += 1;
+            }
+        }
+        self.update_variant_timer(delta);
+    }
+
+    /// The animation being played, which may be a variant of the current one.
+    fn data(&self) -> &AnimationData {
+        match (self.variant, self.current_variants()) {
+            (Some(index), Some(variants)) => &variants.animations[index],
+            _ => &self.animations[self.current],
+        }
+    }
+
+    fn current_variants(&self) -> Option<&AnimationVariants> {
+        let state = self.state();
+        self.variants
+            .iter()
+            .find(|variants| variants.state == state)
+    }
+
+    fn update_variant_timer(&mut self, delta: Duration) {
+        if self.variant.is_some() {
             return;
         }
-        if self.animations[self.current].repeat == Repeat::Loop {
-            self.frame = (self.frame + 1) % self.animations[self.current].frames;
-        } else if self.frame + 1 >= self.animations[self.current].frames {
-            self.finished = true;
-        } else {
-            self.frame += 1;
+        let count = self
+            .current_variants()
+            .map_or(0, |variants| variants.animations.len());
+        if count == 0 {
+            return;
+        }
+        self.variant_timer.tick(delta);
+        if self.variant_timer.is_finished() {
+            self.variant = Some(rand::rng().random_range(0..count));
+            self.frame = 0;
+            self.timer = Timer::new(self.data().interval, TimerMode::Repeating);
+        }
+    }
+
+    fn stop_variant(&mut self) {
+        self.variant = None;
+        self.frame = 0;
+        self.timer = Timer::new(self.animations[self.current].interval, TimerMode::Repeating);
+        self.reset_variant_timer();
+    }
+
+    fn reset_variant_timer(&mut self) {
+        if let Some(variants) = self.current_variants() {
+            let delay = variants.delay.mul_f32(rand::rng().random_range(1.0..2.0));
+            self.variant_timer = Timer::new(delay, TimerMode::Once);
         }
     }
 
-    /// Update animation state if it changes.
+    /// Update animation state if it changes, staying on the current animation if there's none
+    /// for `state`.
     pub fn update_state(&mut self, state: AnimationState) {
         if self.state() != state {
-            self.current = self
-                .animations
-                .iter()
-                .position(|a| a.state == state)
-                .unwrap();
+            let Some(current) = self.animations.iter().position(|a| a.state == state) else {
+                return;
+            };
+            self.current = current;
+            self.variant = None;
+            self.reset_variant_timer();
 
             let data = &self.animations[self.current];
 
             self.finished = false;
             self.timer = Timer::new(data.interval, TimerMode::Repeating);
             self.frame = 0;
-            self.update_timer(self.timer.remaining());
+            let remaining = self.timer.remaining();
+            self.advance(remaining, remaining);
         }
     }
 
-    /// Whether animation changed this tick.
-    pub fn changed(&self) -> bool {
-        self.timer.is_finished()
-    }
-
     pub fn is_finished(&self) -> bool {
         self.finished
     }
@@ -161,18 +552,72 @@ impl Animation {
 
     /// Return sprite index in the atlas.
     pub fn get_atlas_index(&self) -> usize {
-        self.animations[self.current].atlas_index + self.frame
+        self.data().atlas_index + self.frame
+    }
+
+    /// The sprite index in the atlas for a character facing `facing`.
+    pub fn facing_atlas_index(&self, facing: Facing) -> usize {
+        match (self.facing_mode, facing) {
+            (FacingMode::Frames { left_offset }, Facing::Left) => {
+                self.get_atlas_index() + left_offset
+            }
+            _ => self.get_atlas_index(),
+        }
     }
 }
 
-fn handle_animating(mut player_query: Query<(&TnuaController, &mut Animation, Has<Dead>)>) {
-    let Ok((controller, mut player_animation, is_dead)) = player_query.single_mut() else {
-        return;
-    };
+/// The walk cycle plays faster the faster the player moves, so running doesn't look like
+/// sliding along.
+fn handle_animating(
+    movement: Res<MovementConfig>,
+    mut player_query: Query<(
+        &TnuaController,
+        &mut Animation,
+        &Look,
+        Has<Dead>,
+        Has<Stunned>,
+        Has<GroundPounding>,
+    )>,
+) {
+    for (controller, mut player_animation, look, is_dead, is_stunned, is_pounding) in
+        &mut player_query
+    {
+        if let Some(state) = animation_state(controller, *look, is_dead, is_stunned, is_pounding) {
+            player_animation.update_state(state);
+        }
+
+        let speed = match player_animation.state() {
+            AnimationState::Walking => controller
+                .concrete_basis::<TnuaBuiltinWalk>()
+                .map(|(_, state)| state.running_velocity.length() / movement.walk_speed)
+                .unwrap_or(1.0)
+                .clamp(MIN_WALK_ANIMATION_SPEED, MAX_WALK_ANIMATION_SPEED),
+            _ => 1.0,
+        };
+        player_animation.set_speed(speed);
+    }
+}
+
+/// The slowest and fastest the walk cycle plays, compared to its pace at walking speed.
+const MIN_WALK_ANIMATION_SPEED: f32 = 0.5;
+const MAX_WALK_ANIMATION_SPEED: f32 = 2.0;
 
+/// Picks the animation for the player's movement, or `None` if it should stay as it is.
+fn animation_state(
+    controller: &TnuaController,
+    look: Look,
+    is_dead: bool,
+    is_stunned: bool,
+    is_pounding: bool,
+) -> Option<AnimationState> {
     if is_dead {
-        player_animation.update_state(AnimationState::Dying);
-        return;
+        return Some(AnimationState::Dying);
+    }
+    if is_stunned {
+        return Some(AnimationState::Hurt);
+    }
+    if is_pounding {
+        return Some(AnimationState::GroundPound);
     }
 
     let current_status_for_animating = match controller.action_name() {
@@ -181,7 +626,7 @@ fn handle_animating(mut player_query: Query<(&TnuaController, &mut Animation, Ha
                 .concrete_action::<TnuaBuiltinJump>()
                 .expect("action name mismatch");
             match jump_state {
-                TnuaBuiltinJumpState::NoJump => return,
+                TnuaBuiltinJumpState::NoJump => return None,
                 TnuaBuiltinJumpState::StartingJump { .. } => AnimationState::Jumping,
                 TnuaBuiltinJumpState::SlowDownTooFastSlopeJump { .. } => AnimationState::Jumping,
                 TnuaBuiltinJumpState::MaintainingJump { .. } => AnimationState::Jumping,
@@ -193,9 +638,7 @@ fn handle_animating(mut player_query: Query<(&TnuaController, &mut Animation, Ha
         None => {
             // If there is no action going on, we'll base the animation on the state of the
             // basis.
-            let Some((_, basis_state)) = controller.concrete_basis::<TnuaBuiltinWalk>() else {
-                return;
-            };
+            let (_, basis_state) = controller.concrete_basis::<TnuaBuiltinWalk>()?;
             if basis_state.standing_on_entity().is_none() {
                 AnimationState::Falling
             } else {
@@ -203,11 +646,94 @@ fn handle_animating(mut player_query: Query<(&TnuaController, &mut Animation, Ha
                 if 0.01 < speed.length() {
                     AnimationState::Walking
                 } else {
-                    AnimationState::Idle
+                    // Looking only replaces idling, never jumping or falling.
+                    match look {
+                        Look::Ahead => AnimationState::Idle,
+                        Look::Up => AnimationState::LookUp,
+                        Look::Down => AnimationState::LookDown,
+                    }
                 }
             }
         }
     };
 
-    player_animation.update_state(current_status_for_animating);
+    Some(current_status_for_animating)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::test_app;
+
+    const INTERVAL: Duration = Duration::from_millis(100);
+
+    fn animation(frames: usize, repeat: Repeat) -> Animation {
+        Animation::new(vec![AnimationData {
+            frames,
+            interval: INTERVAL,
+            state: AnimationState::Idle,
+            atlas_index: 4,
+            repeat,
+        }])
+    }
+
+    #[test]
+    fn moves_on_once_the_interval_passed() {
+        let mut animation = animation(3, Repeat::Loop);
+        animation.update_timer(INTERVAL / 2);
+        assert_eq!(animation.get_atlas_index(), 4);
+        animation.update_timer(INTERVAL / 2);
+        assert_eq!(animation.get_atlas_index(), 5);
+    }
+
+    #[test]
+    fn loops_back_to_the_first_frame() {
+        let mut animation = animation(3, Repeat::Loop);
+        for _ in 0..3 {
+            animation.update_timer(INTERVAL);
+        }
+        assert_eq!(animation.get_atlas_index(), 4);
+        assert!(!animation.is_finished());
+    }
+
+    #[test]
+    fn one_shot_stops_on_the_last_frame() {
+        let mut animation = animation(3, Repeat::OneShot);
+        for _ in 0..5 {
+            animation.update_timer(INTERVAL);
+        }
+        assert_eq!(animation.get_atlas_index(), 6);
+        assert!(animation.is_finished());
+    }
+
+    #[test]
+    fn speed_scales_the_interval() {
+        let mut animation = animation(3, Repeat::Loop);
+        animation.set_speed(2.0);
+        animation.update_timer(INTERVAL / 2);
+        assert_eq!(animation.get_atlas_index(), 5);
+    }
+
+    #[test]
+    fn sprites_only_change_with_their_frame() {
+        let mut app = test_app();
+        app.add_systems(Update, update_animation_atlas);
+        let entity = app
+            .world_mut()
+            .spawn((
+                Animation::still(AnimationState::Idle),
+                Sprite {
+                    texture_atlas: Some(TextureAtlas::default()),
+                    ..default()
+                },
+            ))
+            .id();
+
+        app.update();
+        app.world_mut().clear_trackers();
+        app.update();
+
+        let sprite = app.world().entity(entity).get_ref::<Sprite>().unwrap();
+        assert!(!sprite.is_changed());
+    }
 }
@@ -0,0 +1,96 @@
+//! Rebuilds the texture atlases of animated sprites when their sprite sheet changes on disk.
+//!
+//! Bevy's hot reloading already swaps in the new pixels, but the atlas layout is built once
+//! from the old size of the sheet. When a sheet grows or shrinks, this rebuilds the layout on
+//! the same frame size, so frames added to it show up without restarting. The animations
+//! themselves are defined in code and keep playing where they were.
+//!
+//! Frames are numbered row by row, so adding columns moves every frame after the first row.
+//! Adding rows at the bottom keeps the existing frames where they are.
+
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+
+use crate::{AppSystems, game::animation::Animation};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, rebuild_changed_atlases.in_set(AppSystems::Update));
+}
+
+/// Sheets whose size didn't change are left alone, along with the animations on them.
+///
+/// Sprites that share a layout keep sharing it: each changed layout is rebuilt once, in place,
+/// so sprites spawned from its handle later get the new grid too. It's only rebuilt if every
+/// animation on it still fits.
+fn rebuild_changed_atlases(
+    mut image_events: MessageReader<AssetEvent<Image>>,
+    images: Res<Assets<Image>>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    sprites: Query<(&Sprite, &Animation)>,
+) {
+    let modified: HashSet<AssetId<Image>> = image_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    if modified.is_empty() {
+        return;
+    }
+
+    // The grid each changed layout should get, or `None` once an animation doesn't fit it.
+    let mut rebuilt: HashMap<AssetId<TextureAtlasLayout>, Option<(UVec2, UVec2)>> = HashMap::new();
+    for (sprite, animation) in &sprites {
+        let image_id = sprite.image.id();
+        if !modified.contains(&image_id) {
+            continue;
+        }
+        let Some(image) = images.get(image_id) else {
+            continue;
+        };
+        let Some(atlas) = sprite.texture_atlas.as_ref() else {
+            continue;
+        };
+        let layout_id = atlas.layout.id();
+        let Some(layout) = texture_atlas_layouts.get(layout_id) else {
+            continue;
+        };
+        let Some(frame_size) = layout.textures.first().map(URect::size) else {
+            continue;
+        };
+
+        let old_grid = layout.size / frame_size;
+        let new_grid = image.size() / frame_size;
+        if new_grid == old_grid {
+            continue;
+        }
+        let grid = rebuilt
+            .entry(layout_id)
+            .or_insert(Some((frame_size, new_grid)));
+        if grid.is_none() {
+            continue;
+        }
+        let atlas_len = (new_grid.x * new_grid.y) as usize;
+        if let Err(error) = animation.check(atlas_len) {
+            error!("Keeping the old atlas of a changed sprite sheet: {error}");
+            *grid = None;
+        }
+    }
+
+    for (layout_id, grid) in rebuilt {
+        let Some((frame_size, grid)) = grid else {
+            continue;
+        };
+        let Some(layout) = texture_atlas_layouts.get_mut(layout_id) else {
+            continue;
+        };
+        info!(
+            "Rebuilding the atlas of a changed sprite sheet with {} columns and {} rows",
+            grid.x, grid.y
+        );
+        *layout = TextureAtlasLayout::from_grid(frame_size, grid.x, grid.y, None, None);
+    }
+}
@@ -0,0 +1,64 @@
+//! An assist mode that makes the game easier without changing how the players move.
+//!
+//! Unlike a global time scale, only the hazards slow down, while the players get more
+//! forgiving timing windows for their jumps. It's stored in the [`SaveData`].
+
+use bevy::prelude::*;
+
+use crate::save::SaveData;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<AssistMode>();
+    app.add_systems(Startup, load_assist_mode);
+    app.add_systems(
+        Update,
+        save_assist_mode.run_if(resource_changed::<AssistMode>),
+    );
+}
+
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct AssistMode {
+    pub enabled: bool,
+    /// How fast moving hazards like enemies, turrets and their projectiles are while enabled,
+    /// compared to their normal speed.
+    pub hazard_speed: f32,
+    /// How much longer the coyote time and jump buffer are while enabled.
+    pub timing_windows: f32,
+}
+
+impl Default for AssistMode {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hazard_speed: 0.6,
+            timing_windows: 1.5,
+        }
+    }
+}
+
+impl AssistMode {
+    /// The factor to scale hazard speeds and timers by.
+    pub fn hazard_speed(&self) -> f32 {
+        if self.enabled { self.hazard_speed } else { 1.0 }
+    }
+
+    /// The factor to scale the players' timing windows by.
+    pub fn timing_windows(&self) -> f32 {
+        if self.enabled {
+            self.timing_windows
+        } else {
+            1.0
+        }
+    }
+}
+
+fn load_assist_mode(save_data: Res<SaveData>, mut assist_mode: ResMut<AssistMode>) {
+    assist_mode.enabled = save_data.assist_mode;
+}
+
+fn save_assist_mode(assist_mode: Res<AssistMode>, mut save_data: ResMut<SaveData>) {
+    if save_data.assist_mode != assist_mode.enabled {
+        save_data.assist_mode = assist_mode.enabled;
+    }
+}
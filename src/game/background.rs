@@ -0,0 +1,127 @@
+//! Draws the background color and image of each LDtk level behind its layers.
+//!
+//! The image is placed, cropped and scaled the way LDtk shows it. With a float level field
+//! `BackgroundParallax` it also moves along with the camera, `0.0` keeping it fixed to the
+//! level and `1.0` fixing it to the camera.
+
+use bevy::{prelude::*, sprite::Anchor};
+use bevy_ecs_ldtk::{
+    LevelIid,
+    assets::{LdtkProject, LdtkProjectHandle},
+    prelude::{LdtkFields, RawLevelAccessor},
+};
+
+use crate::{AppSystems, game::level::loaded_project};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (spawn_level_backgrounds, scroll_parallax_backgrounds)
+            .chain()
+            .in_set(AppSystems::Update),
+    );
+}
+
+/// Below all of the level's layers, which start at 0.
+const BACKGROUND_COLOR_Z: f32 = -2.0;
+const BACKGROUND_IMAGE_Z: f32 = -1.0;
+
+/// A level's background image that moves with the camera.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+struct ParallaxBackground {
+    factor: f32,
+    /// Where the image sits in the level while the camera looks at the level's center.
+    origin: Vec2,
+    level_size: Vec2,
+}
+
+fn spawn_level_backgrounds(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    levels: Query<(Entity, &LevelIid), Added<LevelIid>>,
+    ldtk_projects: Query<&LdtkProjectHandle>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+) {
+    if levels.is_empty() {
+        return;
+    }
+    let ldtk_project = match loaded_project(&ldtk_projects, &ldtk_project_assets) {
+        Ok(ldtk_project) => ldtk_project,
+        Err(error) => {
+            warn!("Can't spawn the level backgrounds: {error}");
+            return;
+        }
+    };
+
+    for (level_entity, level_iid) in &levels {
+        let Some(level) = ldtk_project.get_raw_level_by_iid(&level_iid.to_string()) else {
+            continue;
+        };
+        let level_size = Vec2::new(level.px_wid as f32, level.px_hei as f32);
+
+        commands.entity(level_entity).with_child((
+            Name::new("Level Background Color"),
+            Sprite::from_color(level.bg_color, level_size),
+            Anchor::BOTTOM_LEFT,
+            Transform::from_xyz(0.0, 0.0, BACKGROUND_COLOR_Z),
+        ));
+
+        let (Some(path), Some(position)) = (&level.bg_rel_path, &level.bg_pos) else {
+            continue;
+        };
+        // LDtk measures from the level's top left corner, with y pointing down.
+        let crop = Rect::new(
+            position.crop_rect[0] as f32,
+            position.crop_rect[1] as f32,
+            (position.crop_rect[0] + position.crop_rect[2]) as f32,
+            (position.crop_rect[1] + position.crop_rect[3]) as f32,
+        );
+        let scale = Vec2::new(position.scale[0] as f32, position.scale[1] as f32);
+        let origin = Vec2::new(
+            position.top_left_px[0] as f32,
+            level_size.y - position.top_left_px[1] as f32,
+        );
+
+        let mut background = commands.spawn((
+            Name::new("Level Background Image"),
+            Sprite {
+                // Relative to the LDtk project, which sits at the root of the assets.
+                image: asset_server.load(path),
+                rect: Some(crop),
+                custom_size: Some(crop.size() * scale),
+                ..default()
+            },
+            Anchor::TOP_LEFT,
+            Transform::from_translation(origin.extend(BACKGROUND_IMAGE_Z)),
+            ChildOf(level_entity),
+        ));
+        let factor = level
+            .get_float_field("BackgroundParallax")
+            .copied()
+            .unwrap_or(0.0);
+        if factor != 0.0 {
+            background.insert(ParallaxBackground {
+                factor,
+                origin,
+                level_size,
+            });
+        }
+    }
+}
+
+fn scroll_parallax_backgrounds(
+    camera: Single<&GlobalTransform, With<Camera2d>>,
+    levels: Query<&GlobalTransform, With<LevelIid>>,
+    backgrounds: Query<(&ParallaxBackground, &ChildOf, &mut Transform)>,
+) {
+    let camera_position = camera.translation().truncate();
+    for (background, child_of, mut transform) in backgrounds {
+        let Ok(level_transform) = levels.get(child_of.parent()) else {
+            continue;
+        };
+        let level_center = level_transform.translation().truncate() + background.level_size / 2.0;
+        let offset = (camera_position - level_center) * background.factor;
+        transform.translation = (background.origin + offset).extend(transform.translation.z);
+    }
+}
@@ -0,0 +1,106 @@
+//! Trampolines that launch the player upwards when landed on.
+//!
+//! In LDtk these are `Bouncer` entities with an optional float field `Strength`.
+
+use avian2d::prelude::LinearVelocity;
+use bevy::prelude::*;
+use bevy_ecs_ldtk::{EntityInstance, LdtkEntity, app::LdtkEntityAppExt, prelude::LdtkFields};
+
+use crate::{
+    asset_tracking::LoadResource,
+    audio::{SoundKind, sound_effect},
+    game::{
+        colliders::ColliderBundle,
+        draw_order::DrawLayer,
+        gravity_flip::GravityFlip,
+        ground_pound::{GroundPoundConfig, GroundPounding},
+        player::{Player, PlayerLanded},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.load_resource::<BouncerAssets>();
+    app.register_ldtk_entity::<BouncerBundle>("Bouncer");
+    app.add_observer(bounce_player);
+}
+
+/// Launches a player that lands on it upwards with `strength` as the new vertical velocity.
+#[derive(Clone, Copy, Debug, Component, Reflect)]
+#[reflect(Component)]
+#[require(DrawLayer::Background)]
+pub struct Bouncer {
+    pub strength: f32,
+}
+
+impl Default for Bouncer {
+    fn default() -> Self {
+        // Well above the ~35 pixels of a regular jump.
+        Self { strength: 250.0 }
+    }
+}
+
+#[derive(Clone, Debug, Default, Bundle, LdtkEntity)]
+pub struct BouncerBundle {
+    #[with(bouncer_from_instance)]
+    bouncer: Bouncer,
+
+    #[sprite_sheet]
+    sprite_sheet: Sprite,
+
+    #[from_entity_instance]
+    collider_bundle: ColliderBundle,
+}
+
+fn bouncer_from_instance(instance: &EntityInstance) -> Bouncer {
+    instance
+        .get_float_field("Strength")
+        .map(|&strength| Bouncer { strength })
+        .unwrap_or_default()
+}
+
+/// Bounces players on the tick they land on a bouncer, higher when they ground pound onto it.
+/// Only the landing counts, so standing on it for a few ticks doesn't bounce twice.
+fn bounce_player(
+    event: On<PlayerLanded>,
+    mut commands: Commands,
+    bouncer_assets: If<Res<BouncerAssets>>,
+    ground_pound: Res<GroundPoundConfig>,
+    bouncers: Query<&Bouncer>,
+    mut players: Query<(&mut LinearVelocity, &GravityFlip, Has<GroundPounding>), With<Player>>,
+) {
+    let Ok(bouncer) = bouncers.get(event.ground_entity) else {
+        return;
+    };
+    let Ok((mut velocity, gravity_flip, pounding)) = players.get_mut(event.entity) else {
+        return;
+    };
+
+    let strength = if pounding {
+        bouncer.strength * ground_pound.bounce_multiplier
+    } else {
+        bouncer.strength
+    };
+    velocity.y = strength * gravity_flip.sign();
+    commands.spawn((
+        Name::new("Bounce Sound"),
+        sound_effect(bouncer_assets.boing.clone()),
+        SoundKind::Impact,
+    ));
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct BouncerAssets {
+    #[dependency]
+    pub boing: Handle<AudioSource>,
+}
+
+impl FromWorld for BouncerAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            // There is no bounce sound of its own yet, so it borrows the jump.
+            boing: assets.load("audio/sound_effects/jump.ogg"),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Keeps the camera inside the current level.
+//!
+//! When the level changes, the bounds move from the old level's to the new one's over
+//! [`CameraBoundsTransition`] instead of snapping there.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_ecs_ldtk::{
+    LevelIid,
+    assets::{LdtkProject, LdtkProjectHandle},
+    prelude::RawLevelAccessor,
+};
+
+use crate::{
+    AppSystems,
+    game::{accessibility::ReducedMotion, level::LevelChanged},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<CameraBoundsTransition>();
+    app.init_resource::<CameraBounds>();
+    app.add_observer(start_bounds_transition);
+    app.add_systems(OnExit(Screen::Gameplay), clear_camera_bounds);
+    app.add_systems(
+        Update,
+        tick_bounds_transition.in_set(AppSystems::TickTimers),
+    );
+    app.add_systems(Update, track_level_bounds.in_set(AppSystems::Update));
+}
+
+/// How long the camera bounds take to move to a new level.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct CameraBoundsTransition(pub Duration);
+
+impl Default for CameraBoundsTransition {
+    fn default() -> Self {
+        Self(Duration::from_millis(600))
+    }
+}
+
+/// The area of the world the camera may show.
+#[derive(Resource, Debug, Clone, PartialEq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct CameraBounds {
+    /// The bounds at the moment the level changed.
+    previous: Option<Rect>,
+    /// The bounds of the current level, once it has spawned.
+    current: Option<Rect>,
+    /// The level [`Self::current`] belongs to.
+    level: Option<String>,
+    /// The level that was left, which lingers for a moment after the change.
+    left_level: Option<String>,
+    transition: Timer,
+}
+
+impl CameraBounds {
+    /// The bounds to clamp the camera to right now, part way between the old and the new
+    /// level while transitioning.
+    pub fn active(&self) -> Option<Rect> {
+        let Some(current) = self.current else {
+            return self.previous;
+        };
+        let Some(previous) = self.previous else {
+            return Some(current);
+        };
+        let t = EaseFunction::SmoothStep.sample_clamped(self.transition.fraction());
+        Some(Rect {
+            min: previous.min.lerp(current.min, t),
+            max: previous.max.lerp(current.max, t),
+        })
+    }
+}
+
+/// Starts moving away from the bounds of the level that was just left.
+fn start_bounds_transition(
+    _: On<LevelChanged>,
+    mut bounds: ResMut<CameraBounds>,
+    duration: Res<CameraBoundsTransition>,
+    reduced_motion: Res<ReducedMotion>,
+) {
+    if reduced_motion.0 {
+        *bounds = CameraBounds::default();
+        return;
+    }
+    bounds.previous = bounds.active();
+    bounds.current = None;
+    bounds.left_level = bounds.level.take();
+    bounds.transition = Timer::new(duration.0, TimerMode::Once);
+}
+
+fn tick_bounds_transition(time: Res<Time>, mut bounds: ResMut<CameraBounds>) {
+    // Wait for the new level to spawn before moving towards it.
+    if bounds.current.is_none() {
+        return;
+    }
+    bounds.transition.tick(time.delta());
+    if bounds.transition.is_finished() {
+        bounds.previous = None;
+    }
+}
+
+fn track_level_bounds(
+    mut bounds: ResMut<CameraBounds>,
+    level: Option<Single<(&LevelIid, &GlobalTransform)>>,
+    ldtk_project: Option<Single<&LdtkProjectHandle>>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+) {
+    let (Some(level), Some(ldtk_project)) = (level, ldtk_project) else {
+        return;
+    };
+    let (level_iid, level_transform) = *level;
+    let level_iid = level_iid.to_string();
+    if bounds.level.as_ref() == Some(&level_iid) || bounds.left_level.as_ref() == Some(&level_iid) {
+        return;
+    }
+    let Some(level_data) = ldtk_project_assets
+        .get(*ldtk_project)
+        .and_then(|project| project.get_raw_level_by_iid(&level_iid))
+    else {
+        return;
+    };
+
+    let min = level_transform.translation().truncate();
+    let size = Vec2::new(level_data.px_wid as f32, level_data.px_hei as f32);
+    bounds.current = Some(Rect::from_corners(min, min + size));
+    bounds.level = Some(level_iid);
+}
+
+fn clear_camera_bounds(mut bounds: ResMut<CameraBounds>) {
+    *bounds = CameraBounds::default();
+}
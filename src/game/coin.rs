@@ -0,0 +1,228 @@
+//! Coins to collect along the way, and records of the most that were found.
+//!
+//! In LDtk these are `Coin` entities.
+//!
+//! The coins of a level only count once it's left, so dying gives them all back.
+//! The best count of each level, and of a whole run, is kept in the [`SaveData`].
+
+use std::collections::BTreeMap;
+
+use avian2d::prelude::{CollisionStart, Sensor};
+use bevy::{
+    ecs::{lifecycle::HookContext, world::DeferredWorld},
+    prelude::*,
+};
+use bevy_ecs_ldtk::{
+    LdtkEntity, LevelSelection,
+    app::LdtkEntityAppExt,
+    assets::{LdtkProject, LdtkProjectHandle},
+};
+
+use crate::{
+    AppSystems,
+    asset_tracking::LoadResource,
+    audio::{SoundKind, sound_effect},
+    game::{
+        colliders::ColliderBundle,
+        draw_order::DrawLayer,
+        level::{LevelChanged, ResetLevel, selected_level},
+        player::{Dead, Player},
+    },
+    save::SaveData,
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.load_resource::<CoinAssets>();
+    app.register_ldtk_entity::<CoinBundle>("Coin");
+    app.init_resource::<RunCoins>();
+    app.add_observer(reset_coins);
+    app.add_observer(finish_level_coins);
+    app.add_systems(OnEnter(Screen::Title), reset_run_coins);
+    app.add_systems(OnEnter(Screen::Victory), record_coins);
+    app.add_systems(
+        Update,
+        track_coin_level
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+#[require(DrawLayer::Background)]
+#[component(on_add = on_coin_add)]
+pub struct Coin;
+
+pub fn on_coin_add(mut world: DeferredWorld, context: HookContext) {
+    let coin_entity = context.entity;
+    world
+        .commands()
+        .entity(coin_entity)
+        .observe(on_player_touched_coin);
+}
+
+/// A coin that was picked up during the current attempt.
+/// It stays around hidden, so restarting the level can bring it back.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct Collected;
+
+#[derive(Clone, Debug, Default, Bundle, LdtkEntity)]
+pub struct CoinBundle {
+    coin: Coin,
+
+    #[sprite_sheet]
+    sprite_sheet: Sprite,
+
+    #[from_entity_instance]
+    collider_bundle: ColliderBundle,
+
+    sensor: Sensor,
+}
+
+/// The coins found during the current run.
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource)]
+pub struct RunCoins {
+    /// The position of the current level in the LDtk project, once it's known.
+    level: Option<usize>,
+    /// Coins picked up in the current attempt at the current level.
+    pub current: usize,
+    /// Coins found in each level that was left, by the level's position.
+    pub levels: BTreeMap<usize, usize>,
+    /// Filled in once the game is won, for the victory screen.
+    pub summary: Option<CoinSummary>,
+}
+
+impl RunCoins {
+    pub fn total(&self) -> usize {
+        self.levels.values().sum()
+    }
+
+    fn finish_level(&mut self) {
+        if let Some(level) = self.level.take() {
+            let found = self.levels.entry(level).or_default();
+            *found = (*found).max(self.current);
+        }
+        self.current = 0;
+    }
+}
+
+/// The coins of a finished run next to the records from before it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Reflect)]
+pub struct CoinSummary {
+    /// The position of each level, the coins found in it, and its previous record.
+    pub levels: Vec<(usize, usize, Option<usize>)>,
+    pub total: usize,
+    pub previous_best_total: Option<usize>,
+}
+
+fn on_player_touched_coin(
+    event: On<CollisionStart>,
+    mut commands: Commands,
+    coin_assets: Res<CoinAssets>,
+    mut run_coins: ResMut<RunCoins>,
+    collected: Query<(), With<Collected>>,
+    players: Query<(), (With<Player>, Without<Dead>)>,
+) {
+    let coin_entity = event.collider1;
+    if collected.contains(coin_entity) || !players.contains(event.collider2) {
+        return;
+    }
+
+    run_coins.current += 1;
+    commands
+        .entity(coin_entity)
+        .insert((Collected, Visibility::Hidden));
+    commands.spawn((
+        Name::new("Coin Sound"),
+        sound_effect(coin_assets.pickup.clone()),
+        SoundKind::Pickup,
+    ));
+}
+
+/// Puts collected coins back, and takes them away from the count again.
+fn reset_coins(
+    _: On<ResetLevel>,
+    mut commands: Commands,
+    mut run_coins: ResMut<RunCoins>,
+    coins: Query<Entity, With<Collected>>,
+) {
+    run_coins.current = 0;
+    for coin in coins {
+        commands
+            .entity(coin)
+            .remove::<Collected>()
+            .insert(Visibility::Inherited);
+    }
+}
+
+/// The project may still be loading when the level starts, so the level is looked up until
+/// it's found.
+fn track_coin_level(
+    mut run_coins: ResMut<RunCoins>,
+    level_selection: Res<LevelSelection>,
+    ldtk_project: Option<Single<&LdtkProjectHandle>>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+) {
+    if run_coins.level.is_some() {
+        return;
+    }
+    let level = ldtk_project
+        .and_then(|handle| ldtk_project_assets.get(*handle))
+        .and_then(|project| selected_level(&level_selection, project));
+    if let Some((position, _)) = level {
+        run_coins.level = Some(position);
+    }
+}
+
+fn finish_level_coins(_: On<LevelChanged>, mut run_coins: ResMut<RunCoins>) {
+    run_coins.finish_level();
+}
+
+fn reset_run_coins(mut run_coins: ResMut<RunCoins>) {
+    *run_coins = RunCoins::default();
+}
+
+/// Counts the final level and updates the records with the finished run.
+pub fn record_coins(mut run_coins: ResMut<RunCoins>, mut save_data: ResMut<SaveData>) {
+    run_coins.finish_level();
+
+    let mut summary = CoinSummary {
+        levels: Vec::new(),
+        total: run_coins.total(),
+        previous_best_total: save_data.best_total_coins,
+    };
+    for (&level, &found) in &run_coins.levels {
+        let record = save_data.coin_records.get(&level).copied();
+        summary.levels.push((level, found, record));
+        if record.is_none_or(|record| found > record) {
+            save_data.coin_records.insert(level, found);
+        }
+    }
+    if summary
+        .previous_best_total
+        .is_none_or(|best| summary.total > best)
+    {
+        save_data.best_total_coins = Some(summary.total);
+    }
+    run_coins.summary = Some(summary);
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct CoinAssets {
+    #[dependency]
+    pickup: Handle<AudioSource>,
+}
+
+impl FromWorld for CoinAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            // There is no pickup sound of its own yet, so it borrows the button click.
+            pickup: assets.load("audio/sound_effects/button_click.ogg"),
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! An optional overlay that outlines colliders and sensors, color-coded by what they belong to.
+
+use avian2d::{
+    parry::shape::TypedShape,
+    prelude::{Collider, ColliderAabb, RigidBody, Sensor},
+};
+use bevy::{color::palettes::css, input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    AppSystems,
+    game::{door::Door, force_zone::ForceZone, mine::Mine, player::Player, spike::Spike},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ColliderGizmos>();
+    app.add_systems(
+        Update,
+        (
+            toggle_collider_gizmos.run_if(input_just_pressed(TOGGLE_COLLIDER_GIZMOS_KEY)),
+            draw_collider_gizmos.run_if(collider_gizmos_enabled),
+        )
+            .chain()
+            .in_set(AppSystems::Update),
+    );
+}
+
+const TOGGLE_COLLIDER_GIZMOS_KEY: KeyCode = KeyCode::F2;
+
+/// Whether collider outlines are drawn.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct ColliderGizmos(pub bool);
+
+pub fn collider_gizmos_enabled(collider_gizmos: Res<ColliderGizmos>) -> bool {
+    collider_gizmos.0
+}
+
+fn toggle_collider_gizmos(mut collider_gizmos: ResMut<ColliderGizmos>) {
+    collider_gizmos.0 = !collider_gizmos.0;
+}
+
+fn draw_collider_gizmos(
+    mut gizmos: Gizmos,
+    colliders: Query<(
+        Entity,
+        &Collider,
+        &ColliderAabb,
+        &GlobalTransform,
+        Option<&RigidBody>,
+        Option<&ChildOf>,
+        Has<Sensor>,
+    )>,
+    players: Query<(), With<Player>>,
+    doors: Query<(), With<Door>>,
+    spikes: Query<(), With<Spike>>,
+    mines: Query<(), With<Mine>>,
+    force_zones: Query<(&ForceZone, &GlobalTransform)>,
+) {
+    for (entity, collider, aabb, transform, rigid_body, parent, is_sensor) in &colliders {
+        // Mine sensors are children of the mine itself.
+        let is_mine = mines.contains(entity) || parent.is_some_and(|p| mines.contains(p.parent()));
+
+        let color = if players.contains(entity) {
+            css::LIME
+        } else if doors.contains(entity) {
+            css::DODGER_BLUE
+        } else if spikes.contains(entity) {
+            css::RED
+        } else if is_mine {
+            css::ORANGE
+        } else if rigid_body == Some(&RigidBody::Static) {
+            css::WHITE
+        } else if is_sensor {
+            css::YELLOW
+        } else {
+            css::GRAY
+        };
+
+        draw_collider(&mut gizmos, collider, aabb, transform, color);
+    }
+
+    for (force_zone, transform) in &force_zones {
+        let start = transform.translation().truncate();
+        let end = start + force_zone.force.normalize_or_zero() * 12.0;
+        gizmos.arrow_2d(start, end, css::AQUA);
+    }
+}
+
+/// Outlines the collider's actual shape. Shapes without an outline here fall back to their
+/// bounding box.
+fn draw_collider(
+    gizmos: &mut Gizmos,
+    collider: &Collider,
+    aabb: &ColliderAabb,
+    transform: &GlobalTransform,
+    color: impl Into<Color>,
+) {
+    let (_, rotation, translation) = transform.to_scale_rotation_translation();
+    let isometry = Isometry2d::new(
+        translation.truncate(),
+        Rot2::radians(rotation.to_euler(EulerRot::XYZ).2),
+    );
+    match collider.shape_scaled().as_typed_shape() {
+        TypedShape::Ball(ball) => {
+            gizmos.circle_2d(isometry, ball.radius, color);
+        }
+        TypedShape::Cuboid(cuboid) => {
+            let size = Vec2::new(cuboid.half_extents.x, cuboid.half_extents.y) * 2.0;
+            gizmos.rect_2d(isometry, size, color);
+        }
+        TypedShape::RoundCuboid(round_cuboid) => {
+            let half_extents = round_cuboid.inner_shape.half_extents;
+            let radius = round_cuboid.border_radius;
+            let size = (Vec2::new(half_extents.x, half_extents.y) + radius) * 2.0;
+            gizmos
+                .rounded_rect_2d(isometry, size, color)
+                .corner_radius(radius);
+        }
+        TypedShape::Capsule(capsule) => {
+            let a = Vec2::new(capsule.segment.a.x, capsule.segment.a.y);
+            let b = Vec2::new(capsule.segment.b.x, capsule.segment.b.y);
+            // Capsule2d is upright, so it's turned to lie along the segment.
+            let along = Isometry2d::new((a + b) / 2.0, Rot2::radians(Vec2::Y.angle_to(b - a)));
+            gizmos.primitive_2d(
+                &Capsule2d::new(capsule.radius, a.distance(b)),
+                isometry * along,
+                color,
+            );
+        }
+        _ => {
+            let (min, max) = (aabb.min, aabb.max);
+            gizmos.rect_2d((min + max) / 2.0, max - min, color);
+        }
+    }
+}
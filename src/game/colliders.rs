@@ -22,6 +22,53 @@ pub struct ColliderBundle {
     pub friction: Friction,
     pub density: ColliderDensity,
     pub events: CollisionEventsEnabled,
+    pub layers: CollisionLayers,
+}
+
+/// What kind of thing a collider is, which decides what it can touch.
+///
+/// Colliders without [`CollisionLayers`] are terrain, but touch everything.
+#[derive(PhysicsLayer, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GameLayer {
+    /// Walls and anything else solid the player stands on.
+    #[default]
+    Terrain,
+    Player,
+    /// Things that kill the player, like spikes, mines and enemies.
+    Hazard,
+    Projectile,
+    /// Things the player collects, like coins and powerups.
+    Pickup,
+    /// Sensors that only react to the player, like doors, goals and force zones.
+    Zone,
+}
+
+impl GameLayer {
+    /// Hazards, projectiles, pickups and zones only touch the player and, to stop or to rest
+    /// on, the terrain. They never set each other off.
+    pub fn layers(self) -> CollisionLayers {
+        let filters = match self {
+            GameLayer::Terrain => LayerMask::from([
+                GameLayer::Terrain,
+                GameLayer::Player,
+                GameLayer::Hazard,
+                GameLayer::Projectile,
+            ]),
+            GameLayer::Player => LayerMask::from([
+                GameLayer::Terrain,
+                GameLayer::Player,
+                GameLayer::Hazard,
+                GameLayer::Projectile,
+                GameLayer::Pickup,
+                GameLayer::Zone,
+            ]),
+            GameLayer::Hazard | GameLayer::Projectile => {
+                LayerMask::from([GameLayer::Terrain, GameLayer::Player])
+            }
+            GameLayer::Pickup | GameLayer::Zone => LayerMask::from(GameLayer::Player),
+        };
+        CollisionLayers::new(self, filters)
+    }
 }
 
 impl From<&EntityInstance> for ColliderBundle {
@@ -33,18 +80,75 @@ impl From<&EntityInstance> for ColliderBundle {
                 collider: Collider::rectangle(10., 10.),
                 rigid_body: RigidBody::Kinematic,
                 rotation_constraints,
+                layers: GameLayer::Hazard.layers(),
                 ..Default::default()
             },
             "Mine" => ColliderBundle {
                 collider: Collider::rectangle(16., 16.),
                 rigid_body: RigidBody::Kinematic,
                 rotation_constraints,
+                layers: GameLayer::Hazard.layers(),
+                ..Default::default()
+            },
+            "Coin" => ColliderBundle {
+                collider: Collider::rectangle(8., 8.),
+                rigid_body: RigidBody::Kinematic,
+                rotation_constraints,
+                layers: GameLayer::Pickup.layers(),
+                ..Default::default()
+            },
+            "Powerup" => ColliderBundle {
+                collider: Collider::rectangle(12., 12.),
+                rigid_body: RigidBody::Kinematic,
+                rotation_constraints,
+                layers: GameLayer::Pickup.layers(),
+                ..Default::default()
+            },
+            "Enemy" => ColliderBundle {
+                collider: Collider::rectangle(12., 12.),
+                rigid_body: RigidBody::Kinematic,
+                rotation_constraints,
+                layers: GameLayer::Hazard.layers(),
                 ..Default::default()
             },
             "Door" => ColliderBundle {
                 collider: Collider::rectangle(32., 48.),
                 rigid_body: RigidBody::Kinematic,
                 rotation_constraints,
+                layers: GameLayer::Zone.layers(),
+                ..Default::default()
+            },
+            "Turret" => ColliderBundle {
+                collider: Collider::rectangle(16., 16.),
+                rigid_body: RigidBody::Static,
+                rotation_constraints,
+                layers: GameLayer::Terrain.layers(),
+                ..Default::default()
+            },
+            "Bouncer" => ColliderBundle {
+                collider: Collider::rectangle(16., 16.),
+                rigid_body: RigidBody::Static,
+                rotation_constraints,
+                layers: GameLayer::Terrain.layers(),
+                ..Default::default()
+            },
+            // Zones are resized freely in LDtk, so they take the size of the instance.
+            "ForceZone" | "GravityFlipZone" | "Trigger" => ColliderBundle {
+                collider: Collider::rectangle(
+                    entity_instance.width as f32,
+                    entity_instance.height as f32,
+                ),
+                rigid_body: RigidBody::Static,
+                layers: GameLayer::Zone.layers(),
+                ..Default::default()
+            },
+            "DamageZone" => ColliderBundle {
+                collider: Collider::rectangle(
+                    entity_instance.width as f32,
+                    entity_instance.height as f32,
+                ),
+                rigid_body: RigidBody::Static,
+                layers: GameLayer::Hazard.layers(),
                 ..Default::default()
             },
             _ => ColliderBundle::default(),
@@ -67,9 +171,12 @@ pub fn add_child_colliders(
             .with_children(|c| {
                 c.spawn((
                     CollisionEventsEnabled,
+                    // Who is on the mine when it explodes after flashing.
+                    CollidingEntities::default(),
                     Transform::from_translation(Vec3::new(0., -6.5, 0.)),
                     Collider::rectangle(16., 3.),
                     Sensor,
+                    GameLayer::Hazard.layers(),
                 ))
                 .observe(on_player_touched_mine);
             });
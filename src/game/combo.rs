@@ -0,0 +1,275 @@
+//! Recognizes short sequences of inputs for special moves, like tapping a direction twice.
+//!
+//! Every player remembers the last few controls they pressed, on the keyboard or, for the
+//! first player, on the touch buttons. Whenever the most recent ones
+//! match one of the [`Combos`], the player is sent [`ComboTriggered`], and the systems for the
+//! move take it from there. The combos are a resource, so they can be changed like any other
+//! setting, e.g. through the inspector.
+
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    game::{
+        player::{Dead, InputBindings, Player, PlayerIndex},
+        touch_controls::TouchActions,
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Combos>();
+    app.add_systems(
+        Update,
+        record_combo_inputs
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems),
+    );
+}
+
+/// How many inputs a [`ComboBuffer`] remembers, and so how long a combo can be.
+const MAX_COMBO_LENGTH: usize = 8;
+
+/// A control that can be part of a combo. It stands for whichever keys the player has bound
+/// to it, so combos work the same for every player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+pub enum ComboInput {
+    Left,
+    Right,
+    Up,
+    Down,
+    Jump,
+}
+
+impl ComboInput {
+    const ALL: [ComboInput; 5] = [
+        ComboInput::Left,
+        ComboInput::Right,
+        ComboInput::Up,
+        ComboInput::Down,
+        ComboInput::Jump,
+    ];
+
+    fn keys(self, bindings: &InputBindings) -> &[KeyCode] {
+        match self {
+            ComboInput::Left => &bindings.left,
+            ComboInput::Right => &bindings.right,
+            ComboInput::Up => &bindings.up,
+            ComboInput::Down => &bindings.down,
+            ComboInput::Jump => &bindings.jump,
+        }
+    }
+
+    /// Whether a touch button holds this input down. There are no buttons for up and down.
+    fn touched(self, touch: &TouchActions) -> bool {
+        match self {
+            ComboInput::Left => touch.left,
+            ComboInput::Right => touch.right,
+            ComboInput::Jump => touch.jump,
+            ComboInput::Up | ComboInput::Down => false,
+        }
+    }
+}
+
+/// The special moves combos can trigger.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+pub enum ComboId {
+    GroundPound,
+    DashLeft,
+    DashRight,
+    /// Stands in for real moves in tests, so they don't depend on the moves the game has.
+    #[cfg(test)]
+    Test(u8),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Reflect)]
+pub struct Combo {
+    pub id: ComboId,
+    /// The inputs to press, in order.
+    pub inputs: Vec<ComboInput>,
+    /// How long each input may come after the one before it.
+    pub window: Duration,
+}
+
+impl Combo {
+    /// Whether the end of `history` is this combo, pressed quickly enough.
+    /// The history holds each input with the time it was pressed at, oldest first.
+    pub fn matches(&self, history: &[(ComboInput, Duration)]) -> bool {
+        if self.inputs.is_empty() || history.len() < self.inputs.len() {
+            return false;
+        }
+        let recent = &history[history.len() - self.inputs.len()..];
+        recent
+            .iter()
+            .zip(&self.inputs)
+            .all(|((pressed, _), input)| pressed == input)
+            && recent
+                .windows(2)
+                .all(|pair| pair[1].1.saturating_sub(pair[0].1) <= self.window)
+    }
+}
+
+/// Every combo the players can perform. When several match at once, the longest one wins.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct Combos(pub Vec<Combo>);
+
+impl Combos {
+    /// The combo at the end of `history`, if any, preferring the longest one.
+    pub fn performed(&self, history: &[(ComboInput, Duration)]) -> Option<&Combo> {
+        self.0
+            .iter()
+            .filter(|combo| combo.matches(history))
+            .max_by_key(|combo| combo.inputs.len())
+    }
+}
+
+impl Default for Combos {
+    fn default() -> Self {
+        Self(vec![
+            // Only starts in the air, see `ground_pound`.
+            Combo {
+                id: ComboId::GroundPound,
+                inputs: vec![ComboInput::Down],
+                window: Duration::ZERO,
+            },
+            Combo {
+                id: ComboId::DashLeft,
+                inputs: vec![ComboInput::Left, ComboInput::Left],
+                window: Duration::from_millis(250),
+            },
+            Combo {
+                id: ComboId::DashRight,
+                inputs: vec![ComboInput::Right, ComboInput::Right],
+                window: Duration::from_millis(250),
+            },
+        ])
+    }
+}
+
+/// The inputs a player pressed most recently, with the time they were pressed at.
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct ComboBuffer(VecDeque<(ComboInput, Duration)>);
+
+/// Sent to a player that performed a combo.
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct ComboTriggered {
+    pub entity: Entity,
+    pub combo: ComboId,
+}
+
+/// A performed combo is forgotten, so its inputs can't start or finish another one.
+///
+/// Uses the virtual time, so a combo can't be finished across a pause.
+fn record_combo_inputs(
+    mut commands: Commands,
+    time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    touch_actions: Res<TouchActions>,
+    mut last_touch_actions: Local<TouchActions>,
+    combos: Res<Combos>,
+    players: Query<
+        (Entity, &PlayerIndex, &InputBindings, &mut ComboBuffer),
+        (With<Player>, Without<Dead>),
+    >,
+) {
+    for (player, index, bindings, mut buffer) in players {
+        for input in ComboInput::ALL {
+            // The touch buttons control the first player.
+            let touched = index.0 == 0
+                && input.touched(&touch_actions)
+                && !input.touched(&last_touch_actions);
+            if !touched && !keyboard.any_just_pressed(input.keys(bindings).iter().copied()) {
+                continue;
+            }
+            if buffer.0.len() == MAX_COMBO_LENGTH {
+                buffer.0.pop_front();
+            }
+            buffer.0.push_back((input, time.elapsed()));
+
+            if let Some(combo) = combos.performed(buffer.0.make_contiguous()) {
+                commands.trigger(ComboTriggered {
+                    entity: player,
+                    combo: combo.id,
+                });
+                buffer.0.clear();
+            }
+        }
+    }
+    *last_touch_actions = *touch_actions;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_millis(250);
+
+    fn right_right() -> Combo {
+        Combo {
+            id: ComboId::Test(2),
+            inputs: vec![ComboInput::Right, ComboInput::Right],
+            window: WINDOW,
+        }
+    }
+
+    fn at(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn matches_with_a_gap_of_exactly_the_window() {
+        let history = [(ComboInput::Right, at(1000)), (ComboInput::Right, at(1250))];
+        assert!(right_right().matches(&history));
+    }
+
+    #[test]
+    fn fails_with_a_gap_just_over_the_window() {
+        let history = [(ComboInput::Right, at(1000)), (ComboInput::Right, at(1251))];
+        assert!(!right_right().matches(&history));
+    }
+
+    #[test]
+    fn fails_with_a_history_shorter_than_the_combo() {
+        assert!(!right_right().matches(&[(ComboInput::Right, at(1000))]));
+        assert!(!right_right().matches(&[]));
+    }
+
+    #[test]
+    fn only_looks_at_the_end_of_the_history() {
+        let history = [
+            (ComboInput::Right, at(1000)),
+            (ComboInput::Right, at(1100)),
+            (ComboInput::Jump, at(1200)),
+        ];
+        assert!(!right_right().matches(&history));
+    }
+
+    #[test]
+    fn the_longest_matching_combo_wins() {
+        let combos = Combos(vec![
+            right_right(),
+            Combo {
+                id: ComboId::Test(3),
+                inputs: vec![ComboInput::Left, ComboInput::Right, ComboInput::Right],
+                window: WINDOW,
+            },
+            Combo {
+                id: ComboId::Test(1),
+                inputs: vec![ComboInput::Right],
+                window: WINDOW,
+            },
+        ]);
+        let history = [
+            (ComboInput::Left, at(1000)),
+            (ComboInput::Right, at(1100)),
+            (ComboInput::Right, at(1200)),
+        ];
+
+        let performed = combos.performed(&history).map(|combo| combo.id);
+
+        assert_eq!(performed, Some(ComboId::Test(3)));
+    }
+}
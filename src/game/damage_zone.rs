@@ -0,0 +1,110 @@
+//! Zones that keep hurting the player for as long as they stay inside, like lava or acid.
+//!
+//! In LDtk these are `DamageZone` entities, sized freely like force zones, with a float field
+//! `DamagePerSecond`. Unlike spikes and mines, which react when the player touches them, a
+//! zone deals its damage over time. Without [`Health`] there's nothing to take away, so the
+//! zone kills instead.
+
+use avian2d::prelude::{CollidingEntities, Sensor};
+use bevy::prelude::*;
+use bevy_ecs_ldtk::{EntityInstance, LdtkEntity, app::LdtkEntityAppExt, prelude::LdtkFields};
+
+use crate::{
+    AppSystems, PausableSystems,
+    game::{
+        colliders::ColliderBundle,
+        health::{Damage, Health},
+        player::{Dead, DeathCause, Invulnerable, KillPlayer, Player},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_ldtk_entity::<DamageZoneBundle>("DamageZone");
+    app.add_systems(
+        Update,
+        apply_damage_zones
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// Hurts every player inside it over time.
+#[derive(Clone, Copy, Debug, PartialEq, Component, Reflect)]
+#[reflect(Component)]
+pub struct DamageZone {
+    pub per_second: f32,
+}
+
+impl Default for DamageZone {
+    fn default() -> Self {
+        Self { per_second: 1.0 }
+    }
+}
+
+#[derive(Clone, Debug, Default, Bundle, LdtkEntity)]
+pub struct DamageZoneBundle {
+    #[with(damage_zone_from_instance)]
+    damage_zone: DamageZone,
+
+    #[from_entity_instance]
+    collider_bundle: ColliderBundle,
+
+    sensor: Sensor,
+    colliding_entities: CollidingEntities,
+}
+
+fn damage_zone_from_instance(instance: &EntityInstance) -> DamageZone {
+    DamageZone {
+        per_second: instance
+            .get_float_field("DamagePerSecond")
+            .copied()
+            .unwrap_or(DamageZone::default().per_second),
+    }
+}
+
+/// The damage a player took from zones that doesn't add up to a whole point yet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Component, Reflect)]
+#[reflect(Component)]
+pub struct ZoneDamage(f32);
+
+/// Invulnerable players don't build up damage, and lose what they had, so it doesn't all land
+/// at once when their protection ends.
+fn apply_damage_zones(
+    mut commands: Commands,
+    time: Res<Time>,
+    zones: Query<(&DamageZone, &CollidingEntities)>,
+    mut players: Query<
+        (Entity, &mut ZoneDamage, Has<Health>, Has<Invulnerable>),
+        (With<Player>, Without<Dead>),
+    >,
+) {
+    for (player, mut zone_damage, has_health, invulnerable) in &mut players {
+        let per_second: f32 = zones
+            .iter()
+            .filter(|(_, colliding)| colliding.contains(&player))
+            .map(|(zone, _)| zone.per_second)
+            .sum();
+        if invulnerable || per_second <= 0.0 {
+            zone_damage.0 = 0.0;
+            continue;
+        }
+        if !has_health {
+            commands.trigger(KillPlayer {
+                entity: player,
+                cause: DeathCause::DamageZone,
+            });
+            continue;
+        }
+
+        zone_damage.0 += per_second * time.delta_secs();
+        let amount = zone_damage.0 as u32;
+        if amount > 0 {
+            zone_damage.0 -= amount as f32;
+            commands.trigger(Damage {
+                entity: player,
+                amount,
+                cause: DeathCause::DamageZone,
+            });
+        }
+    }
+}
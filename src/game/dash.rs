@@ -0,0 +1,263 @@
+//! The player's dash, and the movement state machine that carries its momentum into a jump.
+//!
+//! Tnua's dash and jump are separate actions that each set the velocity they want. The
+//! [`MovementState`] decides which of them `apply_controls` feeds, and what horizontal speed
+//! survives when a dash is canceled into a jump.
+
+use bevy::prelude::*;
+
+use crate::game::{
+    combo::{ComboId, ComboTriggered},
+    player::MovementConfig,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(dash_on_combo);
+}
+
+/// What the player is doing beyond walking and jumping, updated with [`MovementState::next`]
+/// every fixed tick.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+pub enum MovementState {
+    /// Walking and jumping as usual.
+    #[default]
+    Moving,
+    /// Dashing towards `direction`, which is `-1.0` for left and `1.0` for right.
+    Dashing { direction: f32 },
+    /// In a jump out of a dash, which keeps its horizontal `velocity` until the player lands
+    /// or steers against it.
+    DashJumping { velocity: f32 },
+}
+
+/// What [`MovementState::next`] looks at.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MovementInput {
+    /// The direction of a dash asked for this tick, if any.
+    pub dash: Option<f32>,
+    /// Whether Tnua's dash is still moving the player, not counting its braking.
+    pub dashing: bool,
+    /// Whether the jump action gets fed this tick.
+    pub jump: bool,
+    /// Whether a jump is going on, including its fall.
+    pub jumping: bool,
+    pub airborne: bool,
+    /// The held horizontal direction, `-1.0`, `0.0` or `1.0`.
+    pub direction: f32,
+    /// The walking or running speed, including powerups.
+    pub speed: f32,
+}
+
+impl MovementState {
+    pub fn next(self, input: &MovementInput, config: &MovementConfig) -> Self {
+        match self {
+            Self::Moving => match input.dash {
+                Some(direction) if !input.airborne => Self::Dashing { direction },
+                _ => self,
+            },
+            Self::Dashing { direction } => {
+                if input.jump && !input.airborne {
+                    Self::DashJumping {
+                        velocity: dash_jump_velocity(
+                            direction,
+                            input.direction,
+                            input.speed,
+                            config,
+                        ),
+                    }
+                } else if input.dashing {
+                    self
+                } else {
+                    Self::Moving
+                }
+            }
+            Self::DashJumping { velocity } => {
+                // Tnua takes the jump in the same tick it's fed, so it's going on by the
+                // next one.
+                let landed = !input.airborne && !input.jumping;
+                let steered_against = input.direction * velocity < 0.0;
+                if landed || steered_against {
+                    Self::Moving
+                } else {
+                    self
+                }
+            }
+        }
+    }
+}
+
+/// The horizontal velocity of a jump out of a dash towards `dash_direction`.
+///
+/// The jump goes the way the player holds, or the way of the dash if they hold neither way.
+/// [`MovementConfig::dash_momentum`] picks its speed between the walking or running `speed` and
+/// [`MovementConfig::dash_speed`].
+pub fn dash_jump_velocity(
+    dash_direction: f32,
+    held_direction: f32,
+    speed: f32,
+    config: &MovementConfig,
+) -> f32 {
+    let direction = if held_direction == 0.0 {
+        dash_direction
+    } else {
+        held_direction.signum()
+    };
+    let momentum = config.dash_momentum.clamp(0.0, 1.0);
+    direction * (speed + (config.dash_speed - speed) * momentum)
+}
+
+/// Turns the dash button into one dash per press.
+///
+/// `apply_controls` runs in `FixedUpdate`, which can miss or repeat a `just_pressed`, so the
+/// press is detected from whether the button is held instead.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+pub struct DashInput {
+    held: bool,
+    requested: Option<f32>,
+}
+
+impl DashInput {
+    /// Asks for a dash towards `direction` on the next tick.
+    pub fn request(&mut self, direction: f32) {
+        self.requested = Some(direction.signum());
+    }
+
+    /// The direction of the dash asked for since the last tick, if any. A fresh press of the
+    /// button dashes towards `facing`.
+    pub fn take(&mut self, held: bool, facing: f32) -> Option<f32> {
+        if held && !self.held {
+            self.request(facing);
+        }
+        self.held = held;
+        self.requested.take()
+    }
+}
+
+/// Tapping a direction twice dashes that way, see [`Combos`](crate::game::combo::Combos).
+fn dash_on_combo(combo: On<ComboTriggered>, mut players: Query<&mut DashInput>) {
+    let direction = match combo.combo {
+        ComboId::DashLeft => -1.0,
+        ComboId::DashRight => 1.0,
+        _ => return,
+    };
+    if let Ok(mut dash_input) = players.get_mut(combo.entity) {
+        dash_input.request(direction);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEED: f32 = 120.0;
+
+    fn config(dash_momentum: f32) -> MovementConfig {
+        MovementConfig {
+            walk_speed: SPEED,
+            dash_speed: 320.0,
+            dash_momentum,
+            ..default()
+        }
+    }
+
+    fn input() -> MovementInput {
+        MovementInput {
+            speed: SPEED,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn a_dash_jump_keeps_part_of_the_dash_speed() {
+        assert_eq!(dash_jump_velocity(1.0, 0.0, SPEED, &config(0.5)), 220.0);
+        assert_eq!(dash_jump_velocity(-1.0, 0.0, SPEED, &config(0.5)), -220.0);
+    }
+
+    #[test]
+    fn the_momentum_ranges_from_walking_to_dash_speed() {
+        assert_eq!(dash_jump_velocity(1.0, 0.0, SPEED, &config(0.0)), SPEED);
+        assert_eq!(dash_jump_velocity(1.0, 0.0, SPEED, &config(1.0)), 320.0);
+        assert_eq!(dash_jump_velocity(1.0, 0.0, SPEED, &config(2.0)), 320.0);
+    }
+
+    #[test]
+    fn holding_the_other_way_redirects_the_momentum() {
+        assert_eq!(dash_jump_velocity(1.0, -1.0, SPEED, &config(0.5)), -220.0);
+    }
+
+    #[test]
+    fn jumping_out_of_a_dash_carries_its_momentum() {
+        let config = config(0.5);
+        let state = MovementState::Moving.next(
+            &MovementInput {
+                dash: Some(1.0),
+                ..input()
+            },
+            &config,
+        );
+        assert_eq!(state, MovementState::Dashing { direction: 1.0 });
+
+        let state = state.next(
+            &MovementInput {
+                dashing: true,
+                jump: true,
+                ..input()
+            },
+            &config,
+        );
+        assert_eq!(state, MovementState::DashJumping { velocity: 220.0 });
+
+        let taking_off = MovementInput {
+            jumping: true,
+            ..input()
+        };
+        assert_eq!(state.next(&taking_off, &config), state);
+        assert_eq!(state.next(&input(), &config), MovementState::Moving);
+    }
+
+    #[test]
+    fn steering_against_a_dash_jump_ends_it() {
+        let config = config(0.5);
+        let state = MovementState::DashJumping { velocity: 220.0 };
+        let airborne = MovementInput {
+            jumping: true,
+            airborne: true,
+            direction: 1.0,
+            ..input()
+        };
+        assert_eq!(state.next(&airborne, &config), state);
+        assert_eq!(
+            state.next(
+                &MovementInput {
+                    direction: -1.0,
+                    ..airborne
+                },
+                &config
+            ),
+            MovementState::Moving
+        );
+    }
+
+    #[test]
+    fn dashes_only_start_on_the_ground() {
+        let input = MovementInput {
+            dash: Some(1.0),
+            airborne: true,
+            ..input()
+        };
+        assert_eq!(
+            MovementState::Moving.next(&input, &config(0.5)),
+            MovementState::Moving
+        );
+    }
+
+    #[test]
+    fn the_dash_button_dashes_once_per_press() {
+        let mut dash = DashInput::default();
+        assert_eq!(dash.take(true, -1.0), Some(-1.0));
+        assert_eq!(dash.take(true, -1.0), None);
+        assert_eq!(dash.take(false, -1.0), None);
+        assert_eq!(dash.take(true, 1.0), Some(1.0));
+    }
+}
@@ -0,0 +1,160 @@
+//! An optional overlay that shows the player's movement state.
+//! Useful when tuning how the controls feel.
+
+use std::time::Duration;
+
+use avian2d::prelude::LinearVelocity;
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy_tnua::prelude::{TnuaBuiltinWalk, TnuaController};
+
+use crate::{
+    AppSystems,
+    game::{
+        animation::Animation,
+        player::{MovementConfig, Player, PlayerIndex},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<DebugOverlay>();
+    app.add_systems(Startup, spawn_debug_overlay);
+    app.add_systems(
+        Update,
+        (
+            toggle_debug_overlay.run_if(input_just_pressed(TOGGLE_DEBUG_OVERLAY_KEY)),
+            apply_debug_overlay_visibility.run_if(resource_changed::<DebugOverlay>),
+            (tune_jump_timing, update_debug_overlay).run_if(debug_overlay_enabled),
+        )
+            .chain()
+            .in_set(AppSystems::Update),
+    );
+}
+
+const TOGGLE_DEBUG_OVERLAY_KEY: KeyCode = KeyCode::F3;
+/// How much a key press changes the coyote time or jump buffer, in seconds.
+const JUMP_TIMING_STEP: f32 = 0.01;
+
+/// Whether the player state overlay is shown.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct DebugOverlay(pub bool);
+
+pub fn debug_overlay_enabled(debug_overlay: Res<DebugOverlay>) -> bool {
+    debug_overlay.0
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct DebugOverlayText;
+
+fn spawn_debug_overlay(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Debug Overlay"),
+        DebugOverlayText,
+        Text::default(),
+        TextFont::from_font_size(14.0),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(5),
+            left: px(5),
+            ..default()
+        },
+        GlobalZIndex(10),
+        Visibility::Hidden,
+        Pickable::IGNORE,
+    ));
+}
+
+fn toggle_debug_overlay(mut debug_overlay: ResMut<DebugOverlay>) {
+    debug_overlay.0 = !debug_overlay.0;
+}
+
+fn apply_debug_overlay_visibility(
+    debug_overlay: Res<DebugOverlay>,
+    mut visibility: Single<&mut Visibility, With<DebugOverlayText>>,
+) {
+    **visibility = if debug_overlay.0 {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+}
+
+/// While the overlay is open, `[` and `]` tune the coyote time and `-` and `=` the jump buffer.
+fn tune_jump_timing(keyboard: Res<ButtonInput<KeyCode>>, mut movement: ResMut<MovementConfig>) {
+    let step = |lower: KeyCode, raise: KeyCode| {
+        if keyboard.just_pressed(lower) {
+            -JUMP_TIMING_STEP
+        } else if keyboard.just_pressed(raise) {
+            JUMP_TIMING_STEP
+        } else {
+            0.0
+        }
+    };
+    let coyote_step = step(KeyCode::BracketLeft, KeyCode::BracketRight);
+    let buffer_step = step(KeyCode::Minus, KeyCode::Equal);
+    if coyote_step != 0.0 {
+        movement.coyote_time = (movement.coyote_time + coyote_step).max(0.0);
+    }
+    if buffer_step != 0.0 {
+        movement.jump_buffer_time = (movement.jump_buffer_time + buffer_step).max(0.0);
+    }
+}
+
+fn update_debug_overlay(
+    time: Res<Time>,
+    movement: Res<MovementConfig>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut airborne_for: Local<Duration>,
+    mut jump_buffered_for: Local<Option<Duration>>,
+    player_query: Query<(&PlayerIndex, &TnuaController, &Animation, &LinearVelocity), With<Player>>,
+    mut text: Single<&mut Text, With<DebugOverlayText>>,
+) {
+    // With several players around, show the first one.
+    let Some((_, controller, animation, velocity)) =
+        player_query.iter().min_by_key(|(index, ..)| index.0)
+    else {
+        text.0 = "No player".to_string();
+        return;
+    };
+
+    // Tnua doesn't expose its internal countdowns, so track them the same way it does.
+    let airborne = controller.is_airborne().unwrap_or(true);
+    if airborne {
+        *airborne_for += time.delta();
+    } else {
+        *airborne_for = Duration::ZERO;
+    }
+    if !keyboard.pressed(KeyCode::Space) || !airborne {
+        *jump_buffered_for = None;
+    } else if keyboard.just_pressed(KeyCode::Space) {
+        *jump_buffered_for = Some(Duration::ZERO);
+    } else if let Some(buffered_for) = jump_buffered_for.as_mut() {
+        *buffered_for += time.delta();
+    }
+
+    let coyote_time = controller
+        .concrete_basis::<TnuaBuiltinWalk>()
+        .map_or(0.0, |(basis, _)| basis.coyote_time);
+    let coyote_left = (coyote_time - airborne_for.as_secs_f32()).max(0.0);
+    let input_buffer_time = movement.jump_buffer_time;
+    let jump_buffer_left = jump_buffered_for
+        .map_or(0.0, |buffered_for| {
+            input_buffer_time - buffered_for.as_secs_f32()
+        })
+        .max(0.0);
+
+    text.0 = format!(
+        "velocity: {:>7.1} {:>7.1}\n\
+         grounded: {}\n\
+         animation: {:?}\n\
+         action: {}\n\
+         coyote: {coyote_left:.3}s / {coyote_time:.3}s  [ ]\n\
+         jump buffer: {jump_buffer_left:.3}s / {input_buffer_time:.3}s  - =",
+        velocity.x,
+        velocity.y,
+        !airborne,
+        animation.state(),
+        controller.action_name().unwrap_or("none"),
+    );
+}
@@ -0,0 +1,157 @@
+//! Mines that can be disarmed by holding down next to them.
+//!
+//! In LDtk these are `Mine` entities with the bool field `Defusable` set.
+//! The optional float field `DefuseTime` is how many seconds it takes, 1.5 by default.
+//! Unlike other mines they are visible, so there's a chance to spot them.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_ecs_ldtk::{EntityInstance, prelude::LdtkFields};
+use bevy_tnua::prelude::TnuaController;
+
+use crate::{
+    AppSystems, PausableSystems,
+    asset_tracking::LoadResource,
+    audio::sound_effect,
+    game::{
+        mine::{Mine, Spent},
+        player::{Dead, InputBindings, Player},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.load_resource::<DefusalAssets>();
+    app.add_systems(
+        Update,
+        (
+            mark_defusable_mines,
+            defuse_mines.in_set(PausableSystems),
+            update_defuse_bars,
+        )
+            .chain()
+            .in_set(AppSystems::Update),
+    );
+}
+
+/// How close a player has to stand to a mine to defuse it.
+const DEFUSE_RANGE: f32 = 24.0;
+
+const BAR_WIDTH: f32 = 16.0;
+
+/// A mine that is disarmed once a player held down next to it for the timer's duration.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct Defusable(pub Timer);
+
+/// Shows the progress of defusing the parent mine.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct DefuseBar;
+
+fn mark_defusable_mines(
+    mut commands: Commands,
+    mines: Query<(Entity, &EntityInstance), Added<Mine>>,
+) {
+    for (mine, instance) in mines {
+        if !instance
+            .get_bool_field("Defusable")
+            .is_ok_and(|&defusable| defusable)
+        {
+            continue;
+        }
+        let seconds = instance
+            .get_float_field("DefuseTime")
+            .copied()
+            .unwrap_or(1.5);
+
+        commands
+            .entity(mine)
+            .insert((
+                Defusable(Timer::new(
+                    Duration::from_secs_f32(seconds),
+                    TimerMode::Once,
+                )),
+                Visibility::Visible,
+            ))
+            .with_child((
+                Name::new("Defuse Bar"),
+                DefuseBar,
+                Sprite::from_color(Color::srgb(0.3, 0.9, 0.4), Vec2::new(BAR_WIDTH, 2.0)),
+                Transform::from_xyz(0.0, 12.0, 1.0),
+                Visibility::Hidden,
+            ));
+    }
+}
+
+/// Progresses while any player stands on the ground next to the mine and holds down.
+/// Jumping or walking away starts over.
+fn defuse_mines(
+    mut commands: Commands,
+    time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    defusal_assets: Res<DefusalAssets>,
+    mut mines: Query<(Entity, &GlobalTransform, &mut Defusable), Without<Spent>>,
+    players: Query<
+        (&GlobalTransform, &InputBindings, &TnuaController),
+        (With<Player>, Without<Dead>),
+    >,
+) {
+    for (mine, mine_transform, mut defusable) in &mut mines {
+        let mine_position = mine_transform.translation().truncate();
+        let defusing = players.iter().any(|(transform, bindings, controller)| {
+            transform.translation().truncate().distance(mine_position) <= DEFUSE_RANGE
+                && !controller.is_airborne().unwrap_or(true)
+                && keyboard.any_pressed(bindings.down.iter().copied())
+        });
+
+        if !defusing {
+            defusable.0.reset();
+            continue;
+        }
+
+        if defusable.0.tick(time.delta()).is_finished() {
+            defusable.0.reset();
+            commands.entity(mine).insert((Spent, Visibility::Hidden));
+            commands.spawn((
+                Name::new("Defuse Sound"),
+                sound_effect(defusal_assets.click.clone()),
+            ));
+        }
+    }
+}
+
+fn update_defuse_bars(
+    mines: Query<&Defusable>,
+    mut bars: Query<(&ChildOf, &mut Transform, &mut Visibility), With<DefuseBar>>,
+) {
+    for (child_of, mut transform, mut visibility) in &mut bars {
+        let Ok(defusable) = mines.get(child_of.parent()) else {
+            continue;
+        };
+        let fraction = defusable.0.fraction();
+        transform.scale.x = fraction;
+        *visibility = if fraction > 0.0 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct DefusalAssets {
+    #[dependency]
+    pub click: Handle<AudioSource>,
+}
+
+impl FromWorld for DefusalAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            // There is no defusing sound of its own yet, so it borrows the button click.
+            click: assets.load("audio/sound_effects/button_click.ogg"),
+        }
+    }
+}
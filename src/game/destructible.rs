@@ -0,0 +1,111 @@
+//! Wall tiles that mine explosions and ground pounds can break, e.g. to open up hidden paths.
+//!
+//! In LDtk these are the value `2` ("Destructible") of the `WallLayer`.
+
+use avian2d::prelude::{Collider, CollisionLayers, Friction, RigidBody};
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+use crate::game::{colliders::GameLayer, grid_coords::GRID_SIZE, particles::ParticleBurst};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_ldtk_int_cell_for_layer::<DestructibleBundle>("WallLayer", 2);
+}
+
+/// How far an explosion reaches, in tiles.
+pub const BLAST_RADIUS: f32 = 1.5;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct Destructible;
+
+/// Unlike walls, every destructible tile gets its own collider,
+/// so breaking a tile never leaves part of a merged collider behind.
+#[derive(Clone, Debug, Bundle, LdtkIntCell)]
+pub struct DestructibleBundle {
+    destructible: Destructible,
+    collider: Collider,
+    rigid_body: RigidBody,
+    friction: Friction,
+    layers: CollisionLayers,
+    // Moved to the tile's position by `translate_grid_coords_entities`.
+    transform: Transform,
+}
+
+impl Default for DestructibleBundle {
+    fn default() -> Self {
+        Self {
+            destructible: Destructible,
+            collider: Collider::rectangle(16., 16.),
+            rigid_body: RigidBody::Static,
+            friction: Friction::new(0.0),
+            layers: GameLayer::Terrain.layers(),
+            transform: Transform::default(),
+        }
+    }
+}
+
+/// Despawns every destructible tile within `radius` tiles around `center`, in world space.
+///
+/// Only entities marked [`Destructible`] are ever despawned,
+/// so this can't take the level or its layers with it.
+pub fn destroy_in_radius(
+    commands: &mut Commands,
+    center: Vec2,
+    radius: f32,
+    destructibles: &Query<(Entity, &GlobalTransform), With<Destructible>>,
+) {
+    for (entity, transform) in destructibles {
+        let offset = transform.translation().truncate() - center;
+        if offset.length() <= radius * GRID_SIZE as f32 {
+            commands.entity(entity).despawn();
+            commands.spawn((
+                Name::new("Debris Particles"),
+                ParticleBurst {
+                    gravity: 300.0,
+                    ..ParticleBurst::colored(Color::srgb(0.45, 0.35, 0.3), 8)
+                },
+                Transform::from_translation(transform.translation()),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn a_blast_breaks_the_tiles_within_its_radius() {
+        let mut world = World::new();
+        let center = Vec2::new(100.0, 50.0);
+        let next_to_it = world
+            .spawn((Destructible, GlobalTransform::from_xyz(116.0, 50.0, 0.0)))
+            .id();
+        let diagonal = world
+            .spawn((Destructible, GlobalTransform::from_xyz(84.0, 66.0, 0.0)))
+            .id();
+        let out_of_reach = world
+            .spawn((Destructible, GlobalTransform::from_xyz(132.0, 50.0, 0.0)))
+            .id();
+        let wall = world
+            .spawn(GlobalTransform::from_xyz(100.0, 50.0, 0.0))
+            .id();
+
+        world
+            .run_system_once(
+                move |mut commands: Commands,
+                      destructibles: Query<(Entity, &GlobalTransform), With<Destructible>>| {
+                    destroy_in_radius(&mut commands, center, BLAST_RADIUS, &destructibles);
+                },
+            )
+            .unwrap();
+
+        assert!(world.get_entity(next_to_it).is_err());
+        assert!(world.get_entity(diagonal).is_err());
+        assert!(world.get_entity(out_of_reach).is_ok());
+        assert!(world.get_entity(wall).is_ok());
+    }
+}
@@ -1,24 +1,56 @@
-use avian2d::prelude::{CollisionStart, Sensor};
+//! Doors that lead to the next level once every player stands in one.
+//!
+//! In LDtk these are `Door` entities. With the bool field `Back` set, a door leads back to the
+//! previous level instead, e.g. to return to a hub. The players then come out next to that
+//! level's regular door, on the side they'd have walked up to it from.
+
+use avian2d::prelude::{CollisionEnd, CollisionStart, Sensor};
 use bevy::{
     ecs::{lifecycle::HookContext, world::DeferredWorld},
     prelude::*,
 };
-use bevy_ecs_ldtk::LdtkEntity;
-use bevy_ecs_ldtk::{LevelSelection, app::LdtkEntityAppExt};
+use bevy_ecs_ldtk::{
+    EntityInstance, LdtkEntity, LevelEvent, LevelIid, LevelSelection,
+    app::LdtkEntityAppExt,
+    assets::{LdtkProject, LdtkProjectHandle},
+    prelude::{LdtkFields, RawLevelAccessor},
+};
 
 use crate::{
+    AppSystems,
     asset_tracking::LoadResource,
     audio::sound_effect,
-    game::{colliders::ColliderBundle, player::Player},
+    game::{
+        colliders::ColliderBundle,
+        draw_order::DrawLayer,
+        grid_coords::GRID_SIZE,
+        level::{
+            LevelAdvance, LevelCompleted, LevelEntry, go_to_next_level, loaded_project,
+            retreat_level,
+        },
+        objectives::{LevelObjectives, ObjectivesIncomplete},
+        player::{Dead, Player, RespawnPlayers, SpawnPlayerAt, spawn_players_without_spawner},
+    },
     screens::Screen,
 };
 
 pub fn plugin(app: &mut App) {
     app.load_resource::<DoorAssets>();
     app.register_ldtk_entity::<DoorBundle>("Door");
+    app.add_systems(
+        Update,
+        place_players_at_exit
+            .after(spawn_players_without_spawner)
+            .in_set(AppSystems::Update),
+    );
 }
 
+/// How far from the exit the players come out when they come back through a back door,
+/// so they don't walk right into it again.
+const EXIT_OFFSET: f32 = 2.0 * GRID_SIZE as f32;
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component)]
+#[require(DrawLayer::Background)]
 #[component(on_add = on_door_add)]
 pub struct Door;
 
@@ -27,7 +59,8 @@ pub fn on_door_add(mut world: DeferredWorld, context: HookContext) {
     world
         .commands()
         .entity(door_entity)
-        .observe(on_player_entered_door);
+        .observe(on_player_entered_door)
+        .observe(on_player_left_door);
 }
 
 #[derive(Clone, Debug, Default, Bundle, LdtkEntity)]
@@ -37,43 +70,161 @@ pub struct DoorBundle {
     #[sprite_sheet]
     sprite_sheet: Sprite,
 
+    #[with(door_direction_from_instance)]
+    direction: DoorDirection,
+
     #[from_entity_instance]
     collider_bundle: ColliderBundle,
 
     sensor: Sensor,
 }
 
+/// Which level a [`Door`] leads to.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub enum DoorDirection {
+    #[default]
+    Next,
+    Previous,
+}
+
+fn door_direction_from_instance(instance: &EntityInstance) -> DoorDirection {
+    if instance.get_bool_field("Back").is_ok_and(|&back| back) {
+        DoorDirection::Previous
+    } else {
+        DoorDirection::Next
+    }
+}
+
+/// Marks a player that is standing in a door or goal, waiting for the other players.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct AtDoor;
+
+/// Going back doesn't need the objectives, they're only for moving on.
 fn on_player_entered_door(
     event: On<CollisionStart>,
     mut commands: Commands,
     door_assets: Res<DoorAssets>,
-    player_query: Query<&Player>,
-    level_selection: ResMut<LevelSelection>,
+    player_query: Query<(Entity, Has<AtDoor>), (With<Player>, Without<Dead>)>,
+    doors: Query<&DoorDirection>,
+    objectives: Res<LevelObjectives>,
+    mut level_entry: ResMut<LevelEntry>,
+    mut level_selection: ResMut<LevelSelection>,
+    ldtk_projects: Query<&LdtkProjectHandle>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
     mut next_screen: ResMut<NextState<Screen>>,
 ) {
     // `colider1` and `body1` refer to the event target and its body.
     // `collider2` and `body2` refer to the other collider and its body.
+    let door_entity = event.collider1;
     let other_entity = event.collider2;
+    let direction = doors.get(door_entity).copied().unwrap_or_default();
 
     if player_query.contains(other_entity) {
+        commands.entity(other_entity).insert(AtDoor);
+
+        // Only leave the level once every living player made it to a door.
+        if !player_query
+            .iter()
+            .all(|(player, at_door)| player == other_entity || at_door)
+        {
+            return;
+        }
+
+        if direction == DoorDirection::Next && !objectives.complete() {
+            commands.trigger(ObjectivesIncomplete);
+            return;
+        }
+
         commands.spawn((
             Name::from("Door Sound"),
             sound_effect(door_assets.use_sound.clone()),
         ));
 
-        let indices = match level_selection.into_inner() {
-            LevelSelection::Indices(indices) => indices,
-            _ => panic!("level selection should always be Indices in this game"),
+        let ldtk_project = match loaded_project(&ldtk_projects, &ldtk_project_assets) {
+            Ok(ldtk_project) => ldtk_project,
+            Err(error) => {
+                warn!("Can't go through the door: {error}");
+                return;
+            }
         };
 
-        indices.level += 1;
-
-        if indices.level > 5 {
-            next_screen.set(Screen::Victory);
+        match direction {
+            DoorDirection::Next => {
+                if go_to_next_level(&mut level_selection, ldtk_project, &mut next_screen)
+                    == LevelAdvance::Advanced
+                {
+                    commands.trigger(LevelCompleted);
+                }
+            }
+            DoorDirection::Previous => {
+                // The selection only changes when there is a level to go back to.
+                match retreat_level(&mut level_selection, ldtk_project) {
+                    LevelAdvance::Advanced => *level_entry = LevelEntry::Exit,
+                    LevelAdvance::Finished => warn!("The first level has a door leading back"),
+                    LevelAdvance::NotFound => {}
+                }
+            }
         }
     }
 }
 
+/// Places the players next to the door leading on from the level they came back to, on the
+/// side facing the level's center. Without such a door they start at the beginning instead.
+fn place_players_at_exit(
+    mut commands: Commands,
+    mut level_events: MessageReader<LevelEvent>,
+    mut level_entry: ResMut<LevelEntry>,
+    doors: Query<(&GlobalTransform, &DoorDirection)>,
+    levels: Query<(&LevelIid, &GlobalTransform)>,
+    ldtk_projects: Query<&LdtkProjectHandle>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+) {
+    let transformed = level_events
+        .read()
+        .any(|event| matches!(event, LevelEvent::Transformed(_)));
+    if !transformed || *level_entry != LevelEntry::Exit {
+        return;
+    }
+    *level_entry = LevelEntry::Start;
+
+    let exit = doors
+        .iter()
+        .find(|(_, direction)| **direction == DoorDirection::Next)
+        .map(|(transform, _)| transform.translation().truncate());
+    let level_center = levels
+        .single()
+        .ok()
+        .and_then(|(level_iid, level_transform)| {
+            let level = loaded_project(&ldtk_projects, &ldtk_project_assets)
+                .ok()?
+                .get_raw_level_by_iid(&level_iid.to_string())?;
+            let size = Vec2::new(level.px_wid as f32, level.px_hei as f32);
+            Some(level_transform.translation().truncate() + size / 2.0)
+        });
+    let (Some(exit), Some(level_center)) = (exit, level_center) else {
+        warn!("The level has no door leading on, starting at its beginning");
+        commands.trigger(RespawnPlayers);
+        return;
+    };
+
+    let side = if level_center.x < exit.x { -1.0 } else { 1.0 };
+    commands.trigger(SpawnPlayerAt(exit + Vec2::X * side * EXIT_OFFSET));
+}
+
+pub(super) fn on_player_left_door(
+    event: On<CollisionEnd>,
+    mut commands: Commands,
+    player_query: Query<(), With<AtDoor>>,
+) {
+    let other_entity = event.collider2;
+
+    if player_query.contains(other_entity) {
+        commands.entity(other_entity).remove::<AtDoor>();
+    }
+}
+
 #[derive(Resource, Asset, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct DoorAssets {
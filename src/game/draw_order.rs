@@ -0,0 +1,159 @@
+//! Keeps the draw order of level entities the same everywhere: background, hazards, the
+//! players, then the foreground.
+//!
+//! Entities spawned from LDtk would otherwise be drawn at the depth of whatever they're
+//! attached to, so a spike could end up in front of a player in one level and behind them in
+//! the next. Every entity with a [`DrawLayer`] is kept at that layer's depth in the world,
+//! whatever its parents are. Each entity type picks its layer, and LDtk entities can override
+//! it with the enum field `DrawLayer`. The depths themselves are in [`DrawOrder`].
+
+use bevy::{
+    prelude::*,
+    transform::{TransformSystems, helper::TransformHelper},
+};
+use bevy_ecs_ldtk::{EntityInstance, prelude::LdtkFields};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<DrawOrder>();
+    app.add_systems(Update, override_draw_layers);
+    app.add_systems(
+        PostUpdate,
+        apply_draw_layers.before(TransformSystems::Propagate),
+    );
+}
+
+/// How far off an entity's depth may be before it's moved back to its layer.
+const Z_TOLERANCE: f32 = 0.001;
+
+/// Which group of things an entity is drawn with.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub enum DrawLayer {
+    /// Behind everything that moves, like doors and pickups.
+    #[default]
+    Background,
+    Hazard,
+    Player,
+    /// In front of the players.
+    Foreground,
+}
+
+/// The world depth of each [`DrawLayer`]. They're all above the LDtk layers of a level.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct DrawOrder {
+    pub background: f32,
+    pub hazard: f32,
+    pub player: f32,
+    pub foreground: f32,
+}
+
+impl Default for DrawOrder {
+    fn default() -> Self {
+        Self {
+            background: 8.0,
+            hazard: 9.0,
+            player: 10.0,
+            foreground: 11.0,
+        }
+    }
+}
+
+impl DrawOrder {
+    pub fn z(&self, layer: DrawLayer) -> f32 {
+        match layer {
+            DrawLayer::Background => self.background,
+            DrawLayer::Hazard => self.hazard,
+            DrawLayer::Player => self.player,
+            DrawLayer::Foreground => self.foreground,
+        }
+    }
+}
+
+fn override_draw_layers(
+    mut commands: Commands,
+    instances: Query<(Entity, &EntityInstance), (Added<EntityInstance>, With<DrawLayer>)>,
+) {
+    for (entity, instance) in instances {
+        let layer = match instance.get_enum_field("DrawLayer").map(String::as_str) {
+            Ok("Background") => DrawLayer::Background,
+            Ok("Hazard") => DrawLayer::Hazard,
+            Ok("Player") => DrawLayer::Player,
+            Ok("Foreground") => DrawLayer::Foreground,
+            _ => continue,
+        };
+        commands.entity(entity).insert(layer);
+    }
+}
+
+/// Moves entities to their layer's depth by changing their own z, which leaves their parents
+/// alone.
+///
+/// Runs right before the transforms are propagated, so new entities are drawn at their depth
+/// from their very first frame. Their parents may be just as new, so the parents' depth is
+/// worked out from the ancestors' own transforms instead of their [`GlobalTransform`]s.
+fn apply_draw_layers(
+    draw_order: Res<DrawOrder>,
+    entities: Query<(Entity, &DrawLayer, Option<&ChildOf>)>,
+    mut transforms: ParamSet<(TransformHelper, Query<&mut Transform, With<DrawLayer>>)>,
+) {
+    let depths: Vec<(Entity, f32)> = {
+        let helper = transforms.p0();
+        entities
+            .iter()
+            .map(|(entity, &layer, parent)| {
+                let parent_z = parent
+                    .and_then(|parent| helper.compute_global_transform(parent.parent()).ok())
+                    .map_or(0.0, |parent_transform| parent_transform.translation().z);
+                (entity, draw_order.z(layer) - parent_z)
+            })
+            .collect()
+    };
+
+    let mut transforms = transforms.p1();
+    for (entity, z) in depths {
+        let Ok(mut transform) = transforms.get_mut(entity) else {
+            continue;
+        };
+        // Rounding would otherwise keep nudging it every frame.
+        if (transform.translation.z - z).abs() > Z_TOLERANCE {
+            transform.translation.z = z;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::transform::TransformPlugin;
+
+    use super::*;
+    use crate::testing::test_app;
+
+    #[test]
+    fn new_entities_start_at_their_layer() {
+        let mut app = test_app();
+        app.add_plugins((TransformPlugin, plugin));
+        let level = app
+            .world_mut()
+            .spawn(Transform::from_xyz(0.0, 0.0, 3.0))
+            .id();
+        let layer = app
+            .world_mut()
+            .spawn((Transform::from_xyz(0.0, 0.0, 2.0), ChildOf(level)))
+            .id();
+        let hazard = app
+            .world_mut()
+            .spawn((DrawLayer::Hazard, Transform::default(), ChildOf(layer)))
+            .id();
+
+        app.update();
+
+        let z = app
+            .world()
+            .get::<GlobalTransform>(hazard)
+            .unwrap()
+            .translation()
+            .z;
+        assert_eq!(z, DrawOrder::default().hazard);
+    }
+}
@@ -0,0 +1,360 @@
+//! Enemies that walk back and forth and chase players that come too close.
+//!
+//! In LDtk these are `Enemy` entities with these optional float fields:
+//! - `PatrolDistance`: how far they walk to either side of where they were placed, in pixels,
+//! - `DetectionRange`: how close a player has to get to be chased, in pixels,
+//! - `Speed`: the walking speed while patrolling,
+//! - `ChaseSpeed`: the walking speed while chasing.
+//!
+//! Touching an enemy kills a player the same way spikes do.
+
+use std::time::Duration;
+
+use avian2d::prelude::{
+    Collider, CollisionEnd, CollisionEventsEnabled, CollisionStart, LinearVelocity, Sensor,
+};
+use bevy::{
+    ecs::{lifecycle::HookContext, world::DeferredWorld},
+    prelude::*,
+};
+use bevy_ecs_ldtk::{EntityInstance, LdtkEntity, app::LdtkEntityAppExt, prelude::LdtkFields};
+
+use crate::{
+    AppSystems, PausableSystems, Pause,
+    asset_tracking::{LoadResource, load_pixel_image},
+    game::{
+        animation::{Animation, AnimationData, AnimationState, AtlasGrid, Facing, Repeat},
+        assist::AssistMode,
+        colliders::{ColliderBundle, GameLayer},
+        draw_order::DrawLayer,
+        level::ResetLevel,
+        physics::Interpolated,
+        player::{Dead, DeathCause, KillPlayer, Player},
+    },
+    gameplay_frozen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.load_resource::<EnemyAssets>();
+    app.register_ldtk_entity::<EnemyBundle>("Enemy");
+    app.add_observer(reset_enemies);
+    app.add_systems(
+        Update,
+        (
+            add_enemy_sprite.in_set(AppSystems::Update),
+            animate_enemies
+                .in_set(AppSystems::Update)
+                .in_set(PausableSystems),
+        ),
+    );
+    app.add_systems(
+        FixedUpdate,
+        (stop_chasing_dead_players, move_enemies)
+            .chain()
+            .run_if(in_state(Pause(false)).and(not(gameplay_frozen))),
+    );
+}
+
+#[derive(Clone, Copy, Debug, Component, Reflect)]
+#[reflect(Component)]
+#[require(EnemyState, PatrolRoute, Interpolated, DrawLayer::Hazard)]
+#[component(on_add = on_enemy_add)]
+pub struct Enemy {
+    pub patrol_distance: f32,
+    pub detection_range: f32,
+    pub speed: f32,
+    pub chase_speed: f32,
+}
+
+impl Default for Enemy {
+    fn default() -> Self {
+        Self {
+            patrol_distance: 32.0,
+            detection_range: 48.0,
+            speed: 20.0,
+            chase_speed: 45.0,
+        }
+    }
+}
+
+/// What an [`Enemy`] is up to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Component, Reflect)]
+#[reflect(Component)]
+pub enum EnemyState {
+    /// Walking back and forth around where it was placed.
+    #[default]
+    Patrol,
+    /// Following a player that came within its detection range.
+    Chase(Entity),
+}
+
+/// Where an [`Enemy`] patrols around, and which way it's currently walking.
+#[derive(Clone, Copy, Debug, Component, Reflect)]
+#[reflect(Component)]
+struct PatrolRoute {
+    /// Taken from the first position the enemy is seen at, since the hook runs before
+    /// LDtk places it.
+    origin: Option<Vec2>,
+    direction: f32,
+}
+
+impl Default for PatrolRoute {
+    fn default() -> Self {
+        Self {
+            origin: None,
+            direction: 1.0,
+        }
+    }
+}
+
+pub fn on_enemy_add(mut world: DeferredWorld, context: HookContext) {
+    let enemy_entity = context.entity;
+    let Some(detection_range) = world
+        .get::<Enemy>(enemy_entity)
+        .map(|enemy| enemy.detection_range)
+    else {
+        return;
+    };
+    world
+        .commands()
+        .entity(enemy_entity)
+        .observe(on_player_touched_enemy)
+        .with_children(|c| {
+            c.spawn((
+                Name::new("Enemy Detection"),
+                CollisionEventsEnabled,
+                Collider::circle(detection_range),
+                Sensor,
+                GameLayer::Hazard.layers(),
+            ))
+            .observe(on_player_detected)
+            .observe(on_player_escaped);
+        });
+}
+
+#[derive(Clone, Debug, Default, Bundle, LdtkEntity)]
+pub struct EnemyBundle {
+    #[with(enemy_from_instance)]
+    enemy: Enemy,
+
+    #[from_entity_instance]
+    collider_bundle: ColliderBundle,
+
+    sensor: Sensor,
+}
+
+fn enemy_from_instance(instance: &EntityInstance) -> Enemy {
+    let default = Enemy::default();
+    let field =
+        |name: &str, default: f32| instance.get_float_field(name).copied().unwrap_or(default);
+    Enemy {
+        patrol_distance: field("PatrolDistance", default.patrol_distance),
+        detection_range: field("DetectionRange", default.detection_range),
+        speed: field("Speed", default.speed),
+        chase_speed: field("ChaseSpeed", default.chase_speed),
+    }
+}
+
+/// There's no art for enemies yet, so they're a red version of the player.
+fn add_enemy_sprite(
+    mut commands: Commands,
+    enemy_assets: Res<EnemyAssets>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    enemies: Query<Entity, Added<Enemy>>,
+) {
+    for enemy in enemies {
+        let layout = enemy_assets.sprite_grid.layout();
+        let animation =
+            Animation::try_new(enemy_animations(), enemy_assets.sprite_grid.frame_count())
+                .unwrap_or_else(|error| {
+                    error!("The enemy doesn't fit its sprite sheet: {error}");
+                    Animation::still(AnimationState::Idle)
+                });
+        commands.entity(enemy).insert((
+            Sprite {
+                color: Color::srgb(1.0, 0.45, 0.4),
+                ..Sprite::from_atlas_image(
+                    enemy_assets.sprite.clone(),
+                    TextureAtlas {
+                        layout: texture_atlas_layouts.add(layout),
+                        index: animation.get_atlas_index(),
+                    },
+                )
+            },
+            animation,
+            Facing::default(),
+        ));
+    }
+}
+
+fn enemy_animations() -> Vec<AnimationData> {
+    let idle = AnimationData {
+        frames: 4,
+        interval: Duration::from_millis(150),
+        state: AnimationState::Idle,
+        atlas_index: 6,
+        repeat: Repeat::Loop,
+    };
+    let walk = AnimationData {
+        frames: 6,
+        interval: Duration::from_millis(80),
+        state: AnimationState::Walking,
+        atlas_index: 0,
+        repeat: Repeat::Loop,
+    };
+    vec![idle, walk]
+}
+
+fn on_player_detected(
+    event: On<CollisionStart>,
+    parents: Query<&ChildOf>,
+    players: Query<(), (With<Player>, Without<Dead>)>,
+    mut enemies: Query<&mut EnemyState>,
+) {
+    let Ok(&ChildOf(enemy_entity)) = parents.get(event.collider1) else {
+        return;
+    };
+    let other_entity = event.collider2;
+    if !players.contains(other_entity) {
+        return;
+    }
+    if let Ok(mut state) = enemies.get_mut(enemy_entity)
+        && *state == EnemyState::Patrol
+    {
+        *state = EnemyState::Chase(other_entity);
+    }
+}
+
+fn on_player_escaped(
+    event: On<CollisionEnd>,
+    parents: Query<&ChildOf>,
+    mut enemies: Query<&mut EnemyState>,
+) {
+    let Ok(&ChildOf(enemy_entity)) = parents.get(event.collider1) else {
+        return;
+    };
+    if let Ok(mut state) = enemies.get_mut(enemy_entity)
+        && *state == EnemyState::Chase(event.collider2)
+    {
+        *state = EnemyState::Patrol;
+    }
+}
+
+/// Dead players don't leave the detection range, so they're let go of here.
+fn stop_chasing_dead_players(
+    mut enemies: Query<&mut EnemyState>,
+    players: Query<(), (With<Player>, Without<Dead>)>,
+) {
+    for mut state in &mut enemies {
+        if let EnemyState::Chase(player) = *state
+            && !players.contains(player)
+        {
+            *state = EnemyState::Patrol;
+        }
+    }
+}
+
+fn move_enemies(
+    mut enemies: Query<(
+        &Enemy,
+        &EnemyState,
+        &mut PatrolRoute,
+        &GlobalTransform,
+        &mut LinearVelocity,
+    )>,
+    players: Query<&GlobalTransform, With<Player>>,
+    assist_mode: Res<AssistMode>,
+) {
+    let speed_factor = assist_mode.hazard_speed();
+    for (enemy, state, mut route, transform, mut velocity) in &mut enemies {
+        let position = transform.translation().truncate();
+        let origin = *route.origin.get_or_insert(position);
+
+        velocity.x = match *state {
+            EnemyState::Patrol => {
+                let offset = position.x - origin.x;
+                if offset >= enemy.patrol_distance {
+                    route.direction = -1.0;
+                } else if offset <= -enemy.patrol_distance {
+                    route.direction = 1.0;
+                }
+                route.direction * enemy.speed * speed_factor
+            }
+            EnemyState::Chase(player) => {
+                let Ok(player_transform) = players.get(player) else {
+                    continue;
+                };
+                let distance = player_transform.translation().x - position.x;
+                // Standing right below or above the player would make it jitter back and forth.
+                if distance.abs() < 2.0 {
+                    0.0
+                } else {
+                    route.direction = distance.signum();
+                    route.direction * enemy.chase_speed * speed_factor
+                }
+            }
+        };
+    }
+}
+
+fn animate_enemies(
+    mut enemies: Query<(&LinearVelocity, &mut Animation, &mut Facing), With<Enemy>>,
+) {
+    for (velocity, mut animation, mut facing) in &mut enemies {
+        if velocity.x == 0.0 {
+            animation.update_state(AnimationState::Idle);
+        } else {
+            animation.update_state(AnimationState::Walking);
+            *facing = if velocity.x < 0.0 {
+                Facing::Left
+            } else {
+                Facing::Right
+            };
+        }
+    }
+}
+
+fn on_player_touched_enemy(event: On<CollisionStart>, mut commands: Commands) {
+    commands.trigger(KillPlayer {
+        entity: event.collider2,
+        cause: DeathCause::Enemy,
+    });
+}
+
+/// Sends enemies back to where they started, so every attempt at a level is the same.
+fn reset_enemies(
+    _: On<ResetLevel>,
+    mut enemies: Query<(&mut EnemyState, &mut PatrolRoute, &mut Transform, &ChildOf)>,
+    global_transforms: Query<&GlobalTransform>,
+) {
+    for (mut state, mut route, mut transform, child_of) in &mut enemies {
+        *state = EnemyState::Patrol;
+        route.direction = 1.0;
+        let (Some(origin), Ok(parent)) = (route.origin, global_transforms.get(child_of.parent()))
+        else {
+            continue;
+        };
+        // The origin is in world space, but enemies are placed relative to their level.
+        let local = origin - parent.translation().truncate();
+        transform.translation.x = local.x;
+        transform.translation.y = local.y;
+    }
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct EnemyAssets {
+    #[dependency]
+    sprite: Handle<Image>,
+    sprite_grid: AtlasGrid,
+}
+
+impl FromWorld for EnemyAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            sprite: load_pixel_image(assets, "images/hero.png"),
+            sprite_grid: AtlasGrid::new(UVec2::splat(16), 4, 5),
+        }
+    }
+}
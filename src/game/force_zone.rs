@@ -0,0 +1,90 @@
+//! Rectangular zones that push players and other bodies around, like wind or updrafts.
+//!
+//! In LDtk these are `ForceZone` entities with the float fields `ForceX` and `ForceY`.
+
+use avian2d::prelude::{CollidingEntities, LinearVelocity, RigidBody, Sensor};
+use bevy::prelude::*;
+use bevy_ecs_ldtk::{EntityInstance, LdtkEntity, app::LdtkEntityAppExt, prelude::LdtkFields};
+use bevy_tnua::TnuaUserControlsSystems;
+
+use crate::game::colliders::ColliderBundle;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_ldtk_entity::<ForceZoneBundle>("ForceZone");
+    app.add_systems(
+        FixedUpdate,
+        apply_force_zones.before(TnuaUserControlsSystems),
+    );
+}
+
+/// Pushes every dynamic body inside it with a constant force, in pixels per second squared.
+///
+/// Tnua would cancel a horizontal push on players by steering against it, so for them it
+/// builds up a [`ForceZoneDrift`] instead.
+#[derive(Clone, Copy, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct ForceZone {
+    pub force: Vec2,
+}
+
+#[derive(Clone, Debug, Default, Bundle, LdtkEntity)]
+pub struct ForceZoneBundle {
+    #[with(force_zone_from_instance)]
+    force_zone: ForceZone,
+
+    #[from_entity_instance]
+    collider_bundle: ColliderBundle,
+
+    sensor: Sensor,
+    colliding_entities: CollidingEntities,
+}
+
+fn force_zone_from_instance(instance: &EntityInstance) -> ForceZone {
+    ForceZone {
+        force: Vec2::new(
+            instance.get_float_field("ForceX").copied().unwrap_or(0.0),
+            instance.get_float_field("ForceY").copied().unwrap_or(0.0),
+        ),
+    }
+}
+
+/// How quickly a player's drift follows the zones they're in, per second. It also keeps the
+/// drift from growing forever, like air resistance would.
+const DRIFT_DRAG: f32 = 2.0;
+
+/// The horizontal velocity that force zones have built up on a player, which is added to the
+/// velocity they walk at. It dies down again after they leave the zones.
+#[derive(Clone, Copy, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct ForceZoneDrift(pub f32);
+
+/// Overlapping zones add up.
+fn apply_force_zones(
+    time: Res<Time>,
+    zones: Query<(&ForceZone, &CollidingEntities)>,
+    mut bodies: Query<(
+        Entity,
+        &RigidBody,
+        &mut LinearVelocity,
+        Option<&mut ForceZoneDrift>,
+    )>,
+) {
+    let delta = time.delta_secs();
+    for (body, rigid_body, mut velocity, drift) in &mut bodies {
+        if *rigid_body != RigidBody::Dynamic {
+            continue;
+        }
+        let force: Vec2 = zones
+            .iter()
+            .filter(|(_, colliding)| colliding.contains(&body))
+            .map(|(zone, _)| zone.force)
+            .sum();
+        match drift {
+            Some(mut drift) => {
+                drift.0 += (force.x - drift.0 * DRIFT_DRAG) * delta;
+                velocity.y += force.y * delta;
+            }
+            None => velocity.0 += force * delta,
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! Records the player's path through a level and replays the fastest one as a ghost.
+//!
+//! A run only counts once the first player reaches a door or goal, and it's saved when the
+//! level is left. Dying or restarting starts a new recording.
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use bevy_ecs_ldtk::LevelIid;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AppSystems, PausableSystems, Pause,
+    game::{
+        animation::AnimationState,
+        door::AtDoor,
+        level::LevelChanged,
+        player::{Player, PlayerAssets, PlayerIndex},
+        rewind::Rewinding,
+        skin::PlayerSkin,
+    },
+    gameplay_frozen,
+    save::SaveData,
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<GhostRecorder>();
+    app.add_systems(
+        FixedUpdate,
+        record_ghost.run_if(
+            in_state(Screen::Gameplay)
+                .and(in_state(Pause(false)))
+                .and(not(gameplay_frozen)),
+        ),
+    );
+    app.add_systems(
+        Update,
+        (
+            (start_attempt, mark_exit_reached).in_set(AppSystems::Update),
+            play_ghost
+                .in_set(AppSystems::Update)
+                .in_set(PausableSystems),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_observer(save_ghost_on_level_change);
+    app.add_systems(OnEnter(Screen::Victory), save_ghost);
+    app.add_systems(OnEnter(Screen::Title), reset_recorder);
+}
+
+/// Only the first player is recorded.
+const RECORDED_PLAYER: PlayerIndex = PlayerIndex(0);
+
+/// A recorded path through a level.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct GhostRun {
+    pub samples: Vec<GhostSample>,
+}
+
+/// Where the player was, some time after the attempt started.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub struct GhostSample {
+    /// Seconds since the attempt started.
+    pub time: f32,
+    pub x: f32,
+    pub y: f32,
+}
+
+impl GhostSample {
+    fn position(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+}
+
+impl GhostRun {
+    /// How long the run took.
+    pub fn duration(&self) -> f32 {
+        self.samples.last().map_or(0.0, |sample| sample.time)
+    }
+
+    /// The position along the path at `time`, or `None` once the run is over.
+    fn position_at(&self, time: f32) -> Option<Vec2> {
+        let next = self.samples.iter().position(|sample| sample.time >= time)?;
+        let Some(previous) = next.checked_sub(1).map(|index| self.samples[index]) else {
+            return Some(self.samples[next].position());
+        };
+        let next = self.samples[next];
+        let t = (time - previous.time) / (next.time - previous.time).max(f32::EPSILON);
+        Some(previous.position().lerp(next.position(), t))
+    }
+}
+
+/// The best run of every level, keyed by the level's iid.
+pub type Ghosts = BTreeMap<String, GhostRun>;
+
+/// The attempt that is being recorded right now.
+#[derive(Resource, Debug, Default)]
+pub struct GhostRecorder {
+    level: Option<String>,
+    run: GhostRun,
+    /// Seconds since the attempt started, including any time spent rewinding.
+    elapsed: f32,
+    reached_exit: bool,
+}
+
+impl GhostRecorder {
+    /// Takes the positions `player` passed through in the last `seconds` of the attempt out of
+    /// the run, from the most recent one back, so they can be rewound through. The run then
+    /// picks up again where the rewind ends. Only the recorded player has any, and only until
+    /// the exit.
+    pub fn rewind(&mut self, player: PlayerIndex, seconds: f32) -> Option<Vec<Vec2>> {
+        if player != RECORDED_PLAYER || self.level.is_none() || self.reached_exit {
+            return None;
+        }
+        let since = self.run.duration() - seconds;
+        let path: Vec<_> = self
+            .run
+            .samples
+            .iter()
+            .rev()
+            .take_while(|sample| sample.time >= since)
+            .map(GhostSample::position)
+            .collect();
+        if path.is_empty() {
+            return None;
+        }
+        // Keep the sample the rewind ends on.
+        let kept = self.run.samples.len() - path.len() + 1;
+        self.run.samples.truncate(kept);
+        Some(path)
+    }
+}
+
+/// The translucent sprite replaying a [`GhostRun`].
+#[derive(Component, Debug, Clone, Default)]
+pub struct Ghost {
+    run: GhostRun,
+    elapsed: f32,
+}
+
+fn reset_recorder(mut recorder: ResMut<GhostRecorder>) {
+    *recorder = GhostRecorder::default();
+}
+
+/// Starts recording whenever the recorded player (re)spawns, and replays the level's best run.
+fn start_attempt(
+    mut commands: Commands,
+    mut recorder: ResMut<GhostRecorder>,
+    save_data: Res<SaveData>,
+    player_assets: Res<PlayerAssets>,
+    skin: Res<PlayerSkin>,
+    players: Query<&PlayerIndex, Added<Player>>,
+    levels: Query<&LevelIid>,
+    ghosts: Query<Entity, With<Ghost>>,
+) {
+    if !players.iter().any(|index| *index == RECORDED_PLAYER) {
+        return;
+    }
+    let Ok(level) = levels.single() else {
+        return;
+    };
+
+    *recorder = GhostRecorder {
+        level: Some(level.to_string()),
+        ..default()
+    };
+
+    for ghost in &ghosts {
+        commands.entity(ghost).despawn();
+    }
+    if let Some(run) = save_data.ghosts.get(&level.to_string()) {
+        let definition = skin.definition();
+        commands.spawn((
+            Name::new("Ghost"),
+            Ghost {
+                run: run.clone(),
+                elapsed: 0.0,
+            },
+            Sprite {
+                color: Color::srgba(1.0, 1.0, 1.0, 0.35),
+                ..Sprite::from_atlas_image(
+                    player_assets.skin_image(*skin).clone(),
+                    TextureAtlas {
+                        layout: player_assets.skin_layout(*skin).clone(),
+                        index: definition.first_frame(AnimationState::Idle),
+                    },
+                )
+            },
+            // Drawn above the level's layers, like the players.
+            Transform::from_xyz(0.0, 0.0, 10.0),
+            Visibility::Hidden,
+            DespawnOnExit(Screen::Gameplay),
+        ));
+    }
+}
+
+fn mark_exit_reached(
+    mut recorder: ResMut<GhostRecorder>,
+    players: Query<&PlayerIndex, Added<AtDoor>>,
+) {
+    if players.iter().any(|index| *index == RECORDED_PLAYER) {
+        recorder.reached_exit = true;
+    }
+}
+
+/// Nothing is recorded while the player is rewound, the path back is already in the run.
+fn record_ghost(
+    time: Res<Time>,
+    mut recorder: ResMut<GhostRecorder>,
+    players: Query<(&PlayerIndex, &GlobalTransform, Has<Rewinding>), With<Player>>,
+) {
+    if recorder.level.is_none() || recorder.reached_exit {
+        return;
+    }
+    let Some((_, transform, rewinding)) = players
+        .iter()
+        .find(|(index, _, _)| **index == RECORDED_PLAYER)
+    else {
+        return;
+    };
+
+    // Rewinding takes time too, so a run doesn't get any faster for it.
+    recorder.elapsed += time.delta_secs();
+    if rewinding {
+        return;
+    }
+    let position = transform.translation();
+    let time = recorder.elapsed;
+    recorder.run.samples.push(GhostSample {
+        time,
+        x: position.x,
+        y: position.y,
+    });
+}
+
+fn save_ghost_on_level_change(
+    _: On<LevelChanged>,
+    mut recorder: ResMut<GhostRecorder>,
+    mut save_data: ResMut<SaveData>,
+) {
+    save_finished_run(&mut recorder, &mut save_data);
+}
+
+fn save_ghost(mut recorder: ResMut<GhostRecorder>, mut save_data: ResMut<SaveData>) {
+    save_finished_run(&mut recorder, &mut save_data);
+}
+
+/// Keeps the recorded run if it reached the exit faster than the saved one.
+fn save_finished_run(recorder: &mut GhostRecorder, save_data: &mut SaveData) {
+    if !recorder.reached_exit {
+        return;
+    }
+    let Some(level) = recorder.level.take() else {
+        return;
+    };
+    let run = std::mem::take(&mut recorder.run);
+    let is_best = save_data
+        .ghosts
+        .get(&level)
+        .is_none_or(|best| run.duration() < best.duration());
+    if is_best {
+        save_data.ghosts.insert(level, run);
+    }
+}
+
+fn play_ghost(time: Res<Time>, ghosts: Query<(&mut Ghost, &mut Transform, &mut Visibility)>) {
+    for (mut ghost, mut transform, mut visibility) in ghosts {
+        ghost.elapsed += time.delta_secs();
+        match ghost.run.position_at(ghost.elapsed) {
+            Some(position) => {
+                transform.translation = position.extend(transform.translation.z);
+                *visibility = Visibility::Visible;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
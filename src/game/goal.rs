@@ -0,0 +1,339 @@
+//! Level exits that can be placed anywhere, independent of the level order.
+//!
+//! In LDtk these are `Goal` entities with a bool field `Final`.
+//! Reaching a goal moves on to the next level, reaching a final goal plays a fanfare and
+//! wins the game.
+//!
+//! Like walls, goals placed next to each other share a sensor, so walking from one goal tile
+//! into the next doesn't count as leaving and reaching the goal again.
+
+use std::collections::HashSet;
+
+use avian2d::prelude::{Collider, CollisionEventsEnabled, CollisionStart, RigidBody, Sensor};
+use bevy::{
+    ecs::{lifecycle::HookContext, world::DeferredWorld},
+    platform::collections::HashMap,
+    prelude::*,
+};
+use bevy_ecs_ldtk::{
+    EntityInstance, GridCoords, LdtkEntity, LevelSelection,
+    app::LdtkEntityAppExt,
+    assets::{LdtkProject, LdtkProjectHandle},
+    prelude::LdtkFields,
+};
+
+use crate::{
+    asset_tracking::LoadResource,
+    audio::sound_effect,
+    game::{
+        colliders::GameLayer,
+        door::{AtDoor, DoorAssets, on_player_left_door},
+        draw_order::DrawLayer,
+        grid_coords::{GRID_SIZE, merge_int_grid_rects},
+        level::{LevelAdvance, LevelCompleted, go_to_next_level, loaded_project},
+        objectives::{LevelObjectives, ObjectivesIncomplete},
+        player::{Dead, Player},
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.load_resource::<GoalAssets>();
+    app.register_ldtk_entity::<GoalBundle>("Goal");
+    app.add_systems(Update, spawn_goal_sensors);
+}
+
+/// A goal tile. The players reach it through the [`GoalSensor`] it shares with the goals
+/// next to it.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+#[require(DrawLayer::Background)]
+pub struct Goal {
+    /// Whether reaching this goal wins the game.
+    pub is_final: bool,
+}
+
+#[derive(Clone, Debug, Default, Bundle, LdtkEntity)]
+pub struct GoalBundle {
+    #[with(goal_from_instance)]
+    goal: Goal,
+
+    #[sprite_sheet]
+    sprite_sheet: Sprite,
+
+    #[grid_coords]
+    grid_coords: GridCoords,
+}
+
+/// The sensor of a group of neighbouring [`Goal`]s.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+#[component(on_add = on_goal_sensor_add)]
+pub struct GoalSensor {
+    /// Whether reaching these goals wins the game.
+    pub is_final: bool,
+}
+
+pub fn on_goal_sensor_add(mut world: DeferredWorld, context: HookContext) {
+    let sensor_entity = context.entity;
+    world
+        .commands()
+        .entity(sensor_entity)
+        .observe(on_player_reached_goal)
+        .observe(on_player_left_door);
+}
+
+/// Marks the fanfare of a final goal, so the victory screen doesn't play over it.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Fanfare;
+
+fn goal_from_instance(instance: &EntityInstance) -> Goal {
+    Goal {
+        is_final: instance.get_bool_field("Final").copied().unwrap_or(false),
+    }
+}
+
+/// Spawns the sensors of the goals, merged into rectangles the same way as the walls.
+///
+/// Final and regular goals are never merged, so each sensor knows where it leads.
+fn spawn_goal_sensors(
+    mut commands: Commands,
+    goal_query: Query<(&GridCoords, &Goal, &ChildOf), Added<Goal>>,
+    parent_query: Query<&ChildOf, Without<Goal>>,
+) {
+    // A goal's direct parent is the entity layer, the level is its grandparent.
+    let mut level_to_goal_locations: HashMap<Entity, HashMap<bool, HashSet<GridCoords>>> =
+        HashMap::new();
+    for (&grid_coords, goal, parent) in &goal_query {
+        if let Ok(grandparent) = parent_query.get(parent.parent()) {
+            level_to_goal_locations
+                .entry(grandparent.parent())
+                .or_default()
+                .entry(goal.is_final)
+                .or_default()
+                .insert(grid_coords);
+        }
+    }
+
+    for (level_entity, level_goals) in level_to_goal_locations {
+        for (is_final, goals) in level_goals {
+            // Unlike walls, goals are few and far between, so the grid only has to reach as
+            // far as they do.
+            let width = goals
+                .iter()
+                .map(|goal| goal.x + 1)
+                .max()
+                .unwrap_or_default();
+            let height = goals
+                .iter()
+                .map(|goal| goal.y + 1)
+                .max()
+                .unwrap_or_default();
+            let goal_rects = merge_int_grid_rects(&goals, width, height);
+
+            // As children of the level, the sensors are placed relative to it and go away
+            // with it.
+            commands.entity(level_entity).with_children(|level| {
+                for goal_rect in goal_rects {
+                    let width = (goal_rect.width() + 1) as f32 * GRID_SIZE as f32;
+                    let height = (goal_rect.height() + 1) as f32 * GRID_SIZE as f32;
+                    level.spawn((
+                        Name::new("Goal Sensor"),
+                        GoalSensor { is_final },
+                        Collider::rectangle(width, height),
+                        RigidBody::Static,
+                        Sensor,
+                        CollisionEventsEnabled,
+                        GameLayer::Zone.layers(),
+                        Transform::from_xyz(
+                            (goal_rect.min.x + goal_rect.max.x + 1) as f32 * GRID_SIZE as f32 / 2.,
+                            (goal_rect.min.y + goal_rect.max.y + 1) as f32 * GRID_SIZE as f32 / 2.,
+                            0.,
+                        ),
+                    ));
+                }
+            });
+        }
+    }
+}
+
+fn on_player_reached_goal(
+    event: On<CollisionStart>,
+    mut commands: Commands,
+    door_assets: Res<DoorAssets>,
+    goal_assets: Res<GoalAssets>,
+    goal_query: Query<&GoalSensor>,
+    player_query: Query<(Entity, Has<AtDoor>), (With<Player>, Without<Dead>)>,
+    objectives: Res<LevelObjectives>,
+    mut level_selection: ResMut<LevelSelection>,
+    ldtk_projects: Query<&LdtkProjectHandle>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    // `colider1` and `body1` refer to the event target and its body.
+    // `collider2` and `body2` refer to the other collider and its body.
+    let goal_entity = event.collider1;
+    let other_entity = event.collider2;
+
+    let Ok(goal) = goal_query.get(goal_entity) else {
+        return;
+    };
+    if !player_query.contains(other_entity) {
+        return;
+    }
+
+    // Like doors, goals wait for every living player.
+    commands.entity(other_entity).insert(AtDoor);
+    if !player_query
+        .iter()
+        .all(|(player, at_door)| player == other_entity || at_door)
+    {
+        return;
+    }
+
+    if !objectives.complete() {
+        commands.trigger(ObjectivesIncomplete);
+        return;
+    }
+
+    if goal.is_final {
+        commands.spawn((
+            Name::from("Goal Fanfare"),
+            sound_effect(goal_assets.fanfare.clone()),
+            Fanfare,
+        ));
+        next_screen.set(Screen::Victory);
+        return;
+    }
+
+    commands.spawn((
+        Name::from("Door Sound"),
+        sound_effect(door_assets.use_sound.clone()),
+    ));
+
+    let ldtk_project = match loaded_project(&ldtk_projects, &ldtk_project_assets) {
+        Ok(ldtk_project) => ldtk_project,
+        Err(error) => {
+            warn!("Can't leave through the goal: {error}");
+            return;
+        }
+    };
+
+    if go_to_next_level(&mut level_selection, ldtk_project, &mut next_screen)
+        == LevelAdvance::Advanced
+    {
+        commands.trigger(LevelCompleted);
+    }
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct GoalAssets {
+    #[dependency]
+    pub fanfare: Handle<AudioSource>,
+}
+
+impl FromWorld for GoalAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            // There's no fanfare of its own yet, so it borrows the victory music.
+            fanfare: assets.load("audio/music/won.ogg"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::test_app;
+
+    /// Spawns goals at `cells` under a level and an entity layer, like LDtk does.
+    fn spawn_goals(app: &mut App, cells: &[(i32, i32, bool)]) -> Entity {
+        let level = app.world_mut().spawn(Transform::default()).id();
+        let layer = app.world_mut().spawn(ChildOf(level)).id();
+        for &(x, y, is_final) in cells {
+            app.world_mut()
+                .spawn((Goal { is_final }, GridCoords::new(x, y), ChildOf(layer)));
+        }
+        level
+    }
+
+    /// The sensors of `level` as whether they're final, their size and their center.
+    fn sensors(app: &mut App, level: Entity) -> Vec<(bool, Vec2, Vec2)> {
+        let mut query = app
+            .world_mut()
+            .query::<(&GoalSensor, &Collider, &Transform, &ChildOf)>();
+        let mut sensors: Vec<_> = query
+            .iter(app.world())
+            .filter(|(.., parent)| parent.parent() == level)
+            .map(|(sensor, collider, transform, _)| {
+                let cuboid = collider
+                    .shape()
+                    .as_cuboid()
+                    .expect("goal sensors are boxes");
+                let size = Vec2::new(cuboid.half_extents.x, cuboid.half_extents.y) * 2.0;
+                (sensor.is_final, size, transform.translation.truncate())
+            })
+            .collect();
+        sensors.sort_by(|a, b| a.2.x.total_cmp(&b.2.x).then(a.2.y.total_cmp(&b.2.y)));
+        sensors
+    }
+
+    #[test]
+    fn neighbouring_goals_share_a_sensor() {
+        let mut app = test_app();
+        app.add_systems(Update, spawn_goal_sensors);
+        // A 2x2 block of goals, and one on its own further right.
+        let level = spawn_goals(
+            &mut app,
+            &[
+                (1, 1, false),
+                (2, 1, false),
+                (1, 2, false),
+                (2, 2, false),
+                (5, 1, false),
+            ],
+        );
+
+        app.update();
+
+        assert_eq!(
+            sensors(&mut app, level),
+            vec![
+                (false, Vec2::new(32.0, 32.0), Vec2::new(32.0, 32.0)),
+                (false, Vec2::new(16.0, 16.0), Vec2::new(88.0, 24.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn final_goals_get_their_own_sensor() {
+        let mut app = test_app();
+        app.add_systems(Update, spawn_goal_sensors);
+        let level = spawn_goals(&mut app, &[(0, 0, false), (1, 0, true)]);
+
+        app.update();
+
+        assert_eq!(
+            sensors(&mut app, level),
+            vec![
+                (false, Vec2::splat(16.0), Vec2::new(8.0, 8.0)),
+                (true, Vec2::splat(16.0), Vec2::new(24.0, 8.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn sensors_are_only_spawned_once() {
+        let mut app = test_app();
+        app.add_systems(Update, spawn_goal_sensors);
+        let level = spawn_goals(&mut app, &[(0, 0, false)]);
+
+        app.update();
+        app.update();
+
+        assert_eq!(sensors(&mut app, level).len(), 1);
+    }
+}
@@ -0,0 +1,94 @@
+//! Zones that turn gravity upside down for the players passing through them, so they walk on
+//! the ceiling until they pass through another one.
+//!
+//! In LDtk these are `GravityFlipZone` entities, sized freely like force zones.
+
+use avian2d::prelude::{CollisionStart, GravityScale, LinearVelocity, Sensor};
+use bevy::{
+    ecs::{lifecycle::HookContext, world::DeferredWorld},
+    prelude::*,
+};
+use bevy_ecs_ldtk::{LdtkEntity, app::LdtkEntityAppExt};
+
+use crate::game::{
+    colliders::ColliderBundle,
+    player::{Dead, Player},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_ldtk_entity::<GravityFlipZoneBundle>("GravityFlipZone");
+    app.add_systems(FixedUpdate, apply_gravity_flip);
+}
+
+/// How much of their vertical speed players keep when gravity flips.
+/// Without slowing down, falling players would shoot up into the ceiling.
+const FLIP_VELOCITY_RETAINED: f32 = 0.25;
+
+#[derive(Clone, Copy, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+#[component(on_add = on_gravity_flip_zone_add)]
+pub struct GravityFlipZone;
+
+pub fn on_gravity_flip_zone_add(mut world: DeferredWorld, context: HookContext) {
+    let zone_entity = context.entity;
+    world
+        .commands()
+        .entity(zone_entity)
+        .observe(on_player_entered_zone);
+}
+
+#[derive(Clone, Debug, Default, Bundle, LdtkEntity)]
+pub struct GravityFlipZoneBundle {
+    gravity_flip_zone: GravityFlipZone,
+
+    #[from_entity_instance]
+    collider_bundle: ColliderBundle,
+
+    sensor: Sensor,
+}
+
+/// Which way gravity pulls a player.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Component, Reflect)]
+#[reflect(Component)]
+pub struct GravityFlip {
+    pub flipped: bool,
+}
+
+impl GravityFlip {
+    /// `1.0` while gravity pulls down, `-1.0` while it's flipped.
+    pub fn sign(self) -> f32 {
+        if self.flipped { -1.0 } else { 1.0 }
+    }
+
+    /// The direction the player stands up in, which Tnua needs to find the ground.
+    pub fn up(self) -> Dir3 {
+        if self.flipped { Dir3::NEG_Y } else { Dir3::Y }
+    }
+}
+
+fn on_player_entered_zone(
+    event: On<CollisionStart>,
+    mut players: Query<&mut GravityFlip, (With<Player>, Without<Dead>)>,
+) {
+    if let Ok(mut gravity_flip) = players.get_mut(event.collider2) {
+        gravity_flip.flipped = !gravity_flip.flipped;
+    }
+}
+
+/// Turns the player's gravity and sprite around. The animations are driven by Tnua, which
+/// works relative to the player's up direction, so they stay the same.
+fn apply_gravity_flip(
+    mut commands: Commands,
+    players: Query<
+        (Entity, &GravityFlip, &mut Sprite, &mut LinearVelocity),
+        (With<Player>, Changed<GravityFlip>),
+    >,
+) {
+    for (player, gravity_flip, mut sprite, mut velocity) in players {
+        sprite.flip_y = gravity_flip.flipped;
+        commands
+            .entity(player)
+            .insert(GravityScale(gravity_flip.sign()));
+        velocity.y *= FLIP_VELOCITY_RETAINED;
+    }
+}
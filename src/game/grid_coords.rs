@@ -1,12 +1,102 @@
-use bevy::prelude::*;
+use std::collections::HashSet;
+
+use bevy::{platform::collections::HashMap, prelude::*};
 use bevy_ecs_ldtk::GridCoords;
 
 pub fn plugin(app: &mut App) {
     app.add_systems(Update, translate_grid_coords_entities);
 }
 
-const GRID_SIZE: i32 = 16;
+/// The size of a level's tiles in pixels.
+pub const GRID_SIZE: i32 = 16;
+
+/// The cell of the grid that `position` lies in, relative to a level at `level_origin`.
+/// Both are in world space.
+pub fn snap_to_grid(position: Vec2, level_origin: Vec2) -> GridCoords {
+    bevy_ecs_ldtk::utils::translation_to_grid_coords(
+        position - level_origin,
+        IVec2::splat(GRID_SIZE),
+    )
+}
+
+/// The world space center of a cell in a level at `level_origin`.
+pub fn grid_coords_to_world(grid_coords: GridCoords, level_origin: Vec2) -> Vec2 {
+    level_origin
+        + bevy_ecs_ldtk::utils::grid_coords_to_translation(grid_coords, IVec2::splat(GRID_SIZE))
+}
+
+/// Combines the `cells` of a `width` by `height` int grid into as few rectangles as is easily
+/// possible, e.g. to give a whole area of tiles a handful of colliders instead of one each.
+/// Each rectangle spans the cells from its `min` to its `max`, both included.
+///
+/// The algorithm is a nice compromise between simplicity, speed, and a small number of
+/// rectangles. In basic terms, it will:
+/// 1. combine the cells into flat "plates" in each individual row
+/// 2. combine the plates into rectangles across multiple rows wherever possible
+pub fn merge_int_grid_rects(cells: &HashSet<GridCoords>, width: i32, height: i32) -> Vec<IRect> {
+    /// Represents a wide area that is 1 tile tall
+    #[derive(Clone, Eq, PartialEq, Debug, Default, Hash)]
+    struct Plate {
+        left: i32,
+        right: i32,
+    }
+
+    // combine cells into flat "plates" in each individual row
+    let mut plate_stack: Vec<Vec<Plate>> = Vec::new();
+
+    for y in 0..height {
+        let mut row_plates: Vec<Plate> = Vec::new();
+        let mut plate_start = None;
+
+        // + 1 to the width so the algorithm "terminates" plates that touch the right edge
+        for x in 0..=width {
+            match (plate_start, cells.contains(&GridCoords { x, y })) {
+                (Some(s), false) => {
+                    row_plates.push(Plate {
+                        left: s,
+                        right: x - 1,
+                    });
+                    plate_start = None;
+                }
+                (None, true) => plate_start = Some(x),
+                _ => (),
+            }
+        }
 
+        plate_stack.push(row_plates);
+    }
+
+    // combine "plates" into rectangles across multiple rows
+    let mut rect_builder: HashMap<Plate, IRect> = HashMap::new();
+    let mut prev_row: Vec<Plate> = Vec::new();
+    let mut rects: Vec<IRect> = Vec::new();
+
+    // an extra empty row so the algorithm "finishes" the rects that touch the top edge
+    plate_stack.push(Vec::new());
+
+    for (y, current_row) in plate_stack.into_iter().enumerate() {
+        for prev_plate in &prev_row {
+            if !current_row.contains(prev_plate) {
+                // remove the finished rect so that the same plate in the future starts a new rect
+                if let Some(rect) = rect_builder.remove(prev_plate) {
+                    rects.push(rect);
+                }
+            }
+        }
+        for plate in &current_row {
+            rect_builder
+                .entry(plate.clone())
+                .and_modify(|e| e.max.y += 1)
+                .or_insert(IRect::new(plate.left, y as i32, plate.right, y as i32));
+        }
+        prev_row = current_row;
+    }
+
+    rects
+}
+
+/// Only moves entities within their plane, so the depth of their
+/// [`DrawLayer`](super::draw_order::DrawLayer) stays as it is.
 fn translate_grid_coords_entities(
     mut grid_coords_entities: Query<(&mut Transform, &GridCoords), Changed<GridCoords>>,
 ) {
@@ -16,3 +106,87 @@ fn translate_grid_coords_entities(
                 .extend(transform.translation.z);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::test_app;
+
+    /// The merged rectangles of `cells`, from the bottom left to the top right.
+    fn merged(cells: &[(i32, i32)], width: i32, height: i32) -> Vec<IRect> {
+        let cells = cells.iter().map(|&(x, y)| GridCoords::new(x, y)).collect();
+        let mut rects = merge_int_grid_rects(&cells, width, height);
+        rects.sort_by_key(|rect| (rect.min.y, rect.min.x));
+        rects
+    }
+
+    #[test]
+    fn plates_touching_the_right_edge_are_finished() {
+        assert_eq!(
+            merged(&[(2, 0), (3, 0), (3, 1)], 4, 2),
+            vec![IRect::new(2, 0, 3, 0), IRect::new(3, 1, 3, 1)]
+        );
+    }
+
+    #[test]
+    fn rects_touching_the_top_edge_are_finished() {
+        assert_eq!(
+            merged(&[(0, 1), (1, 1), (0, 2), (1, 2)], 2, 3),
+            vec![IRect::new(0, 1, 1, 2)]
+        );
+    }
+
+    #[test]
+    fn a_full_grid_is_a_single_rect() {
+        let cells: Vec<_> = (0..3).flat_map(|x| (0..2).map(move |y| (x, y))).collect();
+        assert_eq!(merged(&cells, 3, 2), vec![IRect::new(0, 0, 2, 1)]);
+    }
+
+    #[test]
+    fn plates_with_a_gap_between_them_stay_apart() {
+        assert_eq!(
+            merged(&[(1, 0), (2, 0), (3, 0), (1, 2), (2, 2), (3, 2)], 5, 3),
+            vec![IRect::new(1, 0, 3, 0), IRect::new(1, 2, 3, 2)]
+        );
+    }
+
+    #[test]
+    fn plates_of_different_widths_stay_apart() {
+        assert_eq!(
+            merged(&[(0, 0), (1, 0), (0, 1)], 2, 2),
+            vec![IRect::new(0, 0, 1, 0), IRect::new(0, 1, 0, 1)]
+        );
+    }
+
+    #[test]
+    fn disjoint_regions_get_their_own_rects() {
+        assert_eq!(
+            merged(&[(0, 0), (1, 0), (0, 1), (1, 1), (4, 2), (4, 3)], 5, 4),
+            vec![IRect::new(0, 0, 1, 1), IRect::new(4, 2, 4, 3)]
+        );
+    }
+
+    #[test]
+    fn no_cells_no_rects() {
+        assert_eq!(merged(&[], 4, 4), Vec::new());
+    }
+
+    #[test]
+    fn entities_follow_their_grid_coords() {
+        let mut app = test_app();
+        app.add_plugins(plugin);
+        let entity = app
+            .world_mut()
+            .spawn((GridCoords::new(2, 3), Transform::from_xyz(0.0, 0.0, 5.0)))
+            .id();
+
+        app.update();
+        let translation = app.world().get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(40.0, 56.0, 5.0));
+
+        app.world_mut().get_mut::<GridCoords>(entity).unwrap().x = 0;
+        app.update();
+        let translation = app.world().get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(8.0, 56.0, 5.0));
+    }
+}
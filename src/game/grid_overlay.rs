@@ -0,0 +1,135 @@
+//! An optional overlay that draws the current level's tile grid and shows which cell the
+//! mouse cursor is over. Useful when placing entities in LDtk.
+
+use bevy::{
+    color::palettes::css, input::common_conditions::input_just_pressed, prelude::*,
+    window::PrimaryWindow,
+};
+use bevy_ecs_ldtk::{
+    LevelIid,
+    assets::{LdtkProject, LdtkProjectHandle},
+    prelude::RawLevelAccessor,
+};
+
+use crate::{
+    AppSystems,
+    game::grid_coords::{GRID_SIZE, snap_to_grid},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<GridOverlay>();
+    app.add_systems(Startup, spawn_grid_readout);
+    app.add_systems(
+        Update,
+        (
+            toggle_grid_overlay.run_if(input_just_pressed(TOGGLE_GRID_OVERLAY_KEY)),
+            apply_grid_readout_visibility.run_if(resource_changed::<GridOverlay>),
+            (draw_grid_overlay, update_grid_readout).run_if(grid_overlay_enabled),
+        )
+            .chain()
+            .in_set(AppSystems::Update),
+    );
+}
+
+const TOGGLE_GRID_OVERLAY_KEY: KeyCode = KeyCode::F6;
+
+/// Whether the grid is drawn, and in what color.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct GridOverlay {
+    pub enabled: bool,
+    pub color: Color,
+}
+
+impl Default for GridOverlay {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            color: css::GRAY.with_alpha(0.3).into(),
+        }
+    }
+}
+
+pub fn grid_overlay_enabled(grid_overlay: Res<GridOverlay>) -> bool {
+    grid_overlay.enabled
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct GridReadoutText;
+
+fn spawn_grid_readout(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Grid Readout"),
+        GridReadoutText,
+        Text::default(),
+        TextFont::from_font_size(14.0),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: px(5),
+            left: px(5),
+            ..default()
+        },
+        GlobalZIndex(10),
+        Visibility::Hidden,
+        Pickable::IGNORE,
+    ));
+}
+
+fn toggle_grid_overlay(mut grid_overlay: ResMut<GridOverlay>) {
+    grid_overlay.enabled = !grid_overlay.enabled;
+}
+
+fn apply_grid_readout_visibility(
+    grid_overlay: Res<GridOverlay>,
+    mut visibility: Single<&mut Visibility, With<GridReadoutText>>,
+) {
+    **visibility = if grid_overlay.enabled {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+}
+
+fn draw_grid_overlay(
+    mut gizmos: Gizmos,
+    grid_overlay: Res<GridOverlay>,
+    levels: Query<(&LevelIid, &GlobalTransform)>,
+    ldtk_project: Single<&LdtkProjectHandle>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+) {
+    let Some(ldtk_project) = ldtk_project_assets.get(*ldtk_project) else {
+        return;
+    };
+    for (level_iid, level_transform) in &levels {
+        let Some(level) = ldtk_project.get_raw_level_by_iid(&level_iid.to_string()) else {
+            continue;
+        };
+        let size = Vec2::new(level.px_wid as f32, level.px_hei as f32);
+        gizmos.grid_2d(
+            Isometry2d::from_translation(level_transform.translation().truncate() + size / 2.0),
+            (size / GRID_SIZE as f32).as_uvec2(),
+            Vec2::splat(GRID_SIZE as f32),
+            grid_overlay.color,
+        );
+    }
+}
+
+fn update_grid_readout(
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<Camera2d>>,
+    level: Option<Single<&GlobalTransform, With<LevelIid>>>,
+    mut text: Single<&mut Text, With<GridReadoutText>>,
+) {
+    let (camera, camera_transform) = *camera;
+    let position = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok());
+    text.0 = match (position, level) {
+        (Some(position), Some(level)) => {
+            let cell = snap_to_grid(position, level.translation().truncate());
+            format!("cell: {} {}", cell.x, cell.y)
+        }
+        _ => "cell: none".to_string(),
+    };
+}
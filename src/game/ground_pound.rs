@@ -0,0 +1,113 @@
+//! A slam straight down, started by pressing down in the air.
+//!
+//! Landing from a ground pound launches the player off bouncers harder, sets off the mines
+//! around them without hurting them, and breaks destructible tiles. The move is the
+//! [`ComboId::GroundPound`] combo, so its input can be changed along with the other combos.
+
+use avian2d::prelude::LinearVelocity;
+use bevy::prelude::*;
+use bevy_tnua::{TnuaUserControlsSystems, prelude::TnuaController};
+
+use crate::{
+    audio::{SoundKind, sound_effect},
+    game::{
+        combo::{ComboId, ComboTriggered},
+        destructible::{Destructible, destroy_in_radius},
+        gravity_flip::GravityFlip,
+        grid_coords::GRID_SIZE,
+        health::HealthAssets,
+        mine::{Detonate, Mine, Spent},
+        player::{Dead, Player, PlayerLanded, Stunned},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<GroundPoundConfig>();
+    app.add_observer(start_ground_pound);
+    app.add_observer(land_ground_pound);
+    app.add_systems(FixedUpdate, slam_down.after(TnuaUserControlsSystems));
+}
+
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct GroundPoundConfig {
+    /// How fast the player slams down. Faster than falling gets otherwise.
+    pub slam_speed: f32,
+    /// How far around the landing mines go off and tiles break, in tiles.
+    pub impact_radius: f32,
+    /// How much higher bouncers launch a player that ground pounds onto them.
+    pub bounce_multiplier: f32,
+}
+
+impl Default for GroundPoundConfig {
+    fn default() -> Self {
+        Self {
+            slam_speed: 400.0,
+            impact_radius: 1.5,
+            bounce_multiplier: 1.5,
+        }
+    }
+}
+
+/// Marks a player in the middle of a ground pound. They ignore the controls until they land.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct GroundPounding;
+
+fn start_ground_pound(
+    event: On<ComboTriggered>,
+    mut commands: Commands,
+    players: Query<&TnuaController, (With<Player>, Without<Dead>, Without<Stunned>)>,
+) {
+    if event.combo != ComboId::GroundPound {
+        return;
+    }
+    let Ok(controller) = players.get(event.entity) else {
+        return;
+    };
+    if controller.is_airborne().unwrap_or(false) {
+        commands.entity(event.entity).insert(GroundPounding);
+    }
+}
+
+/// Tnua only steers horizontally, so the slam sets the vertical velocity directly, like the
+/// vertical part of a force zone.
+fn slam_down(
+    config: Res<GroundPoundConfig>,
+    players: Query<(&mut LinearVelocity, &GravityFlip), (With<GroundPounding>, Without<Dead>)>,
+) {
+    for (mut velocity, gravity_flip) in players {
+        velocity.x = 0.0;
+        velocity.y = -config.slam_speed * gravity_flip.sign();
+    }
+}
+
+/// Bouncers check for [`GroundPounding`] themselves, so it's only removed once they're done.
+fn land_ground_pound(
+    event: On<PlayerLanded>,
+    mut commands: Commands,
+    config: Res<GroundPoundConfig>,
+    health_assets: If<Res<HealthAssets>>,
+    players: Query<&GlobalTransform, With<GroundPounding>>,
+    mines: Query<(Entity, &GlobalTransform), (With<Mine>, Without<Spent>)>,
+    destructibles: Query<(Entity, &GlobalTransform), With<Destructible>>,
+) {
+    let Ok(transform) = players.get(event.entity) else {
+        return;
+    };
+    commands.entity(event.entity).remove::<GroundPounding>();
+
+    commands.spawn((
+        Name::new("Ground Pound Sound"),
+        sound_effect(health_assets.heavy_landing.clone()),
+        SoundKind::Impact,
+    ));
+    let center = transform.translation().truncate();
+    let reach = config.impact_radius * GRID_SIZE as f32;
+    for (mine, mine_transform) in &mines {
+        if mine_transform.translation().truncate().distance(center) <= reach {
+            commands.trigger(Detonate { entity: mine });
+        }
+    }
+    destroy_in_radius(&mut commands, center, config.impact_radius, &destructibles);
+}
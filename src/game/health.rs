@@ -0,0 +1,155 @@
+//! Optional hit points, so some damage only hurts the player instead of killing them.
+//!
+//! Health is off by default. Without it, hazards kill instantly and there's no fall damage.
+
+use bevy::prelude::*;
+
+use crate::{
+    asset_tracking::LoadResource,
+    audio::{SoundKind, sound_effect},
+    game::{
+        bouncer::Bouncer,
+        player::{Dead, DeathCause, Hurt, Invulnerable, KillPlayer, Player, PlayerLanded},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.load_resource::<HealthAssets>();
+    app.init_resource::<HealthSettings>();
+    app.init_resource::<FallDamage>();
+
+    app.add_observer(give_player_health);
+    app.add_observer(on_damage);
+    app.add_observer(apply_fall_damage);
+}
+
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct HealthSettings {
+    /// Whether players have [`Health`] at all.
+    pub enabled: bool,
+    /// The health players spawn with.
+    pub max: u32,
+}
+
+impl Default for HealthSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max: 3,
+        }
+    }
+}
+
+/// The player's remaining hit points. They die once it reaches 0.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct Health(pub u32);
+
+/// Takes `amount` of [`Health`] from a player.
+/// Players without health, or while they are [`Invulnerable`], ignore this.
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct Damage {
+    pub entity: Entity,
+    pub amount: u32,
+    /// What the player died of, if this was the last of their health.
+    pub cause: DeathCause,
+}
+
+fn give_player_health(
+    event: On<Add, Player>,
+    mut commands: Commands,
+    settings: Res<HealthSettings>,
+) {
+    if settings.enabled {
+        commands.entity(event.entity).insert(Health(settings.max));
+    }
+}
+
+fn on_damage(
+    event: On<Damage>,
+    mut commands: Commands,
+    mut players: Query<&mut Health, (With<Player>, Without<Dead>, Without<Invulnerable>)>,
+) {
+    let Damage {
+        entity,
+        amount,
+        cause,
+    } = *event.event();
+    let Ok(mut health) = players.get_mut(entity) else {
+        return;
+    };
+
+    health.0 = health.0.saturating_sub(amount);
+    if health.0 > 0 {
+        commands.trigger(Hurt { entity });
+        return;
+    }
+
+    commands.trigger(KillPlayer { entity, cause });
+}
+
+/// How falling from high up hurts.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct FallDamage {
+    /// How far the player can fall without getting hurt, in world units.
+    pub safe_distance: f32,
+    /// How much farther the player has to fall for each point of damage.
+    pub distance_per_damage: f32,
+}
+
+impl Default for FallDamage {
+    fn default() -> Self {
+        Self {
+            // Five tiles.
+            safe_distance: 80.0,
+            distance_per_damage: 32.0,
+        }
+    }
+}
+
+/// Damages players that land too far below where their fall started.
+/// Landing on a bouncer never hurts.
+fn apply_fall_damage(
+    event: On<PlayerLanded>,
+    mut commands: Commands,
+    settings: Res<HealthSettings>,
+    fall_damage: Res<FallDamage>,
+    health_assets: If<Res<HealthAssets>>,
+    bouncers: Query<(), With<Bouncer>>,
+) {
+    let fall = event.fall_height - fall_damage.safe_distance;
+    if !settings.enabled || fall <= 0.0 || bouncers.contains(event.ground_entity) {
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Heavy Landing Sound"),
+        sound_effect(health_assets.heavy_landing.clone()),
+        SoundKind::Impact,
+    ));
+    let amount = 1 + (fall / fall_damage.distance_per_damage) as u32;
+    commands.trigger(Damage {
+        entity: event.entity,
+        amount,
+        cause: DeathCause::FallDamage,
+    });
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct HealthAssets {
+    #[dependency]
+    pub heavy_landing: Handle<AudioSource>,
+}
+
+impl FromWorld for HealthAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            // There is no landing sound of its own yet, so it borrows the explosion.
+            heavy_landing: assets.load("audio/sound_effects/boom.ogg"),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Black bars at the top and bottom of the screen that signal a cinematic moment.
+//!
+//! Set [`Letterbox`] to slide them in, usually along with [`GameplayFrozen`](crate::GameplayFrozen),
+//! and unset it to slide them back out.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{AppSystems, game::accessibility::ReducedMotion, screens::Screen};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Letterbox>();
+    app.init_resource::<LetterboxConfig>();
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_letterbox);
+    app.add_systems(OnExit(Screen::Gameplay), hide_letterbox);
+    app.add_systems(
+        Update,
+        animate_letterbox
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Whether the letterbox bars should be shown.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct Letterbox(pub bool);
+
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct LetterboxConfig {
+    /// The height of each bar, in percent of the screen's height.
+    pub height: f32,
+    /// How long the bars take to slide in or out.
+    pub duration: Duration,
+}
+
+impl Default for LetterboxConfig {
+    fn default() -> Self {
+        Self {
+            height: 12.0,
+            duration: Duration::from_millis(400),
+        }
+    }
+}
+
+/// How far a bar has slid in, from 0 to 1.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+struct LetterboxBar(f32);
+
+fn letterbox_bar(name: &'static str, node: Node) -> impl Bundle {
+    (
+        Name::new(name),
+        LetterboxBar::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            height: px(0),
+            ..node
+        },
+        BackgroundColor(Color::BLACK),
+        // Above the HUD, but below the menus.
+        GlobalZIndex(1),
+        DespawnOnExit(Screen::Gameplay),
+        Pickable::IGNORE,
+    )
+}
+
+fn spawn_letterbox(mut commands: Commands) {
+    commands.spawn(letterbox_bar(
+        "Letterbox Top",
+        Node {
+            top: px(0),
+            ..default()
+        },
+    ));
+    commands.spawn(letterbox_bar(
+        "Letterbox Bottom",
+        Node {
+            bottom: px(0),
+            ..default()
+        },
+    ));
+}
+
+fn hide_letterbox(mut letterbox: ResMut<Letterbox>) {
+    letterbox.0 = false;
+}
+
+/// Uses the real time, so the bars still move while gameplay is frozen or paused.
+fn animate_letterbox(
+    time: Res<Time<Real>>,
+    letterbox: Res<Letterbox>,
+    config: Res<LetterboxConfig>,
+    reduced_motion: Res<ReducedMotion>,
+    bars: Query<(&mut LetterboxBar, &mut Node)>,
+) {
+    let target = if letterbox.0 { 1.0 } else { 0.0 };
+    for (mut bar, mut node) in bars {
+        if bar.0 == target {
+            continue;
+        }
+        bar.0 = if reduced_motion.0 || config.duration.is_zero() {
+            target
+        } else {
+            let step = time.delta_secs() / config.duration.as_secs_f32();
+            if target > bar.0 {
+                (bar.0 + step).min(target)
+            } else {
+                (bar.0 - step).max(target)
+            }
+        };
+        let t = EaseFunction::SmoothStep.sample_clamped(bar.0);
+        node.height = percent(config.height * t);
+    }
+}
@@ -1,19 +1,44 @@
 //! Spawn the main level.
 
+use std::fmt;
+
 use bevy::prelude::*;
-use bevy_ecs_ldtk::{LdtkPlugin, LdtkWorldBundle, LevelSelection, app::LdtkEntityAppExt};
+use bevy_ecs_ldtk::{
+    LdtkPlugin, LdtkWorldBundle, LevelSelection,
+    app::LdtkEntityAppExt,
+    assets::{LdtkProject, LdtkProjectHandle},
+    ldtk::LayerInstance,
+    prelude::{LdtkSettings, LevelBackground, RawLevelAccessor},
+};
 
 use crate::{
-    asset_tracking::LoadResource, audio::music, game::player::PlayerSpawnBundle, screens::Screen,
+    AppSystems,
+    asset_tracking::LoadResource,
+    game::player::PlayerSpawnBundle,
+    screens::{Screen, StartupConfig},
 };
 
 mod walls;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins(LdtkPlugin);
+    // The backgrounds are drawn by `background`, which can also scroll them.
+    app.insert_resource(LdtkSettings {
+        level_background: LevelBackground::Nonexistent,
+        ..default()
+    });
     app.insert_resource(LevelSelection::index(0));
+    app.init_resource::<LevelEntry>();
+    app.add_systems(OnEnter(Screen::Gameplay), reset_level_entry);
     app.register_ldtk_entity::<PlayerSpawnBundle>("PlayerSpawn");
     app.load_resource::<LevelAssets>();
+    app.add_systems(
+        Update,
+        detect_level_change
+            .run_if(resource_changed::<LevelSelection>)
+            .in_set(AppSystems::Update),
+    );
+    app.add_systems(Update, update_level_count);
 
     app.add_plugins(walls::plugin);
 }
@@ -21,8 +46,9 @@ pub(super) fn plugin(app: &mut App) {
 #[derive(Resource, Asset, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct LevelAssets {
+    /// Played in levels that don't pick their own music.
     #[dependency]
-    music: Handle<AudioSource>,
+    pub music: Handle<AudioSource>,
 }
 
 impl FromWorld for LevelAssets {
@@ -34,27 +60,233 @@ impl FromWorld for LevelAssets {
     }
 }
 
+/// The number of levels in the loaded LDtk project.
+///
+/// Levels are counted in the order [`advance_level`] goes through them, so the last level is
+/// always number `LevelCount - 1`, however the levels are named or laid out.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct LevelCount(pub usize);
+
+fn update_level_count(
+    mut commands: Commands,
+    mut asset_events: MessageReader<AssetEvent<LdtkProject>>,
+    ldtk_projects: Res<Assets<LdtkProject>>,
+) {
+    for event in asset_events.read() {
+        if let AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } = event
+            && let Some(ldtk_project) = ldtk_projects.get(*id)
+        {
+            commands.insert_resource(LevelCount(ldtk_project.iter_raw_levels().count()));
+        }
+    }
+}
+
+/// Triggered once the [`LevelSelection`] changed, no matter what changed it.
+///
+/// Observe this instead of watching the selection yourself.
+#[derive(Event, Debug, Clone)]
+pub struct LevelChanged {
+    pub from: LevelSelection,
+    pub to: LevelSelection,
+}
+
+fn detect_level_change(
+    mut commands: Commands,
+    level_selection: Res<LevelSelection>,
+    mut previous: Local<Option<LevelSelection>>,
+) {
+    if previous.as_ref() == Some(&*level_selection) {
+        return;
+    }
+    if let Some(from) = previous.replace(level_selection.clone()) {
+        commands.trigger(LevelChanged {
+            from,
+            to: level_selection.clone(),
+        });
+    }
+}
+
+/// Where the players come into the next level. Set it before changing the [`LevelSelection`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub enum LevelEntry {
+    /// At the level's `PlayerSpawn`.
+    #[default]
+    Start,
+    /// Next to the door that leads on from the level, after coming back through a back door.
+    /// Whoever places the players there sets this back to [`LevelEntry::Start`], so dying
+    /// restarts the level as usual.
+    Exit,
+}
+
+fn reset_level_entry(mut level_entry: ResMut<LevelEntry>) {
+    *level_entry = LevelEntry::Start;
+}
+
+/// Triggered when the players make it through a door or goal into the next level.
+///
+/// The [`LevelChanged`] that follows doesn't say how the level was left.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct LevelCompleted;
+
+/// Triggered when the current level is attempted again, e.g. after dying.
+///
+/// Level entities that change during an attempt observe this to go back to how they started.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ResetLevel;
+
+/// The result of [`advance_level`] and [`retreat_level`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelAdvance {
+    /// The selection now points at the neighbouring level.
+    Advanced,
+    /// There is no level in that direction.
+    Finished,
+    /// The current level couldn't be found in the project, so nothing changed.
+    NotFound,
+}
+
+/// Moves the [`LevelSelection`] to the level following the current one in the LDtk project.
+///
+/// The kind of selection is kept, so an [`LevelSelection::Iid`] stays an iid.
+/// Other kinds are turned into [`LevelSelection::Indices`].
+pub fn advance_level(
+    level_selection: &mut LevelSelection,
+    ldtk_project: &LdtkProject,
+) -> LevelAdvance {
+    step_level(level_selection, ldtk_project, true)
+}
+
+/// Like [`advance_level`], but moves to the level before the current one.
+pub fn retreat_level(
+    level_selection: &mut LevelSelection,
+    ldtk_project: &LdtkProject,
+) -> LevelAdvance {
+    step_level(level_selection, ldtk_project, false)
+}
+
+fn step_level(
+    level_selection: &mut LevelSelection,
+    ldtk_project: &LdtkProject,
+    forward: bool,
+) -> LevelAdvance {
+    let levels: Vec<_> = ldtk_project.iter_raw_levels_with_indices().collect();
+    let Some(current) = levels
+        .iter()
+        .position(|(indices, level)| level_selection.is_match(indices, level))
+    else {
+        warn!("Current level {level_selection:?} doesn't exist in the LDtk project");
+        return LevelAdvance::NotFound;
+    };
+
+    let next = if forward {
+        levels.get(current + 1)
+    } else {
+        current.checked_sub(1).and_then(|index| levels.get(index))
+    };
+    let Some((indices, level)) = next else {
+        return LevelAdvance::Finished;
+    };
+
+    *level_selection = match level_selection {
+        LevelSelection::Iid(_) => LevelSelection::iid(level.iid.clone()),
+        _ => LevelSelection::Indices(*indices),
+    };
+    LevelAdvance::Advanced
+}
+
+/// The position of the selected level in the LDtk project, counting from 0, and its data.
+pub fn selected_level<'a>(
+    level_selection: &LevelSelection,
+    ldtk_project: &'a LdtkProject,
+) -> Option<(usize, &'a bevy_ecs_ldtk::ldtk::Level)> {
+    ldtk_project
+        .iter_raw_levels_with_indices()
+        .enumerate()
+        .find(|(_, (indices, level))| level_selection.is_match(indices, level))
+        .map(|(position, (_, level))| (position, level))
+}
+
+/// Something the LDtk project is missing that a level needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LevelError {
+    /// The project isn't loaded (yet), or there's more than one.
+    ProjectNotLoaded,
+    /// The project has no level with this iid.
+    LevelNotFound(String),
+    /// The level with this iid has no layers.
+    NoLayers(String),
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelError::ProjectNotLoaded => write!(f, "the LDtk project isn't loaded"),
+            LevelError::LevelNotFound(iid) => {
+                write!(f, "the LDtk project has no level with the iid {iid}")
+            }
+            LevelError::NoLayers(iid) => write!(f, "the level with the iid {iid} has no layers"),
+        }
+    }
+}
+
+impl std::error::Error for LevelError {}
+
+/// The LDtk project the levels are spawned from, once it's loaded.
+pub fn loaded_project<'a>(
+    ldtk_projects: &Query<&LdtkProjectHandle>,
+    ldtk_project_assets: &'a Assets<LdtkProject>,
+) -> Result<&'a LdtkProject, LevelError> {
+    ldtk_projects
+        .single()
+        .ok()
+        .and_then(|handle| ldtk_project_assets.get(handle))
+        .ok_or(LevelError::ProjectNotLoaded)
+}
+
+/// The first layer of a level, which all of the level's layers share their grid with.
+pub fn first_layer<'a>(
+    ldtk_project: &'a LdtkProject,
+    level_iid: &str,
+) -> Result<&'a LayerInstance, LevelError> {
+    ldtk_project
+        .as_standalone()
+        .get_loaded_level_by_iid(&level_iid.to_string())
+        .ok_or_else(|| LevelError::LevelNotFound(level_iid.to_string()))?
+        .layer_instances()
+        .first()
+        .ok_or_else(|| LevelError::NoLayers(level_iid.to_string()))
+}
+
+/// Moves on to the next level, or to the victory screen after the last one.
+pub fn go_to_next_level(
+    level_selection: &mut LevelSelection,
+    ldtk_project: &LdtkProject,
+    next_screen: &mut NextState<Screen>,
+) -> LevelAdvance {
+    let advance = advance_level(level_selection, ldtk_project);
+    if advance == LevelAdvance::Finished {
+        next_screen.set(Screen::Victory);
+    }
+    advance
+}
+
 /// A system that spawns the main level.
 pub fn spawn_level(
     mut commands: Commands,
-    level_assets: Res<LevelAssets>,
     asset_server: Res<AssetServer>,
+    startup_config: Res<StartupConfig>,
 ) {
-    commands.insert_resource(LevelSelection::index(0));
+    commands.insert_resource(LevelSelection::index(startup_config.initial_level));
     commands.spawn((
         Name::new("Level"),
         Transform::default(),
         Visibility::default(),
         DespawnOnExit(Screen::Gameplay),
-        children![
-            LdtkWorldBundle {
-                ldtk_handle: asset_server.load("levels.ldtk").into(),
-                ..Default::default()
-            },
-            (
-                Name::new("Gameplay Music"),
-                music(level_assets.music.clone())
-            )
-        ],
+        children![LdtkWorldBundle {
+            ldtk_handle: asset_server.load("levels.ldtk").into(),
+            ..Default::default()
+        }],
     ));
 }
@@ -5,12 +5,25 @@ use bevy_ecs_ldtk::prelude::*;
 
 use avian2d::prelude::*;
 
+use crate::game::{
+    colliders::GameLayer,
+    draw_order::DrawLayer,
+    grid_coords::merge_int_grid_rects,
+    level::{first_layer, loaded_project},
+    physics::{Surface, SurfaceConfig},
+};
+
+/// The tile set only has tiles for regular walls, so ice is tinted to set it apart.
+const ICE_TINT: Color = Color::srgba(0.6, 0.85, 1.0, 0.45);
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component)]
 pub struct Wall;
 
 #[derive(Clone, Debug, Default, Bundle, LdtkIntCell)]
 pub struct WallBundle {
     wall: Wall,
+    #[from_int_grid_cell]
+    surface: Surface,
 }
 
 /// Spawns avian collisions for the walls of a level
@@ -22,37 +35,22 @@ pub struct WallBundle {
 /// Instead, by flagging the wall tiles and spawning the collisions later,
 /// we can minimize the amount of colliding entities.
 ///
-/// The algorithm used here is a nice compromise between simplicity, speed,
-/// and a small number of rectangle colliders.
 /// In basic terms, it will:
 /// 1. consider where the walls are
-/// 2. combine wall tiles into flat "plates" in each individual row
-/// 3. combine the plates into rectangles across multiple rows wherever possible
-/// 4. spawn colliders for each rectangle
+/// 2. combine the wall tiles into rectangles with [`merge_int_grid_rects`]
+/// 3. spawn colliders for each rectangle
+///
+/// Walls of different [`Surface`]s are never combined, so each collider gets a single friction.
+/// Ice colliders are also tinted, so the ice can be told apart from the walls around it.
 pub fn spawn_wall_collision(
     mut commands: Commands,
-    wall_query: Query<(&GridCoords, &ChildOf), Added<Wall>>,
+    surface_config: Res<SurfaceConfig>,
+    wall_query: Query<(&GridCoords, &Surface, &ChildOf), Added<Wall>>,
     parent_query: Query<&ChildOf, Without<Wall>>,
     level_query: Query<(Entity, &LevelIid)>,
     ldtk_projects: Query<&LdtkProjectHandle>,
     ldtk_project_assets: Res<Assets<LdtkProject>>,
 ) {
-    /// Represents a wide wall that is 1 tile tall
-    /// Used to spawn wall collisions
-    #[derive(Clone, Eq, PartialEq, Debug, Default, Hash)]
-    struct Plate {
-        left: i32,
-        right: i32,
-    }
-
-    /// A simple rectangle type representing a wall of any size
-    struct Rect {
-        left: i32,
-        right: i32,
-        top: i32,
-        bottom: i32,
-    }
-
     // Consider where the walls are
     // storing them as GridCoords in a HashSet for quick, easy lookup
     //
@@ -60,122 +58,89 @@ pub fn spawn_wall_collision(
     // This has two consequences in the resulting collision entities:
     // 1. it forces the walls to be split along level boundaries
     // 2. it lets us easily add the collision entities as children of the appropriate level entity
-    let mut level_to_wall_locations: HashMap<Entity, HashSet<GridCoords>> = HashMap::new();
-
-    wall_query.iter().for_each(|(&grid_coords, parent)| {
-        // An intgrid tile's direct parent will be a layer entity, not the level entity
-        // To get the level entity, you need the tile's grandparent.
-        // This is where parent_query comes in.
-        if let Ok(grandparent) = parent_query.get(parent.parent()) {
-            level_to_wall_locations
-                .entry(grandparent.parent())
-                .or_default()
-                .insert(grid_coords);
-        }
-    });
+    // Within a level, the walls are split up by their surface the same way.
+    let mut level_to_wall_locations: HashMap<Entity, HashMap<Surface, HashSet<GridCoords>>> =
+        HashMap::new();
+
+    wall_query
+        .iter()
+        .for_each(|(&grid_coords, &surface, parent)| {
+            // An intgrid tile's direct parent will be a layer entity, not the level entity
+            // To get the level entity, you need the tile's grandparent.
+            // This is where parent_query comes in.
+            if let Ok(grandparent) = parent_query.get(parent.parent()) {
+                level_to_wall_locations
+                    .entry(grandparent.parent())
+                    .or_default()
+                    .entry(surface)
+                    .or_default()
+                    .insert(grid_coords);
+            }
+        });
 
     if !wall_query.is_empty() {
-        level_query.iter().for_each(|(level_entity, level_iid)| {
-            if let Some(level_walls) = level_to_wall_locations.get(&level_entity) {
-                let ldtk_project = ldtk_project_assets
-                    .get(ldtk_projects.single().unwrap())
-                    .expect("Project should be loaded if level has spawned");
-
-                let level = ldtk_project
-                    .as_standalone()
-                    .get_loaded_level_by_iid(&level_iid.to_string())
-                    .expect("Spawned level should exist in LDtk project");
+        let ldtk_project = match loaded_project(&ldtk_projects, &ldtk_project_assets) {
+            Ok(ldtk_project) => ldtk_project,
+            Err(error) => {
+                error!("Can't spawn wall collisions: {error}");
+                return;
+            }
+        };
 
-                let LayerInstance {
+        level_query.iter().for_each(|(level_entity, level_iid)| {
+            if let Some(level_surfaces) = level_to_wall_locations.get(&level_entity) {
+                let &LayerInstance {
                     c_wid: width,
                     c_hei: height,
                     grid_size,
                     ..
-                } = level.layer_instances()[0];
-
-                // combine wall tiles into flat "plates" in each individual row
-                let mut plate_stack: Vec<Vec<Plate>> = Vec::new();
-
-                for y in 0..height {
-                    let mut row_plates: Vec<Plate> = Vec::new();
-                    let mut plate_start = None;
-
-                    // + 1 to the width so the algorithm "terminates" plates that touch the right edge
-                    for x in 0..=width {
-                        match (plate_start, level_walls.contains(&GridCoords { x, y })) {
-                            (Some(s), false) => {
-                                row_plates.push(Plate {
-                                    left: s,
-                                    right: x - 1,
-                                });
-                                plate_start = None;
-                            }
-                            (None, true) => plate_start = Some(x),
-                            _ => (),
-                        }
+                } = match first_layer(ldtk_project, &level_iid.to_string()) {
+                    Ok(layer) => layer,
+                    Err(error) => {
+                        error!("Can't spawn wall collisions: {error}");
+                        return;
                     }
-
-                    plate_stack.push(row_plates);
-                }
-
-                // combine "plates" into rectangles across multiple rows
-                let mut rect_builder: HashMap<Plate, Rect> = HashMap::new();
-                let mut prev_row: Vec<Plate> = Vec::new();
-                let mut wall_rects: Vec<Rect> = Vec::new();
-
-                // an extra empty row so the algorithm "finishes" the rects that touch the top edge
-                plate_stack.push(Vec::new());
-
-                for (y, current_row) in plate_stack.into_iter().enumerate() {
-                    for prev_plate in &prev_row {
-                        if !current_row.contains(prev_plate) {
-                            // remove the finished rect so that the same plate in the future starts a new rect
-                            if let Some(rect) = rect_builder.remove(prev_plate) {
-                                wall_rects.push(rect);
+                };
+
+                for (&surface, level_walls) in level_surfaces {
+                    let wall_rects = merge_int_grid_rects(level_walls, width, height);
+
+                    commands.entity(level_entity).with_children(|level| {
+                        // Spawn colliders for every rectangle..
+                        // Making the collider a child of the level serves two purposes:
+                        // 1. Adjusts the transforms to be relative to the level for free
+                        // 2. the colliders will be despawned automatically when levels unload
+                        for wall_rect in wall_rects {
+                            let width = (wall_rect.width() + 1) as f32 * grid_size as f32;
+                            let height = (wall_rect.height() + 1) as f32 * grid_size as f32;
+                            let mut wall = level.spawn((
+                                Collider::rectangle(width, height),
+                                RigidBody::Static,
+                                GameLayer::Terrain.layers(),
+                                surface_config.friction(surface),
+                                surface,
+                                Transform::from_xyz(
+                                    (wall_rect.min.x + wall_rect.max.x + 1) as f32
+                                        * grid_size as f32
+                                        / 2.,
+                                    (wall_rect.min.y + wall_rect.max.y + 1) as f32
+                                        * grid_size as f32
+                                        / 2.,
+                                    0.,
+                                ),
+                                GlobalTransform::default(),
+                                InheritedVisibility::default(),
+                                Name::new("Wall"),
+                            ));
+                            if surface == Surface::Ice {
+                                wall.insert((
+                                    Sprite::from_color(ICE_TINT, Vec2::new(width, height)),
+                                    DrawLayer::Background,
+                                ));
                             }
                         }
-                    }
-                    for plate in &current_row {
-                        rect_builder
-                            .entry(plate.clone())
-                            .and_modify(|e| e.top += 1)
-                            .or_insert(Rect {
-                                bottom: y as i32,
-                                top: y as i32,
-                                left: plate.left,
-                                right: plate.right,
-                            });
-                    }
-                    prev_row = current_row;
+                    });
                 }
-
-                commands.entity(level_entity).with_children(|level| {
-                    // Spawn colliders for every rectangle..
-                    // Making the collider a child of the level serves two purposes:
-                    // 1. Adjusts the transforms to be relative to the level for free
-                    // 2. the colliders will be despawned automatically when levels unload
-                    for wall_rect in wall_rects {
-                        let width = (wall_rect.right as f32 - wall_rect.left as f32 + 1.)
-                            * grid_size as f32;
-                        let height = (wall_rect.top as f32 - wall_rect.bottom as f32 + 1.)
-                            * grid_size as f32;
-                        level.spawn((
-                            Collider::rectangle(width, height),
-                            RigidBody::Static,
-                            Friction::new(0.0),
-                            Transform::from_xyz(
-                                (wall_rect.left + wall_rect.right + 1) as f32 * grid_size as f32
-                                    / 2.,
-                                (wall_rect.bottom + wall_rect.top + 1) as f32 * grid_size as f32
-                                    / 2.,
-                                0.,
-                            ),
-                            GlobalTransform::default(),
-                            InheritedVisibility::default(),
-                            Name::new("Wall"),
-                        ));
-                    }
-                });
             }
         });
     }
@@ -183,5 +148,7 @@ pub fn spawn_wall_collision(
 
 pub fn plugin(app: &mut App) {
     app.add_systems(Update, spawn_wall_collision)
-        .register_ldtk_int_cell_for_layer::<WallBundle>("WallLayer", 1);
+        .register_ldtk_int_cell_for_layer::<WallBundle>("WallLayer", 1)
+        .register_ldtk_int_cell_for_layer::<WallBundle>("WallLayer", 3)
+        .register_ldtk_int_cell_for_layer::<WallBundle>("WallLayer", 4);
 }
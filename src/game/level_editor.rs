@@ -0,0 +1,244 @@
+//! A rough in-game level editor for sketching layouts on top of the current level.
+//!
+//! F4 toggles the editor along with the grid overlay. Keys 1 to 5 pick what to place: walls,
+//! destructible walls, spikes, mines or doors. Left click places it on the cell under the
+//! cursor, right click removes it again. Only tiles placed in the editor can be removed, the
+//! level itself is left untouched.
+//!
+//! F5 exports the placed tiles to `level_edit.ron`, with each tile's LDtk identifier and grid
+//! coordinates, so they can be copied into the LDtk project.
+
+use bevy::{
+    color::palettes::css, input::common_conditions::input_just_pressed, prelude::*,
+    window::PrimaryWindow,
+};
+use bevy_ecs_ldtk::{GridCoords, LevelIid};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AppSystems,
+    game::{
+        grid_coords::{GRID_SIZE, grid_coords_to_world, snap_to_grid},
+        grid_overlay::GridOverlay,
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LevelEditor>();
+    app.add_systems(
+        Update,
+        (
+            toggle_level_editor.run_if(input_just_pressed(TOGGLE_LEVEL_EDITOR_KEY)),
+            (
+                select_brush,
+                edit_tiles,
+                draw_hovered_cell,
+                export_layout.run_if(input_just_pressed(EXPORT_LAYOUT_KEY)),
+            )
+                .run_if(level_editor_enabled),
+        )
+            .chain()
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+const TOGGLE_LEVEL_EDITOR_KEY: KeyCode = KeyCode::F4;
+const EXPORT_LAYOUT_KEY: KeyCode = KeyCode::F5;
+#[cfg(not(target_family = "wasm"))]
+const EXPORT_PATH: &str = "level_edit.ron";
+
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct LevelEditor {
+    pub enabled: bool,
+    /// What a left click places.
+    pub brush: EditorBrush,
+}
+
+pub fn level_editor_enabled(level_editor: Res<LevelEditor>) -> bool {
+    level_editor.enabled
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+pub enum EditorBrush {
+    #[default]
+    Wall,
+    Destructible,
+    Spike,
+    Mine,
+    Door,
+}
+
+impl EditorBrush {
+    /// The name of the int grid value or entity in the LDtk project.
+    fn ldtk_identifier(self) -> &'static str {
+        match self {
+            Self::Wall => "WallLayer:1",
+            Self::Destructible => "WallLayer:2",
+            Self::Spike => "Spike",
+            Self::Mine => "Mine",
+            Self::Door => "Door",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Self::Wall => css::WHITE,
+            Self::Destructible => css::TAN,
+            Self::Spike => css::RED,
+            Self::Mine => css::ORANGE,
+            Self::Door => css::DODGER_BLUE,
+        }
+        .with_alpha(0.6)
+        .into()
+    }
+}
+
+/// A tile placed with the editor.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+struct EditorTile(EditorBrush);
+
+#[derive(Serialize, Deserialize, Debug)]
+struct EditedLevel {
+    level: String,
+    tiles: Vec<EditedTile>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct EditedTile {
+    kind: EditorBrush,
+    ldtk_identifier: String,
+    x: i32,
+    y: i32,
+}
+
+/// The grid is needed to see where tiles go, so it follows the editor.
+fn toggle_level_editor(
+    mut level_editor: ResMut<LevelEditor>,
+    mut grid_overlay: ResMut<GridOverlay>,
+) {
+    level_editor.enabled = !level_editor.enabled;
+    grid_overlay.enabled = level_editor.enabled;
+}
+
+fn select_brush(keyboard: Res<ButtonInput<KeyCode>>, mut level_editor: ResMut<LevelEditor>) {
+    let brushes = [
+        (KeyCode::Digit1, EditorBrush::Wall),
+        (KeyCode::Digit2, EditorBrush::Destructible),
+        (KeyCode::Digit3, EditorBrush::Spike),
+        (KeyCode::Digit4, EditorBrush::Mine),
+        (KeyCode::Digit5, EditorBrush::Door),
+    ];
+    for (key, brush) in brushes {
+        if keyboard.just_pressed(key) {
+            level_editor.brush = brush;
+        }
+    }
+}
+
+/// The grid cell under the mouse cursor.
+fn hovered_cell(
+    window: &Window,
+    (camera, camera_transform): (&Camera, &GlobalTransform),
+    level_transform: &GlobalTransform,
+) -> Option<GridCoords> {
+    let cursor = window.cursor_position()?;
+    let position = camera.viewport_to_world_2d(camera_transform, cursor).ok()?;
+    Some(snap_to_grid(
+        position,
+        level_transform.translation().truncate(),
+    ))
+}
+
+fn edit_tiles(
+    mut commands: Commands,
+    mouse: Res<ButtonInput<MouseButton>>,
+    level_editor: Res<LevelEditor>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<Camera2d>>,
+    level: Single<(Entity, &GlobalTransform), With<LevelIid>>,
+    tiles: Query<(Entity, &GridCoords), With<EditorTile>>,
+) {
+    let (level, level_transform) = *level;
+    let Some(cell) = hovered_cell(&window, *camera, level_transform) else {
+        return;
+    };
+    let existing = tiles
+        .iter()
+        .find(|(_, grid_coords)| **grid_coords == cell)
+        .map(|(entity, _)| entity);
+
+    if mouse.just_pressed(MouseButton::Right)
+        && let Some(tile) = existing
+    {
+        commands.entity(tile).despawn();
+    } else if mouse.just_pressed(MouseButton::Left) {
+        if let Some(tile) = existing {
+            commands.entity(tile).despawn();
+        }
+        let brush = level_editor.brush;
+        commands.spawn((
+            Name::new("Editor Tile"),
+            EditorTile(brush),
+            cell,
+            Sprite::from_color(brush.color(), Vec2::splat(GRID_SIZE as f32)),
+            // Positioned by its grid coordinates.
+            Transform::from_xyz(0.0, 0.0, 20.0),
+            ChildOf(level),
+        ));
+    }
+}
+
+fn draw_hovered_cell(
+    mut gizmos: Gizmos,
+    level_editor: Res<LevelEditor>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<Camera2d>>,
+    level: Single<&GlobalTransform, With<LevelIid>>,
+) {
+    if let Some(cell) = hovered_cell(&window, *camera, &level) {
+        gizmos.rect_2d(
+            grid_coords_to_world(cell, level.translation().truncate()),
+            Vec2::splat(GRID_SIZE as f32),
+            level_editor.brush.color().with_alpha(1.0),
+        );
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn export_layout(level: Single<&LevelIid>, tiles: Query<(&EditorTile, &GridCoords)>) {
+    let mut tiles = tiles
+        .iter()
+        .map(|(EditorTile(kind), grid_coords)| EditedTile {
+            kind: *kind,
+            ldtk_identifier: kind.ldtk_identifier().to_string(),
+            x: grid_coords.x,
+            y: grid_coords.y,
+        })
+        .collect::<Vec<_>>();
+    tiles.sort_by_key(|tile| (tile.y, tile.x));
+    let edited_level = EditedLevel {
+        level: level.to_string(),
+        tiles,
+    };
+
+    let contents = match ron::ser::to_string_pretty(&edited_level, default()) {
+        Ok(contents) => contents,
+        Err(error) => {
+            error!("Failed to serialize the edited level: {error}");
+            return;
+        }
+    };
+    match std::fs::write(EXPORT_PATH, contents) {
+        Ok(()) => info!("Exported the edited level to {EXPORT_PATH}"),
+        Err(error) => error!("Failed to write the edited level to {EXPORT_PATH}: {error}"),
+    }
+}
+
+#[cfg(target_family = "wasm")]
+fn export_layout() {
+    warn!("Exporting the edited level isn't supported on the web");
+}
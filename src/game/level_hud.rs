@@ -0,0 +1,122 @@
+//! Briefly shows the number and name of a level when it starts.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_ecs_ldtk::{
+    LevelSelection,
+    assets::{LdtkProject, LdtkProjectHandle},
+};
+
+use crate::{
+    AppSystems, PausableSystems,
+    game::{
+        accessibility::ReducedMotion,
+        level::{LevelChanged, LevelCount, selected_level},
+    },
+    screens::Screen,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LevelHudTimer>();
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_level_hud);
+    app.add_observer(show_level_hud);
+    app.add_systems(
+        Update,
+        (
+            tick_level_hud
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+            update_level_hud.in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// How long the level name stays on screen, including the fades.
+const LEVEL_HUD_DURATION: Duration = Duration::from_millis(2500);
+const FADE_IN_SECS: f32 = 0.3;
+const FADE_OUT_SECS: f32 = 0.6;
+
+/// Counts down how long the level name is still shown.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+struct LevelHudTimer(Timer);
+
+impl Default for LevelHudTimer {
+    fn default() -> Self {
+        Self(Timer::new(LEVEL_HUD_DURATION, TimerMode::Once))
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct LevelHudText;
+
+fn spawn_level_hud(mut commands: Commands, mut timer: ResMut<LevelHudTimer>) {
+    timer.0.reset();
+    commands.spawn((
+        Name::new("Level HUD"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            top: px(20),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        DespawnOnExit(Screen::Gameplay),
+        Pickable::IGNORE,
+        children![(widget::label(""), LevelHudText)],
+    ));
+}
+
+fn show_level_hud(_: On<LevelChanged>, mut timer: ResMut<LevelHudTimer>) {
+    timer.0.reset();
+}
+
+fn tick_level_hud(time: Res<Time>, mut timer: ResMut<LevelHudTimer>) {
+    timer.0.tick(time.delta());
+}
+
+/// The name is looked up every frame it's shown, as the project may still be loading when the
+/// level starts.
+fn update_level_hud(
+    timer: Res<LevelHudTimer>,
+    reduced_motion: Res<ReducedMotion>,
+    level_selection: Res<LevelSelection>,
+    level_count: Option<Res<LevelCount>>,
+    ldtk_project: Option<Single<&LdtkProjectHandle>>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    text: Single<(&mut Text, &mut TextColor), With<LevelHudText>>,
+) {
+    let (mut text, mut color) = text.into_inner();
+    if timer.0.is_finished() {
+        if color.0.alpha() != 0.0 {
+            color.0.set_alpha(0.0);
+        }
+        return;
+    }
+
+    let level = ldtk_project
+        .and_then(|handle| ldtk_project_assets.get(*handle))
+        .and_then(|project| selected_level(&level_selection, project));
+    if let Some((position, level)) = level {
+        let number = match level_count {
+            Some(level_count) => format!("{}/{}", position + 1, level_count.0),
+            None => (position + 1).to_string(),
+        };
+        text.0 = format!("Level {number}: {}", level.identifier);
+    }
+
+    let alpha = if reduced_motion.0 {
+        1.0
+    } else {
+        let elapsed = timer.0.elapsed_secs();
+        let remaining = timer.0.remaining_secs();
+        (elapsed / FADE_IN_SECS)
+            .min(remaining / FADE_OUT_SECS)
+            .clamp(0.0, 1.0)
+    };
+    color.0.set_alpha(alpha);
+}
@@ -0,0 +1,251 @@
+//! The music and ambient loop of each level, chosen in LDtk.
+//!
+//! Levels can set the string fields `Music` and `Ambience` to an audio file in the assets,
+//! e.g. `audio/music/penis.ogg`. Without `Music` the default track from [`LevelAssets`] plays,
+//! and without `Ambience` there is none. When the next level wants different tracks, they
+//! crossfade.
+
+use std::time::Duration;
+
+use bevy::{audio::Volume, prelude::*};
+use bevy_ecs_ldtk::{
+    LevelSelection,
+    assets::{LdtkProject, LdtkProjectHandle},
+    ldtk::Level,
+    prelude::LdtkFields,
+};
+
+use crate::{
+    AppSystems,
+    audio::Music,
+    game::level::{LevelAssets, LevelChanged, selected_level},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LevelTracks>();
+    app.add_observer(queue_level_tracks);
+    app.add_systems(OnExit(Screen::Gameplay), reset_level_tracks);
+    app.add_systems(
+        Update,
+        (select_level_tracks, replace_failed_tracks, fade_tracks)
+            .chain()
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// How long the tracks of two levels take to crossfade.
+const CROSSFADE_DURATION: Duration = Duration::from_millis(1500);
+
+/// The file types Bevy can play.
+const AUDIO_EXTENSIONS: [&str; 4] = [".ogg", ".wav", ".mp3", ".flac"];
+
+/// The level the current tracks were chosen for, once it's known.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+struct LevelTracks {
+    level: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+enum TrackKind {
+    Music,
+    Ambience,
+}
+
+impl TrackKind {
+    fn field(self) -> &'static str {
+        match self {
+            TrackKind::Music => "Music",
+            TrackKind::Ambience => "Ambience",
+        }
+    }
+}
+
+/// A looping track chosen by a level.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+struct LevelTrack {
+    kind: TrackKind,
+    /// The file from the level's field, or `None` for the default music.
+    path: Option<String>,
+}
+
+/// Fades a [`LevelTrack`] in, or out and then despawns it.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+struct Fade {
+    timer: Timer,
+    out: bool,
+}
+
+impl Fade {
+    fn new(out: bool) -> Self {
+        Self {
+            timer: Timer::new(CROSSFADE_DURATION, TimerMode::Once),
+            out,
+        }
+    }
+}
+
+fn queue_level_tracks(_: On<LevelChanged>, mut tracks: ResMut<LevelTracks>) {
+    tracks.level = None;
+}
+
+fn reset_level_tracks(mut tracks: ResMut<LevelTracks>) {
+    *tracks = LevelTracks::default();
+}
+
+/// The file a level's field points to, if it's set to something that can be played.
+fn track_path(level: &Level, kind: TrackKind) -> Option<String> {
+    let field = kind.field();
+    let path = level
+        .get_maybe_string_field(field)
+        .ok()?
+        .as_ref()
+        .filter(|path| !path.is_empty())?;
+    if !AUDIO_EXTENSIONS
+        .iter()
+        .any(|extension| path.ends_with(extension))
+    {
+        warn!(
+            "Ignoring the {field} \"{path}\" of level {}, it's not an audio file",
+            level.identifier
+        );
+        return None;
+    }
+    Some(path.clone())
+}
+
+fn level_track(
+    kind: TrackKind,
+    path: Option<String>,
+    asset_server: &AssetServer,
+    level_assets: &LevelAssets,
+) -> impl Bundle {
+    let handle = match &path {
+        Some(path) => asset_server.load(path.clone()),
+        None => level_assets.music.clone(),
+    };
+    let name = match kind {
+        TrackKind::Music => "Level Music",
+        TrackKind::Ambience => "Level Ambience",
+    };
+    (
+        Name::new(name),
+        LevelTrack { kind, path },
+        Fade::new(false),
+        AudioPlayer(handle),
+        // Starts paused, so it doesn't blare for a frame before fading in.
+        PlaybackSettings::LOOP.paused(),
+        Music,
+        DespawnOnExit(Screen::Gameplay),
+    )
+}
+
+/// The project may still be loading when the level starts, so the level is looked up until
+/// it's found.
+fn select_level_tracks(
+    mut commands: Commands,
+    mut tracks: ResMut<LevelTracks>,
+    asset_server: Res<AssetServer>,
+    level_assets: Res<LevelAssets>,
+    level_selection: Res<LevelSelection>,
+    ldtk_project: Option<Single<&LdtkProjectHandle>>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    playing: Query<(Entity, &LevelTrack), Without<Fade>>,
+    fading_in: Query<(Entity, &LevelTrack, &Fade)>,
+) {
+    if tracks.level.is_some() {
+        return;
+    }
+    let Some((position, level)) = ldtk_project
+        .and_then(|handle| ldtk_project_assets.get(*handle))
+        .and_then(|project| selected_level(&level_selection, project))
+    else {
+        return;
+    };
+    tracks.level = Some(position);
+
+    for kind in [TrackKind::Music, TrackKind::Ambience] {
+        let path = track_path(level, kind);
+        let current = playing
+            .iter()
+            .chain(
+                fading_in
+                    .iter()
+                    .filter(|(.., fade)| !fade.out)
+                    .map(|(entity, track, _)| (entity, track)),
+            )
+            .find(|(_, track)| track.kind == kind);
+
+        if let Some((entity, track)) = current {
+            if track.path == path {
+                continue;
+            }
+            commands.entity(entity).insert(Fade::new(true));
+        }
+        if path.is_some() || kind == TrackKind::Music {
+            commands.spawn(level_track(kind, path, &asset_server, &level_assets));
+        }
+    }
+}
+
+/// Falls back to the default music when a level's track can't be loaded.
+fn replace_failed_tracks(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    level_assets: Res<LevelAssets>,
+    tracks: Query<(Entity, &LevelTrack, &AudioPlayer)>,
+) {
+    for (entity, track, player) in &tracks {
+        if !asset_server.load_state(&player.0).is_failed() {
+            continue;
+        }
+        let path = track.path.as_deref().unwrap_or("the default music");
+        warn!("Can't play {path}, check the {} field", track.kind.field());
+        commands.entity(entity).despawn();
+        if track.kind == TrackKind::Music && track.path.is_some() {
+            commands.spawn(level_track(
+                TrackKind::Music,
+                None,
+                &asset_server,
+                &level_assets,
+            ));
+        }
+    }
+}
+
+/// Uses the real time, so the tracks still fade while gameplay is frozen or paused.
+fn fade_tracks(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    global_volume: Res<GlobalVolume>,
+    tracks: Query<(Entity, &mut Fade, &PlaybackSettings, Option<&mut AudioSink>)>,
+) {
+    for (entity, mut fade, playback, sink) in tracks {
+        // Still loading, so there's nothing to fade in yet, and nothing to hear when fading out.
+        let Some(mut sink) = sink else {
+            if fade.out {
+                commands.entity(entity).despawn();
+            }
+            continue;
+        };
+        fade.timer.tick(time.delta());
+        let t = fade.timer.fraction();
+        let loudness = if fade.out { 1.0 - t } else { t };
+        sink.set_volume(global_volume.volume * playback.volume * Volume::Linear(loudness));
+        if sink.is_paused() && !fade.out {
+            sink.play();
+        }
+
+        if fade.timer.is_finished() {
+            if fade.out {
+                commands.entity(entity).despawn();
+            } else {
+                commands.entity(entity).remove::<Fade>();
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! A card with the time, coins and deaths of a level, shown after leaving it for the next one.
+//!
+//! Gameplay stays frozen while the card is up, so the next level doesn't start behind it.
+//! Pressing any key or button skips the card.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, GameplayFrozen,
+    game::{
+        coin::RunCoins,
+        level::{LevelChanged, LevelCompleted},
+        player::DeathCount,
+        run_timer::{RunTimer, format_duration},
+    },
+    screens::Screen,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LevelStats>();
+    app.add_observer(show_level_stats);
+    app.add_observer(reset_level_stats);
+    app.add_systems(OnEnter(Screen::Gameplay), start_level_stats);
+    app.add_systems(
+        Update,
+        dismiss_level_stats
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// How long the card stays up unless it's skipped.
+const LEVEL_STATS_DURATION: Duration = Duration::from_secs(3);
+
+/// Where the per-level counters stood when the current level started.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+struct LevelStats {
+    /// The [`RunTimer`] at the start of the level.
+    start: Duration,
+    /// The [`DeathCount`] at the start of the level.
+    deaths: u32,
+}
+
+impl LevelStats {
+    fn new(run_timer: &RunTimer, death_count: &DeathCount) -> Self {
+        Self {
+            start: run_timer.stopwatch.elapsed(),
+            deaths: death_count.0,
+        }
+    }
+}
+
+/// The shown card, which goes away once the timer runs out.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+struct LevelStatsCard {
+    timer: Timer,
+    /// Whether gameplay was already frozen before the card froze it, so dismissing the card
+    /// doesn't end a freeze that isn't its own.
+    was_frozen: bool,
+}
+
+fn start_level_stats(
+    mut stats: ResMut<LevelStats>,
+    run_timer: Res<RunTimer>,
+    death_count: Res<DeathCount>,
+) {
+    *stats = LevelStats::new(&run_timer, &death_count);
+}
+
+/// The coins are read before [`LevelChanged`] moves them from the current attempt to the
+/// level's record.
+fn show_level_stats(
+    _: On<LevelCompleted>,
+    mut commands: Commands,
+    stats: Res<LevelStats>,
+    run_timer: Res<RunTimer>,
+    run_coins: Res<RunCoins>,
+    death_count: Res<DeathCount>,
+    mut frozen: ResMut<GameplayFrozen>,
+    cards: Query<(Entity, &LevelStatsCard)>,
+) {
+    // A card that's still up froze gameplay itself, so what came before it still counts.
+    let mut was_frozen = frozen.0;
+    for (entity, card) in &cards {
+        was_frozen = card.was_frozen;
+        commands.entity(entity).despawn();
+    }
+
+    let time = run_timer.stopwatch.elapsed().saturating_sub(stats.start);
+    let deaths = death_count.0.saturating_sub(stats.deaths);
+    frozen.freeze();
+    commands.spawn((
+        widget::ui_root("Level Stats"),
+        LevelStatsCard {
+            timer: Timer::new(LEVEL_STATS_DURATION, TimerMode::Once),
+            was_frozen,
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        GlobalZIndex(1),
+        DespawnOnExit(Screen::Gameplay),
+        children![
+            widget::header("Level Complete"),
+            widget::label(format!("Time: {}", format_duration(time))),
+            widget::label(format!("Coins: {}", run_coins.current)),
+            widget::label(format!("Deaths: {deaths}")),
+        ],
+    ));
+}
+
+fn reset_level_stats(
+    _: On<LevelChanged>,
+    mut stats: ResMut<LevelStats>,
+    run_timer: Res<RunTimer>,
+    death_count: Res<DeathCount>,
+) {
+    *stats = LevelStats::new(&run_timer, &death_count);
+}
+
+/// Uses the real time, since the card freezes gameplay.
+fn dismiss_level_stats(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    mut frozen: ResMut<GameplayFrozen>,
+    cards: Query<(Entity, &mut LevelStatsCard)>,
+) {
+    let skipped = keyboard.get_just_pressed().next().is_some()
+        || mouse.get_just_pressed().next().is_some()
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.get_just_pressed().next().is_some());
+    for (entity, mut card) in cards {
+        card.timer.tick(time.delta());
+        if skipped || card.timer.is_finished() {
+            commands.entity(entity).despawn();
+            if !card.was_frozen {
+                frozen.unfreeze();
+            }
+        }
+    }
+}
@@ -1,34 +1,45 @@
 use std::time::Duration;
 
-use avian2d::prelude::{CollisionStart, RigidBody, Sensor};
+use avian2d::prelude::{CollidingEntities, CollisionStart, Sensor};
 use bevy::{
     ecs::{lifecycle::HookContext, world::DeferredWorld},
-    image::{ImageLoaderSettings, ImageSampler},
     prelude::*,
 };
-use bevy_ecs_ldtk::{LdtkEntity, app::LdtkEntityAppExt};
+use bevy_ecs_ldtk::{EntityInstance, LdtkEntity, app::LdtkEntityAppExt, prelude::LdtkFields};
 use rand::seq::IndexedRandom;
 
 use crate::{
     AppSystems,
-    asset_tracking::LoadResource,
-    audio::sound_effect,
+    asset_tracking::{LoadResource, load_pixel_image},
+    audio::{SoundEffect, SoundKind, sound_effect},
     game::{
-        animation::{Animation, AnimationData, AnimationState, Repeat},
+        animation::{Animation, AnimationData, AnimationState, AtlasGrid, Repeat},
         colliders::ColliderBundle,
-        player::{Dead, Player},
+        defusal::Defusable,
+        destructible::{BLAST_RADIUS, Destructible, destroy_in_radius},
+        draw_order::DrawLayer,
+        ground_pound::GroundPounding,
+        level::ResetLevel,
+        particles::ParticleBurst,
+        player::{DeathCause, KillPlayer, Player},
+        rng::GameRng,
+        telegraph::{Telegraph, TelegraphFinished, Telegraphing},
     },
-    menus::Menu,
+    screens::Screen,
 };
 
 pub fn plugin(app: &mut App) {
     app.load_resource::<MineAssets>();
     app.register_ldtk_entity::<MineBundle>("Mine");
     app.add_systems(Update, despawn_explosion.in_set(AppSystems::Update));
+    app.add_observer(on_detonate);
+    app.add_observer(explode_after_telegraph);
+    app.add_observer(rearm_mines);
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component, Reflect)]
 #[reflect(Component)]
+#[require(DrawLayer::Hazard)]
 #[component(on_add = on_mine_add)]
 pub struct Mine;
 
@@ -40,6 +51,12 @@ pub fn on_mine_add(mut world: DeferredWorld, context: HookContext) {
         .insert(Visibility::Hidden);
 }
 
+/// A mine that exploded or was defused.
+/// It stays around without doing anything, so restarting the level can re-arm it.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct Spent;
+
 #[derive(Clone, Debug, Default, Bundle, LdtkEntity)]
 pub struct MineBundle {
     mine: Mine,
@@ -51,44 +68,160 @@ pub struct MineBundle {
     collider_bundle: ColliderBundle,
 
     sensor: Sensor,
+
+    #[with(telegraph_from_instance)]
+    telegraph: Telegraph,
+}
+
+/// The optional float field `TelegraphTime` is how many seconds a mine flashes before it
+/// explodes. Without it, mines explode as soon as they're stepped on.
+fn telegraph_from_instance(instance: &EntityInstance) -> Telegraph {
+    let seconds = instance
+        .get_float_field("TelegraphTime")
+        .copied()
+        .unwrap_or(0.0);
+    Telegraph(Duration::from_secs_f32(seconds.max(0.0)))
 }
 
+/// A player slamming down in a ground pound sets the mine off without getting hurt.
+///
+/// A mine with a [`Telegraph`] only starts flashing instead, see [`explode_after_telegraph`].
 pub fn on_player_touched_mine(
     event: On<CollisionStart>,
     mut commands: Commands,
-    mut next_menu: ResMut<NextState<Menu>>,
-    player_query: Query<Entity, With<Player>>,
-    mine_assets: Res<MineAssets>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    player_query: Query<Has<GroundPounding>, With<Player>>,
     parents: Query<&ChildOf>,
-    transforms: Query<&Transform>,
+    mines: Query<(&Telegraph, Has<Telegraphing>), Without<Spent>>,
 ) {
     let mine_entity = parents.get(event.collider1).unwrap().0;
     let other_entity = event.collider2;
 
-    for player_entity in player_query {
-        if player_entity == other_entity {
-            next_menu.set(Menu::Death);
+    let Ok((telegraph, telegraphing)) = mines.get(mine_entity) else {
+        return;
+    };
+    let Ok(ground_pounding) = player_query.get(other_entity) else {
+        return;
+    };
+
+    if !ground_pounding && !telegraph.0.is_zero() {
+        if !telegraphing {
             commands
-                .entity(player_entity)
-                .insert(Dead)
-                .remove::<RigidBody>();
+                .entity(mine_entity)
+                .insert((Telegraphing::new(telegraph.0), Visibility::Visible));
+        }
+        return;
+    }
+    if !ground_pounding {
+        commands.trigger(KillPlayer {
+            entity: other_entity,
+            cause: DeathCause::Mine,
+        });
+    }
+    commands.trigger(Detonate {
+        entity: mine_entity,
+    });
+}
+
+/// Kills the players still standing on the mine once it's done flashing.
+fn explode_after_telegraph(
+    event: On<TelegraphFinished>,
+    mut commands: Commands,
+    mines: Query<&Children, (With<Mine>, Without<Spent>)>,
+    sensors: Query<&CollidingEntities>,
+    players: Query<Has<GroundPounding>, With<Player>>,
+) {
+    let mine_entity = event.event().entity;
+    let Ok(children) = mines.get(mine_entity) else {
+        return;
+    };
 
-            let mine_transform = transforms.get(mine_entity).unwrap();
-            let mut transform = *mine_transform;
-            transform.translation.y += 6.5;
+    for colliding in sensors.iter_many(children) {
+        for &entity in colliding.iter() {
+            if players
+                .get(entity)
+                .is_ok_and(|ground_pounding| !ground_pounding)
+            {
+                commands.trigger(KillPlayer {
+                    entity,
+                    cause: DeathCause::Mine,
+                });
+            }
+        }
+    }
+    commands.trigger(Detonate {
+        entity: mine_entity,
+    });
+}
 
-            commands.entity(mine_entity).insert((
-                transform,
-                explosion(&mine_assets, &mut texture_atlas_layouts),
-                Visibility::Visible,
-            ));
+/// Sets a mine off. It explodes and breaks the destructible tiles around it, but doesn't hurt
+/// anyone by itself.
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct Detonate {
+    pub entity: Entity,
+}
 
-            let rng = &mut rand::rng();
-            let random_boom = mine_assets.booms.choose(rng).unwrap().clone();
+fn on_detonate(
+    event: On<Detonate>,
+    mut commands: Commands,
+    mine_assets: Res<MineAssets>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut game_rng: ResMut<GameRng>,
+    mines: Query<&GlobalTransform, (With<Mine>, Without<Spent>)>,
+    destructibles: Query<(Entity, &GlobalTransform), With<Destructible>>,
+) {
+    let mine_entity = event.event().entity;
+    let Ok(mine_global_transform) = mines.get(mine_entity) else {
+        return;
+    };
 
-            commands.spawn((Name::from("Boom Sound"), sound_effect(random_boom)));
-        }
+    commands
+        .entity(mine_entity)
+        .remove::<Telegraphing>()
+        .insert((Spent, Visibility::Hidden));
+    // The explosion is its own entity, so it can outlive a re-armed mine.
+    commands.spawn((
+        explosion(&mine_assets, &mut texture_atlas_layouts, &mut game_rng),
+        Transform::from_translation(mine_global_transform.translation() + Vec3::Y * 6.5),
+        DespawnOnExit(Screen::Gameplay),
+    ));
+    commands.spawn((
+        Name::new("Explosion Particles"),
+        ParticleBurst {
+            speed_range: 40.0..120.0,
+            ..ParticleBurst::colored(Color::srgb(1.0, 0.6, 0.2), 24)
+        },
+        Transform::from_translation(mine_global_transform.translation()),
+    ));
+
+    destroy_in_radius(
+        &mut commands,
+        mine_global_transform.translation().truncate(),
+        BLAST_RADIUS,
+        &destructibles,
+    );
+}
+
+/// Puts spent and flashing mines back into place and clears up explosions that are still
+/// going.
+fn rearm_mines(
+    _: On<ResetLevel>,
+    mut commands: Commands,
+    mines: Query<(Entity, Has<Defusable>), Or<(With<Spent>, With<Telegraphing>)>>,
+    explosions: Query<Entity, With<Explosion>>,
+) {
+    for (mine, defusable) in mines {
+        let visibility = if defusable {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+        commands
+            .entity(mine)
+            .remove::<(Spent, Telegraphing)>()
+            .insert(visibility);
+    }
+    for explosion in explosions {
+        commands.entity(explosion).despawn();
     }
 }
 
@@ -99,6 +232,7 @@ pub struct Explosion;
 pub fn explosion(
     mine_assets: &MineAssets,
     texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    game_rng: &mut GameRng,
 ) -> impl Bundle {
     let explode = AnimationData {
         frames: 8,
@@ -108,9 +242,16 @@ pub fn explosion(
         repeat: Repeat::OneShot,
     };
 
-    let layout = TextureAtlasLayout::from_grid(UVec2::splat(32), 8, 1, None, None);
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
-    let explode_animation = Animation::new(vec![explode]);
+    let texture_atlas_layout = texture_atlas_layouts.add(mine_assets.explosion_grid.layout());
+    let explode_animation =
+        Animation::try_new(vec![explode], mine_assets.explosion_grid.frame_count()).unwrap_or_else(
+            |error| {
+                error!("The explosion doesn't fit its sprite sheet: {error}");
+                Animation::still(AnimationState::Idle)
+            },
+        );
+
+    let random_boom = mine_assets.booms.choose(game_rng).unwrap().clone();
 
     (
         Explosion,
@@ -123,15 +264,25 @@ pub fn explosion(
             },
         ),
         explode_animation,
+        // The sound despawns itself once it's over, see `despawn_explosion`.
+        children![(
+            Name::from("Boom Sound"),
+            sound_effect(random_boom),
+            SoundKind::Explosion
+        )],
     )
 }
 
+/// Despawns explosions once both their animation and their sound are over,
+/// however long either of them is.
 pub fn despawn_explosion(
     mut commands: Commands,
-    explosions: Query<(Entity, &Animation), (With<Explosion>, Without<AudioSink>)>,
+    explosions: Query<(Entity, &Animation, Option<&Children>), With<Explosion>>,
+    sounds: Query<(), With<SoundEffect>>,
 ) {
-    for (entity, animation) in explosions {
-        if animation.is_finished() {
+    for (entity, animation, children) in explosions {
+        let playing = children.is_some_and(|children| sounds.iter_many(children).next().is_some());
+        if animation.is_finished() && !playing {
             commands.entity(entity).despawn();
         }
     }
@@ -142,6 +293,7 @@ pub fn despawn_explosion(
 pub struct MineAssets {
     #[dependency]
     explosion: Handle<Image>,
+    explosion_grid: AtlasGrid,
     #[dependency]
     pub booms: Vec<Handle<AudioSource>>,
 }
@@ -150,12 +302,8 @@ impl FromWorld for MineAssets {
     fn from_world(world: &mut World) -> Self {
         let assets = world.resource::<AssetServer>();
         Self {
-            explosion: assets.load_with_settings(
-                "images/boom.png",
-                |settings: &mut ImageLoaderSettings| {
-                    settings.sampler = ImageSampler::nearest();
-                },
-            ),
+            explosion: load_pixel_image(assets, "images/boom.png"),
+            explosion_grid: AtlasGrid::new(UVec2::splat(32), 8, 1),
             booms: vec![assets.load("audio/sound_effects/boom.ogg")],
         }
     }
@@ -0,0 +1,211 @@
+//! A small map of the current level in the corner of the screen, for finding the way around
+//! levels that don't fit on screen.
+//!
+//! It shows the level's bounds along with dots for the players, doors, goals and the coins that
+//! are still left. Nothing else is plotted, so the map stays cheap to update every frame.
+
+use bevy::{color::palettes::css, platform::collections::HashMap, prelude::*};
+use bevy_ecs_ldtk::{
+    LevelIid,
+    assets::{LdtkProject, LdtkProjectHandle},
+    prelude::RawLevelAccessor,
+};
+
+use crate::{
+    AppSystems,
+    game::{
+        coin::Coin,
+        debug_overlay::{DebugOverlay, DebugOverlayText},
+        door::Door,
+        goal::Goal,
+        player::Player,
+    },
+    save::SaveData,
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Minimap>();
+    app.add_systems(Startup, load_minimap);
+    app.add_systems(Update, save_minimap.run_if(resource_changed::<Minimap>));
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_minimap);
+    app.add_systems(
+        Update,
+        (update_minimap, make_room_for_debug_overlay)
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// The most room the map takes up on screen. It keeps the level's aspect ratio within it.
+const MINIMAP_MAX_SIZE: Vec2 = Vec2::new(160.0, 100.0);
+const DOT_SIZE: f32 = 4.0;
+/// The distance from the top left corner of the screen, or from the debug overlay while it's
+/// shown there.
+const MINIMAP_MARGIN: f32 = 10.0;
+
+/// Whether the minimap is shown.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct Minimap(pub bool);
+
+fn load_minimap(save_data: Res<SaveData>, mut minimap: ResMut<Minimap>) {
+    minimap.0 = save_data.minimap;
+}
+
+fn save_minimap(minimap: Res<Minimap>, mut save_data: ResMut<SaveData>) {
+    if save_data.minimap != minimap.0 {
+        save_data.minimap = minimap.0;
+    }
+}
+
+/// The level's outline, which the dots are placed in.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct MinimapFrame;
+
+/// A dot on the map for the entity it holds.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+struct MinimapDot(Entity);
+
+fn spawn_minimap(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Minimap"),
+        MinimapFrame,
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(MINIMAP_MARGIN),
+            left: px(MINIMAP_MARGIN),
+            border: UiRect::all(px(1)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        BorderColor::all(Color::WHITE.with_alpha(0.6)),
+        Visibility::Hidden,
+        DespawnOnExit(Screen::Gameplay),
+        Pickable::IGNORE,
+    ));
+}
+
+/// The F3 debug overlay shares the top left corner, so the map moves down below it while it's
+/// shown.
+fn make_room_for_debug_overlay(
+    debug_overlay: Res<DebugOverlay>,
+    overlay: Single<&ComputedNode, With<DebugOverlayText>>,
+    mut frame: Single<&mut Node, With<MinimapFrame>>,
+) {
+    let mut top = MINIMAP_MARGIN;
+    if debug_overlay.0 {
+        top += overlay.size().y * overlay.inverse_scale_factor();
+    }
+    if frame.top != px(top) {
+        frame.top = px(top);
+    }
+}
+
+fn dot_color(is_player: bool, is_door: bool, is_goal: bool) -> Color {
+    if is_player {
+        css::WHITE.into()
+    } else if is_door {
+        css::SKY_BLUE.into()
+    } else if is_goal {
+        css::LIME.into()
+    } else {
+        css::GOLD.into()
+    }
+}
+
+/// Dots are spawned and despawned as their entities come and go, or show up and hide, e.g.
+/// when a coin is collected. The map is hidden while no single level is loaded.
+fn update_minimap(
+    mut commands: Commands,
+    minimap: Res<Minimap>,
+    level: Option<Single<(&LevelIid, &GlobalTransform)>>,
+    ldtk_project: Option<Single<&LdtkProjectHandle>>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    frame: Single<(Entity, &mut Node, &mut Visibility), With<MinimapFrame>>,
+    mut dots: Query<(Entity, &MinimapDot, &mut Node), Without<MinimapFrame>>,
+    marked: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &InheritedVisibility,
+            Has<Player>,
+            Has<Door>,
+            Has<Goal>,
+        ),
+        Or<(With<Player>, With<Door>, With<Goal>, With<Coin>)>,
+    >,
+) {
+    let (frame, mut frame_node, mut frame_visibility) = frame.into_inner();
+    let level = level.zip(ldtk_project).and_then(|(level, ldtk_project)| {
+        let (level_iid, level_transform) = *level;
+        ldtk_project_assets
+            .get(*ldtk_project)
+            .and_then(|project| project.get_raw_level_by_iid(&level_iid.to_string()))
+            .map(|level_data| {
+                (
+                    level_transform.translation().truncate(),
+                    Vec2::new(level_data.px_wid as f32, level_data.px_hei as f32),
+                )
+            })
+    });
+    let Some((level_min, level_size)) = level.filter(|_| minimap.0) else {
+        *frame_visibility = Visibility::Hidden;
+        return;
+    };
+
+    *frame_visibility = Visibility::Inherited;
+    let scale = (MINIMAP_MAX_SIZE / level_size).min_element();
+    frame_node.width = px(level_size.x * scale);
+    frame_node.height = px(level_size.y * scale);
+
+    let mut dot_of: HashMap<Entity, Entity> = dots
+        .iter()
+        .map(|(dot, marked, _)| (marked.0, dot))
+        .collect();
+    for (entity, transform, visibility, is_player, is_door, is_goal) in &marked {
+        let position = (transform.translation().truncate() - level_min) / level_size;
+        let on_map =
+            visibility.get() && position.cmpge(Vec2::ZERO).all() && position.cmple(Vec2::ONE).all();
+        let dot = dot_of.remove(&entity);
+        match dot {
+            Some(dot) if on_map => {
+                if let Ok((_, _, mut node)) = dots.get_mut(dot) {
+                    node.left = percent(position.x * 100.0);
+                    node.bottom = percent(position.y * 100.0);
+                }
+            }
+            Some(dot) => commands.entity(dot).despawn(),
+            None if on_map => {
+                commands.spawn((
+                    Name::new("Minimap Dot"),
+                    MinimapDot(entity),
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: percent(position.x * 100.0),
+                        bottom: percent(position.y * 100.0),
+                        width: px(DOT_SIZE),
+                        height: px(DOT_SIZE),
+                        // Centers the dot on its position.
+                        margin: UiRect {
+                            left: px(-DOT_SIZE / 2.0),
+                            bottom: px(-DOT_SIZE / 2.0),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    BackgroundColor(dot_color(is_player, is_door, is_goal)),
+                    Pickable::IGNORE,
+                    ChildOf(frame),
+                ));
+            }
+            None => {}
+        }
+    }
+    // Whatever is left marks an entity that's gone.
+    for dot in dot_of.into_values() {
+        commands.entity(dot).despawn();
+    }
+}
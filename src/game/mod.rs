@@ -1,14 +1,61 @@
 use bevy::prelude::*;
 
+pub mod accessibility;
+pub mod achievements;
+mod animated_tiles;
 mod animation;
+#[cfg(feature = "dev")]
+mod animation_reload;
+pub mod assist;
+mod background;
+mod bouncer;
+pub mod camera_bounds;
+pub mod coin;
+mod collider_gizmos;
 mod colliders;
+pub mod combo;
+mod damage_zone;
+pub mod dash;
+mod debug_overlay;
+mod defusal;
+mod destructible;
 mod door;
+mod draw_order;
+mod enemy;
+mod force_zone;
+pub mod ghost;
+mod goal;
+pub mod gravity_flip;
 mod grid_coords;
+mod grid_overlay;
+mod ground_pound;
+pub mod health;
+pub mod letterbox;
 pub mod level;
+#[cfg(feature = "dev")]
+mod level_editor;
+mod level_hud;
+mod level_music;
+mod level_stats;
 mod mine;
-mod physics;
+pub mod minimap;
+pub mod objectives;
+mod particles;
+mod perf_overlay;
+pub mod physics;
 pub mod player;
+mod powerup;
+pub mod rewind;
+mod rng;
+pub mod run_timer;
+pub mod skin;
 mod spike;
+mod squash_stretch;
+pub mod telegraph;
+pub mod touch_controls;
+pub mod trigger_zone;
+mod turret;
+mod unstuck;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
@@ -17,9 +64,73 @@ pub(super) fn plugin(app: &mut App) {
         player::plugin,
         physics::plugin,
         grid_coords::plugin,
+        colliders::plugin,
+        particles::plugin,
+        run_timer::plugin,
+        ghost::plugin,
+        touch_controls::plugin,
+        health::plugin,
+        unstuck::plugin,
+        accessibility::plugin,
+        skin::plugin,
+        rng::plugin,
+    ));
+
+    // Difficulty options.
+    app.add_plugins((assist::plugin, rewind::plugin));
+
+    // Special moves.
+    app.add_plugins((combo::plugin, dash::plugin, ground_pound::plugin));
+
+    // Progression.
+    app.add_plugins(achievements::plugin);
+
+    // Presentation.
+    app.add_plugins((
+        camera_bounds::plugin,
+        squash_stretch::plugin,
+        animated_tiles::plugin,
+        level_hud::plugin,
+        letterbox::plugin,
+        background::plugin,
+        level_music::plugin,
+        level_stats::plugin,
+        minimap::plugin,
+        draw_order::plugin,
+    ));
+
+    // Level entities.
+    app.add_plugins((
         door::plugin,
+        goal::plugin,
+        objectives::plugin,
+        bouncer::plugin,
+        force_zone::plugin,
+        destructible::plugin,
+        powerup::plugin,
+        gravity_flip::plugin,
+        coin::plugin,
+        trigger_zone::plugin,
+    ));
+
+    // Hazards.
+    app.add_plugins((
         spike::plugin,
         mine::plugin,
-        colliders::plugin,
+        defusal::plugin,
+        enemy::plugin,
+        turret::plugin,
+        damage_zone::plugin,
+        telegraph::plugin,
+    ));
+
+    // Debugging aids.
+    app.add_plugins((
+        collider_gizmos::plugin,
+        debug_overlay::plugin,
+        grid_overlay::plugin,
+        perf_overlay::plugin,
     ));
+    #[cfg(feature = "dev")]
+    app.add_plugins((level_editor::plugin, animation_reload::plugin));
 }
@@ -0,0 +1,186 @@
+//! Objectives a level can require before its exits open.
+//!
+//! In LDtk this is the bool level field `CollectAllCoins`.
+//! Until they are met, doors and goals stay shut and show a hint about what's left.
+//!
+//! What's left is counted from the level's entities, so restarting the level puts the
+//! collected coins back on the list on its own.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_ecs_ldtk::{
+    LevelSelection,
+    assets::{LdtkProject, LdtkProjectHandle},
+    prelude::LdtkFields,
+};
+
+use crate::{
+    AppSystems, PausableSystems,
+    game::{
+        coin::{Coin, Collected},
+        level::{LevelChanged, selected_level},
+    },
+    screens::Screen,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LevelObjectives>();
+    app.init_resource::<ObjectiveHintTimer>();
+    app.add_observer(reset_objectives);
+    app.add_observer(show_objective_hint);
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_objective_hint);
+    app.add_systems(
+        Update,
+        (
+            tick_objective_hint
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+            (
+                read_level_objectives,
+                count_objectives,
+                update_objective_hint,
+            )
+                .chain()
+                .in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// How long the hint stays on screen after trying to leave early.
+const HINT_DURATION: Duration = Duration::from_secs(2);
+
+/// What the current level requires before it can be left, and how much of it is left.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct LevelObjectives {
+    /// The position of the level the objectives were read from, once it's known.
+    level: Option<usize>,
+    pub collect_all_coins: bool,
+    pub coins_left: usize,
+}
+
+impl LevelObjectives {
+    /// Whether every objective of the level is met, which is also the case without any.
+    pub fn complete(&self) -> bool {
+        !self.collect_all_coins || self.coins_left == 0
+    }
+
+    /// Describes what's still left to do, one objective per line.
+    pub fn hint(&self) -> String {
+        let mut lines = Vec::new();
+        if self.collect_all_coins && self.coins_left > 0 {
+            lines.push(format!("Collect {} more coin(s)", self.coins_left));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Triggered when the players try to leave a level before its objectives are met.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ObjectivesIncomplete;
+
+/// Counts down how long the hint is still shown.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+struct ObjectiveHintTimer(Timer);
+
+impl Default for ObjectiveHintTimer {
+    fn default() -> Self {
+        let mut timer = Timer::new(HINT_DURATION, TimerMode::Once);
+        // Nothing to show until the players try to leave.
+        timer.finish();
+        Self(timer)
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ObjectiveHintText;
+
+fn reset_objectives(_: On<LevelChanged>, mut objectives: ResMut<LevelObjectives>) {
+    *objectives = LevelObjectives::default();
+}
+
+/// The project may still be loading when the level starts, so the level is looked up until
+/// it's found.
+fn read_level_objectives(
+    mut objectives: ResMut<LevelObjectives>,
+    level_selection: Res<LevelSelection>,
+    ldtk_project: Option<Single<&LdtkProjectHandle>>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+) {
+    if objectives.level.is_some() {
+        return;
+    }
+    let level = ldtk_project
+        .and_then(|handle| ldtk_project_assets.get(*handle))
+        .and_then(|project| selected_level(&level_selection, project));
+    if let Some((position, level)) = level {
+        objectives.level = Some(position);
+        objectives.collect_all_coins = level
+            .get_bool_field("CollectAllCoins")
+            .copied()
+            .unwrap_or(false);
+    }
+}
+
+pub fn count_objectives(
+    mut objectives: ResMut<LevelObjectives>,
+    coins: Query<(), (With<Coin>, Without<Collected>)>,
+) {
+    let coins_left = coins.iter().count();
+    // Avoid triggering change detection every frame.
+    if objectives.coins_left != coins_left {
+        objectives.coins_left = coins_left;
+    }
+}
+
+fn spawn_objective_hint(mut commands: Commands, mut timer: ResMut<ObjectiveHintTimer>) {
+    *timer = ObjectiveHintTimer::default();
+    commands.spawn((
+        Name::new("Objective Hint"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            bottom: px(40),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        DespawnOnExit(Screen::Gameplay),
+        Pickable::IGNORE,
+        children![(widget::label(""), ObjectiveHintText)],
+    ));
+}
+
+fn show_objective_hint(_: On<ObjectivesIncomplete>, mut timer: ResMut<ObjectiveHintTimer>) {
+    timer.0.reset();
+}
+
+fn tick_objective_hint(time: Res<Time>, mut timer: ResMut<ObjectiveHintTimer>) {
+    timer.0.tick(time.delta());
+}
+
+/// Keeps the hint up to date while it's shown, so it counts down as the players go back for
+/// what they missed.
+fn update_objective_hint(
+    timer: Res<ObjectiveHintTimer>,
+    objectives: Res<LevelObjectives>,
+    text: Single<(&mut Text, &mut Visibility), With<ObjectiveHintText>>,
+) {
+    let (mut text, mut visibility) = text.into_inner();
+    let shown = !timer.0.is_finished() && !objectives.complete();
+    visibility.set_if_neq(if shown {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    });
+    if shown {
+        let hint = objectives.hint();
+        if text.0 != hint {
+            text.0 = hint;
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Small bursts of short-lived sprites for dust, debris and explosions.
+
+use std::{f32::consts::TAU, ops::Range, time::Duration};
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{AppSystems, PausableSystems, game::accessibility::ReducedMotion, screens::Screen};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (emit_particle_bursts, update_particles)
+            .chain()
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// Upper bound for live particles, so a chain of bursts can't flood the world with entities.
+const MAX_PARTICLES: usize = 256;
+
+/// Spawn this with a world-space [`Transform`] to emit a burst of particles there.
+/// The burst entity itself is despawned right away.
+#[derive(Component, Clone, Debug)]
+pub struct ParticleBurst {
+    pub count: usize,
+    pub lifetime: Duration,
+    pub speed_range: Range<f32>,
+    /// Downwards acceleration of the particles.
+    pub gravity: f32,
+    /// The sprite each particle is drawn with. Its alpha fades out over the lifetime.
+    pub sprite: Sprite,
+}
+
+impl ParticleBurst {
+    /// A burst of small squares in a single color.
+    pub fn colored(color: Color, count: usize) -> Self {
+        Self {
+            count,
+            lifetime: Duration::from_millis(400),
+            speed_range: 20.0..60.0,
+            gravity: 100.0,
+            sprite: Sprite::from_color(color, Vec2::splat(2.0)),
+        }
+    }
+}
+
+/// A single particle spawned by a [`ParticleBurst`].
+#[derive(Component, Debug)]
+pub struct Particle {
+    velocity: Vec2,
+    gravity: f32,
+    lifetime: Timer,
+    alpha: f32,
+}
+
+fn emit_particle_bursts(
+    mut commands: Commands,
+    bursts: Query<(Entity, &ParticleBurst, &Transform), Added<ParticleBurst>>,
+    particles: Query<(), With<Particle>>,
+    reduced_motion: Res<ReducedMotion>,
+) {
+    // With reduced motion the bursts are still consumed, they just don't emit anything.
+    let mut budget = if reduced_motion.0 {
+        0
+    } else {
+        MAX_PARTICLES.saturating_sub(particles.iter().len())
+    };
+    let rng = &mut rand::rng();
+
+    for (entity, burst, transform) in bursts {
+        commands.entity(entity).despawn();
+
+        let count = burst.count.min(budget);
+        budget -= count;
+        for _ in 0..count {
+            let angle = rng.random_range(0.0..TAU);
+            let speed = rng.random_range(burst.speed_range.clone());
+            commands.spawn((
+                Name::new("Particle"),
+                Particle {
+                    velocity: Vec2::from_angle(angle) * speed,
+                    gravity: burst.gravity,
+                    lifetime: Timer::new(burst.lifetime, TimerMode::Once),
+                    alpha: burst.sprite.color.alpha(),
+                },
+                burst.sprite.clone(),
+                Transform::from_translation(transform.translation),
+                DespawnOnExit(Screen::Gameplay),
+            ));
+        }
+    }
+}
+
+fn update_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut particles: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut particle, mut transform, mut sprite) in &mut particles {
+        particle.lifetime.tick(time.delta());
+        if particle.lifetime.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        particle.velocity.y -= particle.gravity * time.delta_secs();
+        transform.translation += particle.velocity.extend(0.0) * time.delta_secs();
+        sprite
+            .color
+            .set_alpha(particle.alpha * particle.lifetime.fraction_remaining());
+    }
+}
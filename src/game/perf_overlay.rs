@@ -0,0 +1,103 @@
+//! An optional overlay that shows the frame rate and how many entities and colliders exist.
+//! Useful for spotting levels or effects that spawn more than they should.
+
+use avian2d::prelude::Collider;
+use bevy::{
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    ecs::entity::Entities,
+    input::common_conditions::input_just_pressed,
+    prelude::*,
+};
+
+use crate::{
+    AppSystems,
+    theme::widget::{TOAST_MARGIN, TOAST_WIDTH},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+        app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+    }
+    app.init_resource::<PerfOverlay>();
+    app.add_systems(Startup, spawn_perf_overlay);
+    app.add_systems(
+        Update,
+        (
+            toggle_perf_overlay.run_if(input_just_pressed(TOGGLE_PERF_OVERLAY_KEY)),
+            apply_perf_overlay_visibility.run_if(resource_changed::<PerfOverlay>),
+            update_perf_overlay.run_if(perf_overlay_enabled),
+        )
+            .chain()
+            .in_set(AppSystems::Update),
+    );
+}
+
+/// F4 already toggles the level editor in dev builds.
+const TOGGLE_PERF_OVERLAY_KEY: KeyCode = KeyCode::F7;
+
+/// Whether the performance overlay is shown.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PerfOverlay(pub bool);
+
+pub fn perf_overlay_enabled(perf_overlay: Res<PerfOverlay>) -> bool {
+    perf_overlay.0
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct PerfOverlayText;
+
+fn spawn_perf_overlay(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Performance Overlay"),
+        PerfOverlayText,
+        Text::default(),
+        TextFont::from_font_size(14.0),
+        TextLayout::new_with_justify(Justify::Right),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(5),
+            // Left of the toasts, which take the top right corner.
+            right: px(TOAST_MARGIN + TOAST_WIDTH + 10.0),
+            ..default()
+        },
+        GlobalZIndex(10),
+        Visibility::Hidden,
+        Pickable::IGNORE,
+    ));
+}
+
+fn toggle_perf_overlay(mut perf_overlay: ResMut<PerfOverlay>) {
+    perf_overlay.0 = !perf_overlay.0;
+}
+
+fn apply_perf_overlay_visibility(
+    perf_overlay: Res<PerfOverlay>,
+    mut visibility: Single<&mut Visibility, With<PerfOverlayText>>,
+) {
+    **visibility = if perf_overlay.0 {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+}
+
+fn update_perf_overlay(
+    diagnostics: Res<DiagnosticsStore>,
+    entities: &Entities,
+    colliders: Query<(), With<Collider>>,
+    mut text: Single<&mut Text, With<PerfOverlayText>>,
+) {
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .map_or("-".to_string(), |fps| format!("{fps:.0}"));
+    text.0 = format!(
+        "fps: {fps}\n\
+         entities: {}\n\
+         colliders: {}",
+        entities.len(),
+        colliders.iter().count(),
+    );
+}
@@ -1,10 +1,14 @@
-use avian2d::{PhysicsPlugins, prelude::Gravity};
-use bevy::{
-    app::{App, FixedUpdate},
-    math::Vec2,
+use avian2d::{
+    PhysicsPlugins,
+    prelude::{Friction, Gravity, Physics, SubstepCount, TransformInterpolation},
 };
+use bevy::prelude::*;
+use bevy_ecs_ldtk::IntGridCell;
 use bevy_tnua::prelude::TnuaControllerPlugin;
 use bevy_tnua_avian2d::TnuaAvian2dPlugin;
+use serde::{Deserialize, Serialize};
+
+use crate::{GameplayFrozen, Pause, save::SaveData};
 
 pub fn plugin(app: &mut App) {
     app.add_plugins((
@@ -13,4 +17,205 @@ pub fn plugin(app: &mut App) {
         TnuaAvian2dPlugin::new(FixedUpdate),
     ))
     .insert_resource(Gravity(Vec2::NEG_Y * 300.0));
+    app.init_resource::<PhysicsInterpolation>();
+    app.init_resource::<SurfaceConfig>();
+    app.init_resource::<PhysicsQuality>();
+    app.add_systems(Startup, load_physics_quality);
+    app.add_systems(
+        Update,
+        (
+            freeze_physics.run_if(resource_changed::<GameplayFrozen>),
+            toggle_interpolation.run_if(resource_changed::<PhysicsInterpolation>),
+            (apply_physics_quality, save_physics_quality)
+                .run_if(resource_changed::<PhysicsQuality>),
+        ),
+    );
+    app.add_observer(interpolate_added);
+}
+
+/// The friction of the players and of the surfaces in the levels.
+///
+/// The players have none at all. Tnua keeps them floating above the ground, so friction only
+/// ever comes into play when they press against a wall, where it would let them cling to it
+/// instead of sliding down. Regular walls have none for the same reason.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct SurfaceConfig {
+    pub player: f32,
+    pub wall: f32,
+    pub ice: f32,
+    pub sticky: f32,
+    /// Scales how fast players speed up, slow down and turn around while standing on ice.
+    /// Their friction never touches the ground, so this is what makes ice slippery.
+    pub ice_acceleration: f32,
+}
+
+impl Default for SurfaceConfig {
+    fn default() -> Self {
+        Self {
+            player: 0.0,
+            wall: 0.0,
+            ice: 0.0,
+            sticky: 1.0,
+            ice_acceleration: 0.15,
+        }
+    }
+}
+
+impl SurfaceConfig {
+    pub fn friction(&self, surface: Surface) -> Friction {
+        Friction::new(match surface {
+            Surface::Wall => self.wall,
+            Surface::Ice => self.ice,
+            Surface::Sticky => self.sticky,
+        })
+    }
+
+    /// The factor to scale the players' walking acceleration by while standing on `surface`.
+    pub fn acceleration(&self, surface: Surface) -> f32 {
+        match surface {
+            Surface::Ice => self.ice_acceleration,
+            Surface::Wall | Surface::Sticky => 1.0,
+        }
+    }
+}
+
+/// What a wall tile is made of. In LDtk these are the `WallLayer` values 1 (wall), 3 (ice)
+/// and 4 (sticky).
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect)]
+#[reflect(Component)]
+pub enum Surface {
+    #[default]
+    Wall,
+    Ice,
+    Sticky,
+}
+
+impl From<IntGridCell> for Surface {
+    fn from(cell: IntGridCell) -> Self {
+        match cell.value {
+            3 => Surface::Ice,
+            4 => Surface::Sticky,
+            _ => Surface::Wall,
+        }
+    }
+}
+
+/// How often and how finely the physics are simulated.
+///
+/// Higher settings keep fast movement and stacked collisions more accurate, and make the
+/// movement feel more immediate on high refresh rate screens, but cost more CPU time every
+/// frame. Lower settings are cheaper, at the cost of a little more input delay and of
+/// collisions that are resolved less precisely.
+///
+/// The movement is tuned in units per second, so jumps and walking reach the same heights and
+/// speeds with every setting. [`PhysicsInterpolation`] hides the difference in rate.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub enum PhysicsQuality {
+    Low,
+    /// The defaults of Bevy and Avian.
+    #[default]
+    Medium,
+    High,
+}
+
+impl PhysicsQuality {
+    /// How many times a second `FixedUpdate` runs.
+    ///
+    /// Low doesn't go below 48 Hz, where Tnua's spring still holds the players steadily at
+    /// their float height.
+    pub fn fixed_hz(self) -> f64 {
+        match self {
+            PhysicsQuality::Low => 48.0,
+            PhysicsQuality::Medium => 64.0,
+            PhysicsQuality::High => 120.0,
+        }
+    }
+
+    /// How many substeps the solver splits every physics step into.
+    pub fn substeps(self) -> u32 {
+        match self {
+            PhysicsQuality::Low => 4,
+            PhysicsQuality::Medium => 6,
+            PhysicsQuality::High => 8,
+        }
+    }
+}
+
+fn load_physics_quality(save_data: Res<SaveData>, mut quality: ResMut<PhysicsQuality>) {
+    *quality = save_data.physics_quality;
+}
+
+fn save_physics_quality(quality: Res<PhysicsQuality>, mut save_data: ResMut<SaveData>) {
+    if save_data.physics_quality != *quality {
+        save_data.physics_quality = *quality;
+    }
+}
+
+fn apply_physics_quality(
+    quality: Res<PhysicsQuality>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+    mut substeps: ResMut<SubstepCount>,
+) {
+    fixed_time.set_timestep_hz(quality.fixed_hz());
+    substeps.0 = quality.substeps();
+}
+
+/// Whether things that are moved by the physics are drawn between their last two physics
+/// positions, instead of jumping from one fixed timestep to the next.
+///
+/// Without it, motion stutters whenever the frame rate doesn't match the fixed timestep.
+/// It only applies to entities with [`Interpolated`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct PhysicsInterpolation(pub bool);
+
+impl Default for PhysicsInterpolation {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Marks something that moves with the physics and should be drawn smoothly, like the players
+/// and moving hazards. Static level geometry doesn't need it.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Interpolated;
+
+fn interpolate_added(
+    add: On<Add, Interpolated>,
+    mut commands: Commands,
+    interpolation: Res<PhysicsInterpolation>,
+) {
+    if interpolation.0 {
+        commands.entity(add.entity).insert(TransformInterpolation);
+    }
+}
+
+fn toggle_interpolation(
+    mut commands: Commands,
+    interpolation: Res<PhysicsInterpolation>,
+    entities: Query<Entity, With<Interpolated>>,
+) {
+    for entity in entities {
+        if interpolation.0 {
+            commands.entity(entity).insert(TransformInterpolation);
+        } else {
+            commands.entity(entity).remove::<TransformInterpolation>();
+        }
+    }
+}
+
+/// Physics stops along with the rest of the gameplay, and stays stopped if a menu paused it.
+fn freeze_physics(
+    frozen: Res<GameplayFrozen>,
+    pause: Res<State<Pause>>,
+    mut time: ResMut<Time<Physics>>,
+) {
+    if frozen.0 {
+        time.pause();
+    } else if !pause.get().0 {
+        time.unpause();
+    }
 }
@@ -2,42 +2,132 @@
 
 use std::time::Duration;
 
-use avian2d::prelude::{Collider, CollisionEventsEnabled, Friction, LockedAxes, RigidBody};
+use avian2d::prelude::{
+    Collider, CollisionEventsEnabled, Friction, LinearVelocity, LockedAxes, RigidBody,
+};
 use bevy::{
-    ecs::{lifecycle::HookContext, world::DeferredWorld},
-    image::{ImageLoaderSettings, ImageSampler},
+    ecs::{entity::EntityHashMap, lifecycle::HookContext, world::DeferredWorld},
     prelude::*,
 };
-use bevy_ecs_ldtk::LdtkEntity;
+use bevy_ecs_ldtk::{
+    LdtkEntity, LevelEvent, LevelIid,
+    assets::{LdtkProject, LdtkProjectHandle},
+    prelude::RawLevelAccessor,
+};
 use bevy_tnua::{
     TnuaUserControlsSystems,
+    builtins::{TnuaBuiltinDash, TnuaBuiltinDashState, TnuaBuiltinJumpState},
     prelude::{TnuaBuiltinJump, TnuaBuiltinWalk, TnuaController},
 };
 use bevy_tnua_avian2d::TnuaAvian2dSensorShape;
 use rand::seq::IndexedRandom;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     AppSystems, PausableSystems,
-    asset_tracking::LoadResource,
-    audio::sound_effect,
-    follow_camera,
-    game::animation::{Animation, AnimationData, AnimationState, Repeat},
+    asset_tracking::{LoadResource, load_pixel_image},
+    audio::{SoundKind, sound_effect},
+    game::{
+        accessibility::ReducedMotion,
+        animation::{Animation, Facing},
+        assist::AssistMode,
+        colliders::GameLayer,
+        combo::ComboBuffer,
+        damage_zone::ZoneDamage,
+        dash::{DashInput, MovementInput, MovementState},
+        draw_order::{DrawLayer, DrawOrder},
+        force_zone::ForceZoneDrift,
+        ghost::GhostRecorder,
+        gravity_flip::GravityFlip,
+        ground_pound::GroundPounding,
+        level::{LevelEntry, ResetLevel, loaded_project},
+        particles::ParticleBurst,
+        physics::{Interpolated, Surface, SurfaceConfig},
+        powerup::ActivePowerups,
+        rewind::{REWIND_SECONDS, RewindCharges, Rewinding},
+        rng::GameRng,
+        skin::{PlayerSkin, SKINS},
+        squash_stretch::{SquashStretch, squash_and_stretch},
+        touch_controls::TouchActions,
+    },
+    gameplay_frozen,
+    menus::Menu,
+    save::SaveData,
+    screens::Screen,
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.load_resource::<PlayerAssets>();
 
     // Record directional input as movement controls.
+    app.add_systems(
+        FixedUpdate,
+        apply_controls
+            .in_set(TnuaUserControlsSystems)
+            .run_if(not(gameplay_frozen)),
+    );
+    app.add_systems(
+        FixedUpdate,
+        (limit_fall_speed, detect_landings).after(TnuaUserControlsSystems),
+    );
+    app.add_systems(
+        Update,
+        (despawn_player, spawn_players_without_spawner).in_set(AppSystems::Update),
+    );
+    app.add_systems(
+        Update,
+        (tick_stun, tick_invulnerability, tick_respawning)
+            .in_set(AppSystems::TickTimers)
+            .in_set(PausableSystems),
+    );
+    app.add_systems(
+        Update,
+        (
+            blink_invulnerable,
+            grow_respawning.after(squash_and_stretch),
+        )
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
     app.add_systems(
         Update,
-        (follow_camera)
-            .in_set(AppSystems::RecordInput)
+        spawn_death_particles
+            .in_set(AppSystems::Update)
             .in_set(PausableSystems),
     );
-    app.add_systems(FixedUpdate, apply_controls.in_set(TnuaUserControlsSystems));
-    app.add_systems(Update, despawn_player.in_set(AppSystems::Update));
+
+    app.init_resource::<DeathCount>();
+    app.init_resource::<LastDeathCause>();
+    app.init_resource::<RespawnMode>();
+    app.init_resource::<PracticeMode>();
+    app.init_resource::<PlayerCount>();
+    app.init_resource::<StunDuration>();
+    app.init_resource::<SpawnProtection>();
+    app.init_resource::<MovementConfig>();
+    app.init_resource::<JumpTrigger>();
+    app.init_resource::<RespawnTransition>();
+    app.init_resource::<AutoRun>();
+    app.add_systems(
+        Startup,
+        (load_jump_trigger, load_respawn_transition, load_auto_run),
+    );
+    app.add_systems(
+        Update,
+        (
+            save_jump_trigger.run_if(resource_changed::<JumpTrigger>),
+            save_respawn_transition.run_if(resource_changed::<RespawnTransition>),
+            save_auto_run.run_if(resource_changed::<AutoRun>),
+        ),
+    );
+    app.add_systems(OnEnter(Screen::Title), reset_death_count);
 
     app.add_observer(on_spawn_player);
+    app.add_observer(on_spawn_player_at);
+    app.add_observer(on_respawn_players);
+    app.add_observer(on_kill_player);
+    app.add_observer(on_player_hurt);
+    app.add_observer(end_invulnerability);
+    app.add_observer(spawn_landing_dust);
 }
 
 #[derive(Default, Bundle, LdtkEntity)]
@@ -55,80 +145,226 @@ pub fn on_player_spawn_add(mut world: DeferredWorld, context: HookContext) {
     world.trigger(SpawnPlayer(spawner_entity));
 }
 
+/// Spawns the players as children of the given [`PlayerSpawn`].
 #[derive(Event)]
 pub struct SpawnPlayer(pub Entity);
 
+/// Spawns the players at a world position inside the current level, e.g. for checkpoints or
+/// scripted sequences, without needing a [`PlayerSpawn`] there.
+#[derive(Event)]
+pub struct SpawnPlayerAt(pub Vec2);
+
+/// Spawns the players at the current level's [`PlayerSpawn`], e.g. to restart the level.
+///
+/// A level without one still gets its players, in the center of the level.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RespawnPlayers;
+
+fn on_respawn_players(
+    _: On<RespawnPlayers>,
+    mut commands: Commands,
+    spawners: Query<Entity, With<PlayerSpawn>>,
+    levels: Query<(&LevelIid, &GlobalTransform)>,
+    ldtk_projects: Query<&LdtkProjectHandle>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+) {
+    let mut spawners = spawners.iter();
+    if let Some(spawner) = spawners.next() {
+        if spawners.next().is_some() {
+            warn!("The level has more than one PlayerSpawn, using the first one");
+        }
+        commands.trigger(SpawnPlayer(spawner));
+        return;
+    }
+
+    let Ok((level_iid, level_transform)) = levels.single() else {
+        warn!("Can't spawn the players without exactly one level");
+        return;
+    };
+    let ldtk_project = match loaded_project(&ldtk_projects, &ldtk_project_assets) {
+        Ok(ldtk_project) => ldtk_project,
+        Err(error) => {
+            warn!("Can't spawn the players: {error}");
+            return;
+        }
+    };
+    let Some(level) = ldtk_project.get_raw_level_by_iid(&level_iid.to_string()) else {
+        warn!("Can't spawn the players: the LDtk project has no level with the iid {level_iid}");
+        return;
+    };
+    warn!(
+        "Level {} has no PlayerSpawn, spawning the players in its center",
+        level.identifier
+    );
+    let center = Vec2::new(level.px_wid as f32, level.px_hei as f32) / 2.0;
+    commands.trigger(SpawnPlayerAt(
+        level_transform.translation().truncate() + center,
+    ));
+}
+
+/// Players are spawned by the [`PlayerSpawn`] as soon as it's added, so only levels without one
+/// need to be taken care of.
+///
+/// Runs before the players are placed at an exit, which moves [`LevelEntry`] back to the start.
+pub fn spawn_players_without_spawner(
+    mut commands: Commands,
+    mut level_events: MessageReader<LevelEvent>,
+    level_entry: Res<LevelEntry>,
+    spawners: Query<(), With<PlayerSpawn>>,
+) {
+    for event in level_events.read() {
+        if matches!(event, LevelEvent::Transformed(_))
+            && spawners.is_empty()
+            && *level_entry == LevelEntry::Start
+        {
+            commands.trigger(RespawnPlayers);
+        }
+    }
+}
+
+/// How many players play at once. Each player beyond the first uses its own [`InputBindings`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct PlayerCount(pub usize);
+
+impl Default for PlayerCount {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+pub const MAX_PLAYERS: usize = 2;
+
+/// Spawns every player that isn't alive right now, so no player ever exists twice.
+///
+/// Players coming back into a level are placed at its exit instead, see [`LevelEntry`].
 fn on_spawn_player(
     event: On<SpawnPlayer>,
     mut commands: Commands,
+    level_entry: Res<LevelEntry>,
     player_assets: Res<PlayerAssets>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
-    players: Query<(), (With<Player>, Without<Dead>)>,
+    player_count: Res<PlayerCount>,
+    skin: Res<PlayerSkin>,
+    surface_config: Res<SurfaceConfig>,
+    spawn_protection: Res<SpawnProtection>,
+    respawn_transition: Res<RespawnTransition>,
+    players: Query<&PlayerIndex, (With<Player>, Without<Dead>)>,
 ) {
-    if players.is_empty() {
+    if *level_entry == LevelEntry::Exit {
+        return;
+    }
+    let missing = missing_players(&player_count, &players);
+    if !missing.is_empty() {
         commands.entity(event.event().0).with_children(|p| {
-            p.spawn(player(&player_assets, &mut texture_atlas_layouts));
+            for index in missing {
+                let mut spawned = p.spawn((
+                    player(&player_assets, *skin, &surface_config, index),
+                    Invulnerable::new(spawn_protection.0),
+                ));
+                if *respawn_transition == RespawnTransition::Animated {
+                    spawned.insert(Respawning::default());
+                }
+            }
         });
+        play_respawn_sound(&mut commands, &player_assets, *respawn_transition);
     }
 }
 
+/// Like [`on_spawn_player`], but the players are placed at an absolute position.
+///
+/// They become children of the level, so they're still cleaned up when it changes.
+fn on_spawn_player_at(
+    event: On<SpawnPlayerAt>,
+    mut commands: Commands,
+    player_assets: Res<PlayerAssets>,
+    player_count: Res<PlayerCount>,
+    skin: Res<PlayerSkin>,
+    surface_config: Res<SurfaceConfig>,
+    spawn_protection: Res<SpawnProtection>,
+    respawn_transition: Res<RespawnTransition>,
+    draw_order: Res<DrawOrder>,
+    players: Query<&PlayerIndex, (With<Player>, Without<Dead>)>,
+    levels: Query<(Entity, &GlobalTransform), With<LevelIid>>,
+) {
+    let Ok((level, level_transform)) = levels.single() else {
+        warn!("Can't spawn the players at a position without exactly one level");
+        return;
+    };
+
+    let position = event.event().0 - level_transform.translation().truncate();
+    // Starts out at its draw layer, so it's never drawn behind the level for a frame.
+    let z = draw_order.z(DrawLayer::Player) - level_transform.translation().z;
+    let missing = missing_players(&player_count, &players);
+    if !missing.is_empty() {
+        commands.entity(level).with_children(|p| {
+            for index in missing {
+                let mut spawned = p.spawn((
+                    player(&player_assets, *skin, &surface_config, index),
+                    Transform::from_translation(position.extend(z)),
+                    Invulnerable::new(spawn_protection.0),
+                ));
+                if *respawn_transition == RespawnTransition::Animated {
+                    spawned.insert(Respawning::default());
+                }
+            }
+        });
+        play_respawn_sound(&mut commands, &player_assets, *respawn_transition);
+    }
+}
+
+/// The indices of the players that should be alive but aren't.
+fn missing_players(
+    player_count: &PlayerCount,
+    players: &Query<&PlayerIndex, (With<Player>, Without<Dead>)>,
+) -> Vec<PlayerIndex> {
+    (0..player_count.0)
+        .map(PlayerIndex)
+        .filter(|index| !players.iter().any(|player| player == index))
+        .collect()
+}
+
 /// The player character.
 pub fn player(
     player_assets: &PlayerAssets,
-    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    skin: PlayerSkin,
+    surface_config: &SurfaceConfig,
+    index: PlayerIndex,
 ) -> impl Bundle {
-    let run = AnimationData {
-        frames: 6,
-        interval: Duration::from_millis(80),
-        state: AnimationState::Walking,
-        atlas_index: 0,
-        repeat: Repeat::Loop,
-    };
-    let idle = AnimationData {
-        frames: 4,
-        interval: Duration::from_millis(150),
-        state: AnimationState::Idle,
-        atlas_index: 6,
-        repeat: Repeat::Loop,
-    };
-    let fall = AnimationData {
-        frames: 3,
-        interval: Duration::from_millis(150),
-        state: AnimationState::Falling,
-        atlas_index: 10,
-        repeat: Repeat::Loop,
-    };
-    let jump = AnimationData {
-        frames: 3,
-        interval: Duration::from_millis(150),
-        state: AnimationState::Jumping,
-        atlas_index: 13,
-        repeat: Repeat::Loop,
-    };
-    let death = AnimationData {
-        frames: 3,
-        interval: Duration::from_millis(80),
-        state: AnimationState::Dying,
-        atlas_index: 16,
-        repeat: Repeat::OneShot,
+    let definition = skin.definition();
+    let (skin, player_animation) = match definition.animation() {
+        Ok(animation) => (skin, animation),
+        Err(error) => {
+            error!(
+                "The {} skin doesn't fit its sprite sheet, using the default one: {error}",
+                definition.name
+            );
+            let skin = PlayerSkin::default();
+            (skin, Animation::new((skin.definition().animations)()))
+        }
     };
+    let definition = skin.definition();
 
-    // A texture atlas is a way to split a single image into a grid of related images.
-    // You can learn more in this example: https://github.com/bevyengine/bevy/blob/latest/examples/2d/texture_atlas.rs
-    let layout = TextureAtlasLayout::from_grid(UVec2::splat(16), 4, 5, None, None);
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
-    let player_animation = Animation::new(vec![run, idle, fall, jump, death]);
+    // Tint every player but the first, so they can be told apart.
+    let color = match index.0 {
+        0 => definition.tint,
+        _ => definition.tint.mix(&Color::srgb(0.6, 0.8, 1.0), 0.5),
+    };
 
     (
         Player,
+        index,
+        InputBindings::for_player(index),
         Name::new("Player"),
-        Sprite::from_atlas_image(
-            player_assets.ducky.clone(),
-            TextureAtlas {
-                layout: texture_atlas_layout,
-                index: player_animation.get_atlas_index(),
-            },
-        ),
+        Sprite {
+            color,
+            ..Sprite::from_atlas_image(
+                player_assets.skin_image(skin).clone(),
+                TextureAtlas {
+                    layout: player_assets.skin_layout(skin).clone(),
+                    index: player_animation.get_atlas_index(),
+                },
+            )
+        },
         player_animation,
         // The player character needs to be configured as a dynamic rigid body of the physics
         // engine.
@@ -142,111 +378,978 @@ pub fn player(
         // By locking the rotation we can prevent this.
         LockedAxes::ROTATION_LOCKED,
         CollisionEventsEnabled,
-        Friction::new(0.0),
+        GameLayer::Player.layers(),
+        // See `SurfaceConfig` for why players have no friction.
+        Friction::new(surface_config.player),
+        JustJumped::default(),
     )
 }
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
 #[reflect(Component)]
+#[require(
+    ForceZoneDrift,
+    ActivePowerups,
+    Look,
+    SquashStretch,
+    GravityFlip,
+    Interpolated,
+    Facing,
+    JumpPress,
+    MovementState,
+    DashInput,
+    TakeoffSpeed,
+    ZoneDamage,
+    ComboBuffer,
+    DrawLayer::Player
+)]
 pub struct Player;
 
+/// Which of the local players this is, starting at 0.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct PlayerIndex(pub usize);
+
+/// The keys a player is controlled with.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct InputBindings {
+    pub left: Vec<KeyCode>,
+    pub right: Vec<KeyCode>,
+    pub jump: Vec<KeyCode>,
+    /// Held to look up while standing still.
+    pub up: Vec<KeyCode>,
+    /// Held to look down while standing still, or to interact with things below the player,
+    /// like defusing mines.
+    pub down: Vec<KeyCode>,
+    /// Dashes the way the player is facing, see [`MovementState`].
+    pub dash: Vec<KeyCode>,
+    /// Held to run instead of walk, or the other way around with [`AutoRun`].
+    pub run: Vec<KeyCode>,
+}
+
+impl InputBindings {
+    pub fn for_player(index: PlayerIndex) -> Self {
+        match index.0 {
+            0 => Self {
+                left: vec![KeyCode::KeyR, KeyCode::KeyA],
+                right: vec![KeyCode::KeyT, KeyCode::KeyD],
+                jump: vec![KeyCode::Space],
+                up: vec![KeyCode::KeyW],
+                down: vec![KeyCode::KeyS],
+                dash: vec![KeyCode::KeyF],
+                run: vec![KeyCode::ShiftLeft],
+            },
+            _ => Self {
+                left: vec![KeyCode::ArrowLeft],
+                right: vec![KeyCode::ArrowRight],
+                // Up both jumps and looks up, see `apply_controls`.
+                jump: vec![KeyCode::ArrowUp],
+                up: vec![KeyCode::ArrowUp],
+                down: vec![KeyCode::ArrowDown],
+                dash: vec![KeyCode::ControlRight],
+                run: vec![KeyCode::ShiftRight],
+            },
+        }
+    }
+}
+
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
 #[reflect(Component)]
 pub struct Dead;
 
+/// What killed a player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum DeathCause {
+    #[default]
+    Unknown,
+    Spike,
+    Mine,
+    Enemy,
+    Projectile,
+    FallDamage,
+    DamageZone,
+}
+
+impl DeathCause {
+    /// What the death menu says about it.
+    pub fn message(self) -> &'static str {
+        match self {
+            DeathCause::Unknown => "You Died!",
+            DeathCause::Spike => "Impaled!",
+            DeathCause::Mine => "Blown up!",
+            DeathCause::Enemy => "Caught!",
+            DeathCause::Projectile => "Shot down!",
+            DeathCause::FallDamage => "Splat!",
+            DeathCause::DamageZone => "Burned up!",
+        }
+    }
+}
+
+/// What killed a player last.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct LastDeathCause(pub DeathCause);
+
+/// Kills a player, unless [`PracticeMode`] is on or they are [`Invulnerable`].
+///
+/// Every hazard kills through this, so whatever should happen on death only has to be added
+/// in [`on_kill_player`].
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct KillPlayer {
+    pub entity: Entity,
+    pub cause: DeathCause,
+}
+
+fn on_kill_player(
+    event: On<KillPlayer>,
+    mut commands: Commands,
+    mut next_menu: ResMut<NextState<Menu>>,
+    mut death_count: ResMut<DeathCount>,
+    mut last_death_cause: ResMut<LastDeathCause>,
+    respawn_mode: Res<RespawnMode>,
+    practice_mode: Res<PracticeMode>,
+    mut rewind_charges: ResMut<RewindCharges>,
+    mut ghost_recorder: ResMut<GhostRecorder>,
+    players: Query<
+        (&PlayerIndex, Has<Rewinding>, Has<Invulnerable>),
+        (With<Player>, Without<Dead>),
+    >,
+) {
+    let KillPlayer { entity, cause } = *event.event();
+    let Ok((index, rewinding, invulnerable)) = players.get(entity) else {
+        return;
+    };
+    if practice_mode.0 || rewinding || invulnerable {
+        return;
+    }
+
+    // Rewinding takes the path out of the recording, so only do it with a charge to spend.
+    if rewind_charges.available()
+        && let Some(path) = ghost_recorder.rewind(*index, REWIND_SECONDS)
+    {
+        rewind_charges.take();
+        commands.entity(entity).insert(Rewinding::new(path));
+        return;
+    }
+
+    if *respawn_mode == RespawnMode::Menu {
+        next_menu.set(Menu::Death);
+    }
+    death_count.0 += 1;
+    last_death_cause.0 = cause;
+    commands.entity(entity).insert(Dead).remove::<RigidBody>();
+}
+
+/// Sent to a player that takes damage without dying.
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct Hurt {
+    pub entity: Entity,
+}
+
+/// How long a [`Hurt`] player can't be controlled.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct StunDuration(pub Duration);
+
+impl Default for StunDuration {
+    fn default() -> Self {
+        Self(Duration::from_millis(400))
+    }
+}
+
+/// Keeps the player from reacting to input until the timer finishes.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct Stunned(pub Timer);
+
+fn on_player_hurt(
+    event: On<Hurt>,
+    mut commands: Commands,
+    stun_duration: Res<StunDuration>,
+    players: Query<(), (With<Player>, Without<Dead>)>,
+) {
+    let player = event.event().entity;
+    if players.contains(player) {
+        commands
+            .entity(player)
+            .insert(Stunned(Timer::new(stun_duration.0, TimerMode::Once)));
+    }
+}
+
+fn tick_stun(mut commands: Commands, time: Res<Time>, players: Query<(Entity, &mut Stunned)>) {
+    for (player, mut stunned) in players {
+        if stunned.0.tick(time.delta()).is_finished() {
+            commands.entity(player).remove::<Stunned>();
+        }
+    }
+}
+
+/// How long players can't be killed or damaged after spawning, so a hazard next to the spawn
+/// point can't kill them again right away.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct SpawnProtection(pub Duration);
+
+impl Default for SpawnProtection {
+    fn default() -> Self {
+        Self(Duration::from_millis(1500))
+    }
+}
+
+/// Keeps the player from being killed or damaged until the timer finishes. They blink
+/// meanwhile.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct Invulnerable(pub Timer);
+
+impl Invulnerable {
+    pub fn new(duration: Duration) -> Self {
+        Self(Timer::new(duration, TimerMode::Once))
+    }
+}
+
+/// How long each half of a blink takes.
+const BLINK_INTERVAL: f32 = 0.1;
+
+/// How see-through an [`Invulnerable`] player gets while blinking.
+const BLINK_ALPHA: f32 = 0.4;
+
+fn tick_invulnerability(
+    mut commands: Commands,
+    time: Res<Time>,
+    players: Query<(Entity, &mut Invulnerable)>,
+) {
+    for (player, mut invulnerable) in players {
+        if invulnerable.0.tick(time.delta()).is_finished() {
+            commands.entity(player).remove::<Invulnerable>();
+        }
+    }
+}
+
+/// With [`ReducedMotion`], the player stays see-through instead of blinking.
+fn blink_invulnerable(
+    reduced_motion: Res<ReducedMotion>,
+    players: Query<(&Invulnerable, &mut Sprite)>,
+) {
+    for (invulnerable, mut sprite) in players {
+        let blink = (invulnerable.0.elapsed_secs() / BLINK_INTERVAL) as u32 % 2 == 0;
+        let alpha = if blink || reduced_motion.0 {
+            BLINK_ALPHA
+        } else {
+            1.0
+        };
+        sprite.color.set_alpha(alpha);
+    }
+}
+
+fn end_invulnerability(remove: On<Remove, Invulnerable>, mut players: Query<&mut Sprite>) {
+    // This also runs when the player is despawned along with the level.
+    if let Ok(mut sprite) = players.get_mut(remove.entity) {
+        sprite.color.set_alpha(1.0);
+    }
+}
+
+/// Whether players grow in when they spawn, or appear at once.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub enum RespawnTransition {
+    /// Grow in with a sound, and only take control once that's done.
+    #[default]
+    Animated,
+    /// Appear and take control right away.
+    Instant,
+}
+
+fn load_respawn_transition(
+    save_data: Res<SaveData>,
+    mut respawn_transition: ResMut<RespawnTransition>,
+) {
+    *respawn_transition = save_data.respawn_transition;
+}
+
+fn save_respawn_transition(
+    respawn_transition: Res<RespawnTransition>,
+    mut save_data: ResMut<SaveData>,
+) {
+    if save_data.respawn_transition != *respawn_transition {
+        save_data.respawn_transition = *respawn_transition;
+    }
+}
+
+/// How long the respawn transition takes.
+const RESPAWN_DURATION: Duration = Duration::from_millis(400);
+
+/// Grows a freshly spawned player in. They ignore the controls until the timer finishes.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct Respawning(pub Timer);
+
+impl Default for Respawning {
+    fn default() -> Self {
+        Self(Timer::new(RESPAWN_DURATION, TimerMode::Once))
+    }
+}
+
+fn play_respawn_sound(
+    commands: &mut Commands,
+    player_assets: &PlayerAssets,
+    respawn_transition: RespawnTransition,
+) {
+    if respawn_transition == RespawnTransition::Animated {
+        commands.spawn((
+            Name::new("Respawn Sound"),
+            sound_effect(player_assets.respawn.clone()),
+        ));
+    }
+}
+
+fn tick_respawning(
+    mut commands: Commands,
+    time: Res<Time>,
+    players: Query<(Entity, &mut Respawning)>,
+) {
+    for (player, mut respawning) in players {
+        if respawning.0.tick(time.delta()).is_finished() {
+            commands.entity(player).remove::<Respawning>();
+        }
+    }
+}
+
+/// Scales the sprite on top of [`squash_and_stretch`], which sets its size every frame.
+/// With [`ReducedMotion`] the sprite keeps its size.
+fn grow_respawning(reduced_motion: Res<ReducedMotion>, players: Query<(&Respawning, &mut Sprite)>) {
+    if reduced_motion.0 {
+        return;
+    }
+    for (respawning, mut sprite) in players {
+        let scale = EaseFunction::BackOut.sample_clamped(respawning.0.fraction());
+        if let Some(size) = sprite.custom_size {
+            sprite.custom_size = Some(size * scale);
+        }
+    }
+}
+
+/// How many times the player has died since leaving the title screen.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct DeathCount(pub u32);
+
+fn reset_death_count(mut death_count: ResMut<DeathCount>) {
+    death_count.0 = 0;
+}
+
+/// What happens after the player dies.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub enum RespawnMode {
+    /// Show the death menu and wait for the player to restart.
+    #[default]
+    Menu,
+    /// Respawn at the spawn point as soon as the death animation is over.
+    Instant,
+}
+
+/// Whether holding the jump button keeps jumping.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub enum JumpTrigger {
+    /// Jump again on every landing while the button is held.
+    #[default]
+    Hold,
+    /// Every jump takes a fresh press. A press shortly before landing is still buffered.
+    Tap,
+}
+
+fn load_jump_trigger(save_data: Res<SaveData>, mut jump_trigger: ResMut<JumpTrigger>) {
+    *jump_trigger = save_data.jump_trigger;
+}
+
+fn save_jump_trigger(jump_trigger: Res<JumpTrigger>, mut save_data: ResMut<SaveData>) {
+    if save_data.jump_trigger != *jump_trigger {
+        save_data.jump_trigger = *jump_trigger;
+    }
+}
+
+/// Whether the players run without holding the run button. Holding it walks instead then.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct AutoRun(pub bool);
+
+fn load_auto_run(save_data: Res<SaveData>, mut auto_run: ResMut<AutoRun>) {
+    auto_run.0 = save_data.auto_run;
+}
+
+fn save_auto_run(auto_run: Res<AutoRun>, mut save_data: ResMut<SaveData>) {
+    if save_data.auto_run != auto_run.0 {
+        save_data.auto_run = auto_run.0;
+    }
+}
+
+/// Makes hazards harmless, for practicing a level without dying.
+/// They still go off, so it's clear where a run would have ended.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PracticeMode(pub bool);
+
+/// How the player moves. These are fed into Tnua's walk basis and jump action every frame, so
+/// changing them (e.g. through an inspector) takes effect immediately.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct MovementConfig {
+    /// Top walking speed.
+    pub walk_speed: f32,
+    /// Top running speed, while the run button is held or with [`AutoRun`].
+    pub run_speed: f32,
+    /// How fast the player reaches their top speed on the ground.
+    pub acceleration: f32,
+    /// How much the player can steer in the air.
+    pub air_acceleration: f32,
+    /// The horizontal speed steering in the air can't push the player past. Running raises it
+    /// as much as it raises the top speed, so a running jump keeps its speed.
+    pub max_air_speed: f32,
+    /// Scales [`Self::air_acceleration`] while a jump is still rising, to keep more of the
+    /// jump's momentum. `1.0` steers as freely as in the rest of the air.
+    pub jump_steering: f32,
+    /// Whether letting go of the controls in the air keeps the current horizontal speed,
+    /// instead of slowing down to a stop.
+    pub preserve_air_momentum: bool,
+    /// Must be greater (even if by little) than the distance between the character's center
+    /// and the lowest point of its collider.
+    pub float_height: f32,
+    /// How high a full jump goes.
+    pub jump_height: f32,
+    /// The fastest the player can fall. Rising, e.g. while jumping, isn't limited.
+    pub max_fall_speed: f32,
+    /// How long after walking off a ledge a jump still works, in seconds.
+    pub coyote_time: f32,
+    /// How long before landing a jump press is remembered, in seconds.
+    pub jump_buffer_time: f32,
+    /// How far a dash goes.
+    pub dash_distance: f32,
+    /// How fast a dash goes.
+    pub dash_speed: f32,
+    /// How much of a dash's extra speed a jump out of it keeps, from `0.0` for none, which
+    /// jumps at walking or running speed, to `1.0` for all of it.
+    pub dash_momentum: f32,
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        Self {
+            walk_speed: 120.0,
+            run_speed: 180.0,
+            acceleration: 800.0,
+            air_acceleration: 400.0,
+            max_air_speed: 120.0,
+            jump_steering: 1.0,
+            preserve_air_momentum: true,
+            float_height: 1.5,
+            jump_height: 35.0,
+            max_fall_speed: 300.0,
+            coyote_time: 0.15,
+            jump_buffer_time: 0.2,
+            dash_distance: 48.0,
+            dash_speed: 320.0,
+            dash_momentum: 0.5,
+        }
+    }
+}
+
+/// Where the player is looking. Only affects standing still, see
+/// [`AnimationState::LookUp`](crate::game::animation::AnimationState::LookUp).
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub enum Look {
+    #[default]
+    Ahead,
+    Up,
+    Down,
+}
+
+/// Tracks a press of the jump button for [`JumpTrigger::Tap`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct JumpPress {
+    held: bool,
+    /// Whether the press still asks for a jump.
+    armed: bool,
+    /// Whether the press already led to a jump.
+    jumped: bool,
+}
+
+impl JumpPress {
+    /// Whether the jump action should be fed this tick. A press keeps feeding it until the
+    /// jump it started is over, so holding still makes for a higher jump. Before that, it's
+    /// fed while airborne too, which lets the jump buffer remember it until landing.
+    ///
+    /// `starting` is whether a jump is just taking off, and `jumping` whether one is going on
+    /// at all, which may still be the fall of an earlier one.
+    fn update(&mut self, held: bool, starting: bool, jumping: bool) -> bool {
+        if !held {
+            *self = Self::default();
+            return false;
+        }
+        if !self.held {
+            self.held = true;
+            self.armed = true;
+        }
+        if self.armed && starting {
+            self.jumped = true;
+        } else if self.jumped && !jumping {
+            self.armed = false;
+        }
+        self.armed
+    }
+}
+
+/// Whether the player's last jump already played its sound.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct JustJumped(bool);
+
+/// The top speed the player left the ground with, walking or running. It holds until they
+/// land, so letting go of the run button mid-jump doesn't cut a running jump short.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+pub struct TakeoffSpeed(Option<f32>);
+
 fn apply_controls(
-    mut just_jumped: Local<bool>,
     mut commands: Commands,
     player_assets: If<Res<PlayerAssets>>,
     keyboard: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&mut TnuaController, &mut Sprite)>,
+    movement: Res<MovementConfig>,
+    jump_trigger: Res<JumpTrigger>,
+    auto_run: Res<AutoRun>,
+    assist_mode: Res<AssistMode>,
+    touch_actions: Res<TouchActions>,
+    surface_config: Res<SurfaceConfig>,
+    surfaces: Query<&Surface>,
+    mut game_rng: ResMut<GameRng>,
+    // Dying players keep their controller until they are despawned, but shouldn't react to
+    // input anymore. This also keeps a respawned player controllable while the old one dies.
+    query: Query<
+        (
+            &PlayerIndex,
+            &InputBindings,
+            &mut TnuaController,
+            &mut MovementState,
+            &mut DashInput,
+            &mut Facing,
+            &mut JustJumped,
+            &mut JumpPress,
+            &mut Look,
+            &mut TakeoffSpeed,
+            &ForceZoneDrift,
+            &ActivePowerups,
+            &LinearVelocity,
+            &GravityFlip,
+            Has<Stunned>,
+            Has<Respawning>,
+            Has<GroundPounding>,
+        ),
+        Without<Dead>,
+    >,
 ) {
-    let Ok((mut controller, mut sprite)) = query.single_mut() else {
-        return;
-    };
+    for (
+        index,
+        bindings,
+        mut controller,
+        mut movement_state,
+        mut dash_input,
+        mut facing,
+        mut just_jumped,
+        mut jump_press,
+        mut look,
+        mut takeoff_speed,
+        drift,
+        powerups,
+        velocity,
+        gravity_flip,
+        stunned,
+        respawning,
+        pounding,
+    ) in query
+    {
+        let mut direction = Vec3::ZERO;
 
-    let mut direction = Vec3::ZERO;
-
-    if keyboard.pressed(KeyCode::KeyR) || keyboard.pressed(KeyCode::KeyA) {
-        direction -= Vec3::X;
-    }
-    if keyboard.pressed(KeyCode::KeyT) || keyboard.pressed(KeyCode::KeyD) {
-        direction += Vec3::X;
-    }
-
-    if direction.x != 0.0 {
-        sprite.flip_x = direction.x < 0.0;
-    }
-
-    // Feed the basis every frame. Even if the player doesn't move - just use `desired_velocity:
-    // Vec3::ZERO`. `TnuaController` starts without a basis, which will make the character collider
-    // just fall.
-    controller.basis(TnuaBuiltinWalk {
-        // The `desired_velocity` determines how the character will move.
-        desired_velocity: direction.normalize_or_zero() * 120.0,
-        acceleration: 800.0,
-        air_acceleration: 400.0,
-        // The `float_height` must be greater (even if by little) from the distance between the
-        // character's center and the lowest point of its collider.
-        float_height: 1.5,
-        // `TnuaBuiltinWalk` has many other fields for customizing the movement - but they have
-        // sensible defaults. Refer to the `TnuaBuiltinWalk`'s documentation to learn what they do.
-        ..Default::default()
-    });
-
-    // Feed the jump action every frame as long as the player holds the jump button. If the player
-    // stops holding the jump button, simply stop feeding the action.
-    if keyboard.pressed(KeyCode::Space) {
-        controller.action(TnuaBuiltinJump {
-            // The height is the only mandatory field of the jump button.
-            height: 35.0,
-            // `TnuaBuiltinJump` also has customization fields with sensible defaults.
+        // The touch buttons control the first player.
+        let touch = match index.0 {
+            0 => *touch_actions,
+            _ => TouchActions::default(),
+        };
+        // Stunned, respawning and ground pounding players still get a basis, they just ignore
+        // the controls.
+        let pressed = |keys: &[KeyCode], touched: bool| {
+            !stunned
+                && !respawning
+                && !pounding
+                && (touched || keyboard.any_pressed(keys.iter().copied()))
+        };
+
+        if pressed(&bindings.left, touch.left) {
+            direction -= Vec3::X;
+        }
+        if pressed(&bindings.right, touch.right) {
+            direction += Vec3::X;
+        }
+
+        if direction.x != 0.0 {
+            *facing = if direction.x < 0.0 {
+                Facing::Left
+            } else {
+                Facing::Right
+            };
+        }
+
+        // A key that also jumps only looks up once the player has landed and keeps holding it,
+        // so every jump doesn't look up along the way.
+        let up_jumps = bindings.up.iter().any(|key| bindings.jump.contains(key));
+        let looking_up = keyboard.any_pressed(bindings.up.iter().copied())
+            && !(up_jumps && controller.is_airborne().unwrap_or(true));
+        let looking_down = keyboard.any_pressed(bindings.down.iter().copied());
+        *look = if stunned || direction.x != 0.0 || looking_up == looking_down {
+            Look::Ahead
+        } else if looking_up {
+            Look::Up
+        } else {
+            Look::Down
+        };
+
+        let holding_jump = pressed(&bindings.jump, touch.jump);
+        let jump_state = controller
+            .concrete_action::<TnuaBuiltinJump>()
+            .map(|(_, state)| state);
+        let starting = matches!(jump_state, Some(TnuaBuiltinJumpState::StartingJump { .. }));
+        let feed_jump = match *jump_trigger {
+            JumpTrigger::Hold => holding_jump,
+            JumpTrigger::Tap => jump_press.update(holding_jump, starting, jump_state.is_some()),
+        };
+
+        let running = auto_run.0 != keyboard.any_pressed(bindings.run.iter().copied());
+        let airborne = controller.is_airborne().unwrap_or(true);
+        let ground_speed = if running {
+            movement.run_speed
+        } else {
+            movement.walk_speed
+        };
+        let top_speed = if airborne {
+            *takeoff_speed.0.get_or_insert(ground_speed)
+        } else {
+            takeoff_speed.set_if_neq(TakeoffSpeed(None));
+            ground_speed
+        };
+        let speed = top_speed * powerups.speed_multiplier();
+        let facing_direction = match *facing {
+            Facing::Left => -1.0,
+            Facing::Right => 1.0,
+        };
+        let dashing = matches!(
+            controller.concrete_action::<TnuaBuiltinDash>(),
+            Some((
+                _,
+                TnuaBuiltinDashState::PreDash | TnuaBuiltinDashState::During { .. }
+            ))
+        );
+        *movement_state = movement_state.next(
+            &MovementInput {
+                dash: dash_input.take(pressed(&bindings.dash, false), facing_direction),
+                dashing,
+                jump: feed_jump,
+                jumping: jump_state.is_some(),
+                airborne,
+                direction: direction.x,
+                speed,
+            },
+            &movement,
+        );
+
+        let mut desired_velocity = direction.normalize_or_zero() * speed;
+        let mut air_acceleration = movement.air_acceleration;
+        if airborne {
+            if direction.x == 0.0 && movement.preserve_air_momentum {
+                // Force zones are added back on below, so they shouldn't count as momentum.
+                desired_velocity = Vec3::X * (velocity.x - drift.0);
+            }
+            let max_air_speed = movement.max_air_speed * top_speed / movement.walk_speed;
+            desired_velocity =
+                desired_velocity.clamp_length_max(max_air_speed * powerups.speed_multiplier());
+
+            let rising = matches!(
+                controller.concrete_action::<TnuaBuiltinJump>(),
+                Some((
+                    _,
+                    TnuaBuiltinJumpState::StartingJump { .. }
+                        | TnuaBuiltinJumpState::MaintainingJump { .. }
+                ))
+            );
+            if rising {
+                air_acceleration *= movement.jump_steering;
+            }
+        }
+        // A jump out of a dash carries its momentum past the usual air speed.
+        if let MovementState::DashJumping { velocity } = *movement_state {
+            desired_velocity = Vec3::X * velocity;
+        }
+
+        // Ice takes over once the player lands on it, and lets go as soon as they leave it.
+        let surface = controller
+            .concrete_basis::<TnuaBuiltinWalk>()
+            .and_then(|(_, state)| state.standing_on_entity())
+            .and_then(|entity| surfaces.get(entity).ok())
+            .copied()
+            .unwrap_or_default();
+
+        // Feed the basis every frame. Even if the player doesn't move - just use `desired_velocity:
+        // Vec3::ZERO`. `TnuaController` starts without a basis, which will make the character collider
+        // just fall.
+        controller.basis(TnuaBuiltinWalk {
+            // The `desired_velocity` determines how the character will move.
+            desired_velocity: desired_velocity + Vec3::X * drift.0,
+            acceleration: movement.acceleration * surface_config.acceleration(surface),
+            air_acceleration,
+            // The `float_height` must be greater (even if by little) from the distance between the
+            // character's center and the lowest point of its collider.
+            float_height: movement.float_height,
+            coyote_time: movement.coyote_time * assist_mode.timing_windows(),
+            // Players walk on the ceiling while their gravity is flipped.
+            up: gravity_flip.up(),
+            // `TnuaBuiltinWalk` has many other fields for customizing the movement - but they have
+            // sensible defaults. Refer to the `TnuaBuiltinWalk`'s documentation to learn what they do.
             ..Default::default()
         });
-        if !controller.is_airborne().unwrap_or(true) {
-            if !*just_jumped {
-                let rng = &mut rand::rng();
-                let random_step = player_assets.jumps.choose(rng).unwrap().clone();
-                commands.spawn((Name::new("Walking Sound"), sound_effect(random_step)));
-                *just_jumped = true;
+
+        // The dash is fed for as long as it goes on. A jump cancels it, see `MovementState`.
+        if let MovementState::Dashing {
+            direction: dash_direction,
+        } = *movement_state
+        {
+            controller.action(TnuaBuiltinDash {
+                displacement: Vec3::X * dash_direction * movement.dash_distance,
+                speed: movement.dash_speed,
+                ..Default::default()
+            });
+        }
+
+        // Feed the jump action every frame as long as the player holds the jump button. If the player
+        // stops holding the jump button, simply stop feeding the action.
+        if feed_jump && !matches!(*movement_state, MovementState::Dashing { .. }) {
+            controller.action(TnuaBuiltinJump {
+                // The height is the only mandatory field of the jump button.
+                height: movement.jump_height * powerups.jump_multiplier(),
+                input_buffer_time: movement.jump_buffer_time * assist_mode.timing_windows(),
+                // `TnuaBuiltinJump` also has customization fields with sensible defaults.
+                ..Default::default()
+            });
+            if !airborne {
+                if !just_jumped.0 {
+                    let random_step = player_assets.jumps.choose(&mut *game_rng).unwrap().clone();
+                    commands.spawn((
+                        Name::new("Walking Sound"),
+                        sound_effect(random_step),
+                        SoundKind::Footstep,
+                    ));
+                    just_jumped.0 = true;
+                }
+            } else {
+                just_jumped.0 = false;
             }
-        } else {
-            *just_jumped = false;
         }
     }
 }
 
-pub fn despawn_player(mut commands: Commands, explosions: Query<(Entity, &Animation), With<Dead>>) {
+/// A ground pound falls faster on purpose.
+fn limit_fall_speed(
+    movement: Res<MovementConfig>,
+    players: Query<
+        (&mut LinearVelocity, &GravityFlip),
+        (With<Player>, Without<Dead>, Without<GroundPounding>),
+    >,
+) {
+    for (mut velocity, gravity_flip) in players {
+        let sign = gravity_flip.sign();
+        velocity.y = (velocity.y * sign).max(-movement.max_fall_speed) * sign;
+    }
+}
+
+pub fn despawn_player(
+    mut commands: Commands,
+    respawn_mode: Res<RespawnMode>,
+    explosions: Query<(Entity, &Animation), With<Dead>>,
+) {
     for (entity, animation) in explosions {
         if animation.is_finished() {
             commands.entity(entity).despawn();
+
+            if *respawn_mode == RespawnMode::Instant {
+                commands.trigger(ResetLevel);
+                commands.trigger(RespawnPlayers);
+            }
+        }
+    }
+}
+
+/// A player landed on something.
+///
+/// Triggered on the tick a player starts standing on something new, whether that's after
+/// falling or after stepping over from other ground.
+///
+/// Observe this to react to landings, and inspect `ground_entity` to find out what was landed on.
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct PlayerLanded {
+    pub entity: Entity,
+    pub ground_entity: Entity,
+    /// What the player stood on before, or `None` if they landed from the air.
+    pub previous_ground: Option<Entity>,
+    /// The player's velocity on the last tick before landing.
+    pub impact_velocity: Vec2,
+    /// How far below the highest point of their time in the air the player landed.
+    pub fall_height: f32,
+}
+
+/// What a player stood on during the last tick, and how they moved since leaving the ground.
+#[derive(Debug, Clone, Copy)]
+struct GroundContact {
+    ground: Option<Entity>,
+    peak: f32,
+    velocity: Vec2,
+}
+
+fn detect_landings(
+    mut commands: Commands,
+    mut contacts: Local<EntityHashMap<GroundContact>>,
+    players: Query<
+        (
+            Entity,
+            &TnuaController,
+            &GlobalTransform,
+            &LinearVelocity,
+            &GravityFlip,
+        ),
+        (With<Player>, Without<Dead>),
+    >,
+) {
+    contacts.retain(|player, _| players.contains(*player));
+
+    for (player, controller, transform, velocity, gravity_flip) in &players {
+        // Measured against gravity, so falling towards the ceiling counts too.
+        let height = transform.translation().y * gravity_flip.sign();
+        let ground = controller
+            .concrete_basis::<TnuaBuiltinWalk>()
+            .and_then(|(_, state)| state.standing_on_entity());
+        let contact = contacts.entry(player).or_insert(GroundContact {
+            ground,
+            peak: height,
+            velocity: velocity.0,
+        });
+
+        if let Some(ground_entity) = ground
+            && ground != contact.ground
+        {
+            commands.trigger(PlayerLanded {
+                entity: player,
+                ground_entity,
+                previous_ground: contact.ground,
+                impact_velocity: contact.velocity,
+                fall_height: (contact.peak - height).max(0.0),
+            });
         }
+
+        contact.peak = if ground.is_some() {
+            height
+        } else {
+            contact.peak.max(height)
+        };
+        contact.ground = ground;
+        contact.velocity = velocity.0;
+    }
+}
+
+/// Kicks up some dust when a player lands.
+fn spawn_landing_dust(
+    event: On<PlayerLanded>,
+    mut commands: Commands,
+    transforms: Query<&GlobalTransform>,
+) {
+    if event.previous_ground.is_some() {
+        return;
+    }
+    let Ok(transform) = transforms.get(event.entity) else {
+        return;
+    };
+    commands.spawn((
+        Name::new("Landing Dust"),
+        ParticleBurst {
+            speed_range: 5.0..20.0,
+            gravity: 0.0,
+            ..ParticleBurst::colored(Color::srgba(0.8, 0.8, 0.8, 0.6), 6)
+        },
+        Transform::from_translation(transform.translation() - Vec3::Y * 4.0),
+    ));
+}
+
+fn spawn_death_particles(
+    mut commands: Commands,
+    players: Query<&GlobalTransform, (With<Player>, Added<Dead>)>,
+) {
+    for transform in players {
+        commands.spawn((
+            Name::new("Death Particles"),
+            ParticleBurst::colored(Color::srgb(0.9, 0.9, 0.3), 16),
+            Transform::from_translation(transform.translation()),
+        ));
     }
 }
 
 #[derive(Resource, Asset, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct PlayerAssets {
+    /// The sprite sheet of each of the [`SKINS`], in the same order.
     #[dependency]
-    ducky: Handle<Image>,
+    pub skins: Vec<Handle<Image>>,
+    /// The texture atlas layout of each of the [`SKINS`], shared by every player wearing it, so
+    /// a rebuilt layout reaches the players spawned later as well.
+    pub skin_layouts: Vec<Handle<TextureAtlasLayout>>,
     #[dependency]
     pub jumps: Vec<Handle<AudioSource>>,
     #[dependency]
     pub death: Handle<AudioSource>,
+    #[dependency]
+    pub respawn: Handle<AudioSource>,
+}
+
+impl PlayerAssets {
+    pub fn skin_image(&self, skin: PlayerSkin) -> &Handle<Image> {
+        self.skins.get(skin.0).unwrap_or(&self.skins[0])
+    }
+
+    pub fn skin_layout(&self, skin: PlayerSkin) -> &Handle<TextureAtlasLayout> {
+        self.skin_layouts
+            .get(skin.0)
+            .unwrap_or(&self.skin_layouts[0])
+    }
 }
 
 impl FromWorld for PlayerAssets {
     fn from_world(world: &mut World) -> Self {
+        // A texture atlas is a way to split a single image into a grid of related images.
+        // You can learn more in this example: https://github.com/bevyengine/bevy/blob/latest/examples/2d/texture_atlas.rs
+        let mut texture_atlas_layouts = world.resource_mut::<Assets<TextureAtlasLayout>>();
+        let skin_layouts = SKINS
+            .iter()
+            .map(|skin| texture_atlas_layouts.add(skin.atlas_layout()))
+            .collect();
         let assets = world.resource::<AssetServer>();
         Self {
-            ducky: assets.load_with_settings(
-                "images/hero.png",
-                |settings: &mut ImageLoaderSettings| {
-                    settings.sampler = ImageSampler::nearest();
-                },
-            ),
+            skins: SKINS
+                .iter()
+                .map(|skin| load_pixel_image(assets, skin.image))
+                .collect(),
+            skin_layouts,
             jumps: vec![assets.load("audio/sound_effects/jump.ogg")],
             death: assets.load("audio/sound_effects/death.ogg"),
+            // There's no sound of its own for it yet.
+            respawn: assets.load("audio/sound_effects/button_click.ogg"),
         }
     }
 }
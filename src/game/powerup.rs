@@ -0,0 +1,239 @@
+//! Pickups that temporarily boost the player's movement.
+//!
+//! In LDtk these are `Powerup` entities with an enum field `Kind` (`Speed` or `HighJump`)
+//! and an optional float field `Duration` in seconds.
+
+use std::time::Duration;
+
+use avian2d::prelude::{CollisionStart, Sensor};
+use bevy::{
+    ecs::{lifecycle::HookContext, world::DeferredWorld},
+    prelude::*,
+};
+use bevy_ecs_ldtk::{EntityInstance, LdtkEntity, app::LdtkEntityAppExt, prelude::LdtkFields};
+
+use crate::{
+    AppSystems, PausableSystems,
+    asset_tracking::LoadResource,
+    audio::{SoundKind, sound_effect},
+    game::{colliders::ColliderBundle, draw_order::DrawLayer, player::Player},
+    screens::Screen,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.load_resource::<PowerupAssets>();
+    app.register_ldtk_entity::<PowerupBundle>("Powerup");
+    app.init_resource::<PowerupStacking>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_powerup_hud);
+    app.add_systems(
+        Update,
+        (
+            tick_active_powerups
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+            update_powerup_hud.in_set(AppSystems::Update),
+        ),
+    );
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum PowerupKind {
+    #[default]
+    Speed,
+    HighJump,
+}
+
+impl PowerupKind {
+    fn name(self) -> &'static str {
+        match self {
+            PowerupKind::Speed => "Speed",
+            PowerupKind::HighJump => "High Jump",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+#[require(DrawLayer::Background)]
+#[component(on_add = on_powerup_add)]
+pub struct Powerup {
+    pub kind: PowerupKind,
+    pub duration: Duration,
+}
+
+pub fn on_powerup_add(mut world: DeferredWorld, context: HookContext) {
+    let powerup_entity = context.entity;
+    world
+        .commands()
+        .entity(powerup_entity)
+        .observe(on_player_touched_powerup);
+}
+
+#[derive(Clone, Debug, Default, Bundle, LdtkEntity)]
+pub struct PowerupBundle {
+    #[with(powerup_from_instance)]
+    powerup: Powerup,
+
+    #[sprite_sheet]
+    sprite_sheet: Sprite,
+
+    #[from_entity_instance]
+    collider_bundle: ColliderBundle,
+
+    sensor: Sensor,
+}
+
+fn powerup_from_instance(instance: &EntityInstance) -> Powerup {
+    let kind = match instance.get_enum_field("Kind").map(|s| s.as_str()) {
+        Ok("HighJump") => PowerupKind::HighJump,
+        _ => PowerupKind::Speed,
+    };
+    let duration = instance.get_float_field("Duration").copied().unwrap_or(5.0);
+    Powerup {
+        kind,
+        duration: Duration::from_secs_f32(duration),
+    }
+}
+
+/// What picking up a powerup that is already active does.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub enum PowerupStacking {
+    /// Restart the effect with the new duration.
+    #[default]
+    Refresh,
+    /// Add the new duration to the time that is left.
+    Stack,
+}
+
+/// The powerups currently affecting a player.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct ActivePowerups(Vec<(PowerupKind, Timer)>);
+
+impl ActivePowerups {
+    fn is_active(&self, kind: PowerupKind) -> bool {
+        self.0.iter().any(|(active, _)| *active == kind)
+    }
+
+    /// Multiplier for the walking speed.
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.is_active(PowerupKind::Speed) {
+            1.6
+        } else {
+            1.0
+        }
+    }
+
+    /// Multiplier for the jump height.
+    pub fn jump_multiplier(&self) -> f32 {
+        if self.is_active(PowerupKind::HighJump) {
+            2.0
+        } else {
+            1.0
+        }
+    }
+
+    fn add(&mut self, powerup: &Powerup, stacking: PowerupStacking) {
+        match self.0.iter_mut().find(|(kind, _)| *kind == powerup.kind) {
+            Some((_, timer)) => {
+                let duration = match stacking {
+                    PowerupStacking::Refresh => powerup.duration,
+                    PowerupStacking::Stack => timer.remaining() + powerup.duration,
+                };
+                *timer = Timer::new(duration, TimerMode::Once);
+            }
+            None => self
+                .0
+                .push((powerup.kind, Timer::new(powerup.duration, TimerMode::Once))),
+        }
+    }
+}
+
+fn on_player_touched_powerup(
+    event: On<CollisionStart>,
+    mut commands: Commands,
+    powerup_assets: Res<PowerupAssets>,
+    stacking: Res<PowerupStacking>,
+    powerup_query: Query<&Powerup>,
+    mut player_query: Query<&mut ActivePowerups, With<Player>>,
+) {
+    // `colider1` and `body1` refer to the event target and its body.
+    // `collider2` and `body2` refer to the other collider and its body.
+    let powerup_entity = event.collider1;
+    let other_entity = event.collider2;
+
+    let (Ok(powerup), Ok(mut active_powerups)) = (
+        powerup_query.get(powerup_entity),
+        player_query.get_mut(other_entity),
+    ) else {
+        return;
+    };
+
+    active_powerups.add(powerup, *stacking);
+    commands.entity(powerup_entity).despawn();
+    commands.spawn((
+        Name::new("Pickup Sound"),
+        sound_effect(powerup_assets.pickup.clone()),
+        SoundKind::Pickup,
+    ));
+}
+
+fn tick_active_powerups(time: Res<Time>, mut query: Query<&mut ActivePowerups>) {
+    for mut active_powerups in &mut query {
+        for (_, timer) in &mut active_powerups.0 {
+            timer.tick(time.delta());
+        }
+        active_powerups.0.retain(|(_, timer)| !timer.is_finished());
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct PowerupHud;
+
+fn spawn_powerup_hud(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Powerup HUD"),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: px(10),
+            left: px(10),
+            ..default()
+        },
+        DespawnOnExit(Screen::Gameplay),
+        Pickable::IGNORE,
+        children![(widget::label(""), PowerupHud)],
+    ));
+}
+
+fn update_powerup_hud(
+    players: Query<&ActivePowerups, With<Player>>,
+    mut label: Single<&mut Text, With<PowerupHud>>,
+) {
+    label.0 = players
+        .iter()
+        .flat_map(|active_powerups| &active_powerups.0)
+        .map(|(kind, timer)| format!("{} {:.1}s", kind.name(), timer.remaining_secs()))
+        .collect::<Vec<_>>()
+        .join("\n");
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct PowerupAssets {
+    #[dependency]
+    pub pickup: Handle<AudioSource>,
+}
+
+impl FromWorld for PowerupAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            // There is no pickup sound of its own yet, so it borrows the button click.
+            pickup: assets.load("audio/sound_effects/button_click.ogg"),
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! An optional second chance: instead of dying, the player is rewound a couple of seconds
+//! along the path the ghost recorder took down.
+//!
+//! Every level comes with a few [`RewindCharges`]. Once they're used up, hazards kill as usual.
+//! Only the recorded player can be rewound, since there's no path for the others.
+//! Whether it's enabled is stored in the [`SaveData`].
+
+use std::time::Duration;
+
+use avian2d::prelude::{ColliderDisabled, LinearVelocity, Position, RigidBodyDisabled};
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, Pause,
+    game::{
+        level::LevelChanged,
+        player::{Dead, Player},
+    },
+    gameplay_frozen,
+    save::SaveData,
+    screens::Screen,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<RewindCharges>();
+    app.add_systems(Startup, load_rewind);
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        (refill_charges, spawn_rewind_hud),
+    );
+    app.add_observer(refill_charges_on_level_change);
+    app.add_observer(start_rewind);
+    app.add_observer(finish_rewind);
+    app.add_systems(
+        FixedUpdate,
+        play_rewind.run_if(
+            in_state(Screen::Gameplay)
+                .and(in_state(Pause(false)))
+                .and(not(gameplay_frozen)),
+        ),
+    );
+    app.add_systems(
+        Update,
+        (
+            save_rewind.run_if(resource_changed::<RewindCharges>),
+            update_rewind_hud
+                .in_set(AppSystems::Update)
+                .run_if(in_state(Screen::Gameplay)),
+        ),
+    );
+}
+
+/// How far back a rewind goes.
+pub const REWIND_SECONDS: f32 = 2.0;
+
+/// How long the rewind takes to play back.
+const PLAYBACK_DURATION: Duration = Duration::from_millis(600);
+
+/// The tint of a player while they are being rewound.
+const REWIND_TINT: Color = Color::srgba(0.5, 0.7, 1.0, 0.7);
+
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct RewindCharges {
+    pub enabled: bool,
+    /// How many rewinds every level starts with.
+    pub per_level: u32,
+    /// How many rewinds are left in the current level.
+    pub left: u32,
+}
+
+impl Default for RewindCharges {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            per_level: 3,
+            left: 3,
+        }
+    }
+}
+
+impl RewindCharges {
+    /// Whether there's a charge left to use.
+    pub fn available(&self) -> bool {
+        self.enabled && self.left > 0
+    }
+
+    /// Uses up a charge, if there is one.
+    pub fn take(&mut self) -> bool {
+        if !self.available() {
+            return false;
+        }
+        self.left -= 1;
+        true
+    }
+}
+
+/// Moves a player back along `path` instead of letting them die. They can't collide with
+/// anything until it's done.
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Rewinding {
+    /// The positions to pass through, starting with the most recent one.
+    path: Vec<Vec2>,
+    timer: Timer,
+    /// The player's own tint, to go back to afterwards.
+    color: Color,
+}
+
+impl Rewinding {
+    pub fn new(path: Vec<Vec2>) -> Self {
+        Self {
+            path,
+            timer: Timer::new(PLAYBACK_DURATION, TimerMode::Once),
+            color: Color::WHITE,
+        }
+    }
+}
+
+fn load_rewind(save_data: Res<SaveData>, mut charges: ResMut<RewindCharges>) {
+    charges.enabled = save_data.rewind;
+}
+
+fn save_rewind(charges: Res<RewindCharges>, mut save_data: ResMut<SaveData>) {
+    if save_data.rewind != charges.enabled {
+        save_data.rewind = charges.enabled;
+    }
+}
+
+fn refill_charges(mut charges: ResMut<RewindCharges>) {
+    charges.left = charges.per_level;
+}
+
+fn refill_charges_on_level_change(_: On<LevelChanged>, mut charges: ResMut<RewindCharges>) {
+    charges.left = charges.per_level;
+}
+
+fn start_rewind(
+    add: On<Add, Rewinding>,
+    mut commands: Commands,
+    mut players: Query<(&mut Rewinding, &Sprite)>,
+) {
+    if let Ok((mut rewinding, sprite)) = players.get_mut(add.entity) {
+        rewinding.color = sprite.color;
+    }
+    commands
+        .entity(add.entity)
+        .insert((RigidBodyDisabled, ColliderDisabled));
+}
+
+fn finish_rewind(
+    remove: On<Remove, Rewinding>,
+    mut commands: Commands,
+    mut players: Query<(&Rewinding, &mut Sprite, &mut LinearVelocity)>,
+) {
+    // This also runs when the player is despawned along with the level.
+    let Ok((rewinding, mut sprite, mut velocity)) = players.get_mut(remove.entity) else {
+        return;
+    };
+    sprite.color = rewinding.color;
+    velocity.0 = Vec2::ZERO;
+    commands
+        .entity(remove.entity)
+        .try_remove::<(RigidBodyDisabled, ColliderDisabled)>();
+}
+
+/// Plays the path back in reverse, so the rewind is drawn smoothly like any other movement.
+fn play_rewind(
+    mut commands: Commands,
+    time: Res<Time>,
+    players: Query<
+        (
+            Entity,
+            &mut Rewinding,
+            &mut Position,
+            &mut LinearVelocity,
+            &mut Sprite,
+        ),
+        (With<Player>, Without<Dead>),
+    >,
+) {
+    for (player, mut rewinding, mut position, mut velocity, mut sprite) in players {
+        rewinding.timer.tick(time.delta());
+        let last = rewinding.path.len() - 1;
+        let index = (rewinding.timer.fraction() * last as f32).round() as usize;
+        position.0 = rewinding.path[index.min(last)];
+        velocity.0 = Vec2::ZERO;
+        sprite.color = REWIND_TINT;
+
+        if rewinding.timer.is_finished() {
+            commands.entity(player).remove::<Rewinding>();
+        }
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct RewindHud;
+
+fn spawn_rewind_hud(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Rewind HUD"),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: px(10),
+            right: px(10),
+            ..default()
+        },
+        DespawnOnExit(Screen::Gameplay),
+        Pickable::IGNORE,
+        children![(widget::label(""), RewindHud)],
+    ));
+}
+
+fn update_rewind_hud(charges: Res<RewindCharges>, mut label: Single<&mut Text, With<RewindHud>>) {
+    let text = if charges.enabled {
+        format!("Rewinds: {}", charges.left)
+    } else {
+        String::new()
+    };
+    if label.0 != text {
+        label.0 = text;
+    }
+}
@@ -0,0 +1,80 @@
+//! The randomness of the gameplay, seeded anew for every run so a run can be reproduced.
+//!
+//! The seed is logged whenever a run starts. Set the `GAME_SEED` environment variable to play
+//! every run with that seed instead.
+//!
+//! Purely visual randomness, like particles and idle animations, doesn't draw from it. How
+//! often that happens depends on the frame rate, which would throw the gameplay off.
+
+use bevy::prelude::*;
+use rand::{Rng, RngCore, SeedableRng, rngs::StdRng};
+
+use crate::screens::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(GameRng::from_env());
+    app.add_systems(OnEnter(Screen::Gameplay), reseed_game_rng);
+}
+
+/// Draw gameplay randomness from this instead of `rand::rng()`, e.g.
+/// `sounds.choose(&mut *game_rng)`.
+#[derive(Resource, Debug)]
+pub struct GameRng {
+    /// The seed every run uses, or `None` for a random one each time.
+    pub fixed_seed: Option<u64>,
+    seed: u64,
+    rng: StdRng,
+}
+
+impl GameRng {
+    pub fn new(fixed_seed: Option<u64>) -> Self {
+        let seed = fixed_seed.unwrap_or_else(|| rand::rng().random());
+        Self {
+            fixed_seed,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    fn from_env() -> Self {
+        let fixed_seed = std::env::var("GAME_SEED")
+            .ok()
+            .and_then(|seed| match seed.parse() {
+                Ok(seed) => Some(seed),
+                Err(error) => {
+                    warn!("Ignoring GAME_SEED={seed}: {error}");
+                    None
+                }
+            });
+        Self::new(fixed_seed)
+    }
+
+    /// The seed the current run started with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Starts over from the fixed seed, or from a new random one without it.
+    pub fn reseed(&mut self) {
+        *self = Self::new(self.fixed_seed);
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.rng.fill_bytes(dst);
+    }
+}
+
+fn reseed_game_rng(mut game_rng: ResMut<GameRng>) {
+    game_rng.reseed();
+    info!("Starting the run with seed {}", game_rng.seed());
+}
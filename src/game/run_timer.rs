@@ -0,0 +1,55 @@
+//! Measures how long the current run has taken.
+
+use std::time::Duration;
+
+use bevy::{prelude::*, time::Stopwatch};
+
+use crate::{AppSystems, PausableSystems, save::SaveData, screens::Screen};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<RunTimer>();
+    app.add_systems(OnEnter(Screen::Title), reset_run_timer);
+    app.add_systems(
+        Update,
+        tick_run_timer
+            .in_set(AppSystems::TickTimers)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(OnEnter(Screen::Victory), record_best_time);
+}
+
+/// Time spent in gameplay since leaving the title screen, excluding pauses.
+#[derive(Resource, Debug, Default)]
+pub struct RunTimer {
+    pub stopwatch: Stopwatch,
+    /// Whether the finished run beat the previous best time.
+    pub new_best: bool,
+}
+
+fn reset_run_timer(mut run_timer: ResMut<RunTimer>) {
+    *run_timer = RunTimer::default();
+}
+
+fn tick_run_timer(time: Res<Time>, mut run_timer: ResMut<RunTimer>) {
+    run_timer.stopwatch.tick(time.delta());
+}
+
+pub fn record_best_time(mut run_timer: ResMut<RunTimer>, mut save_data: ResMut<SaveData>) {
+    let elapsed = run_timer.stopwatch.elapsed();
+    run_timer.new_best = save_data.best_time.is_none_or(|best| elapsed < best);
+    if run_timer.new_best {
+        save_data.best_time = Some(elapsed);
+    }
+}
+
+/// Formats a duration as `mm:ss.mmm`.
+pub fn format_duration(duration: Duration) -> String {
+    let millis = duration.as_millis();
+    format!(
+        "{:02}:{:02}.{:03}",
+        millis / 60_000,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
@@ -0,0 +1,218 @@
+//! The looks the player character can be picked from.
+//!
+//! Each skin brings its own sprite sheet along with the animations in it, so sheets with a
+//! different layout work too. To add one, append a [`SkinDefinition`] to [`SKINS`].
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    game::animation::{
+        Animation, AnimationData, AnimationError, AnimationState, AnimationVariants, AtlasGrid,
+        FacingMode, Repeat,
+    },
+    save::SaveData,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PlayerSkin>();
+    app.add_systems(Startup, (load_player_skin, check_skins));
+    app.add_systems(
+        Update,
+        save_player_skin.run_if(resource_changed::<PlayerSkin>),
+    );
+}
+
+pub struct SkinDefinition {
+    pub name: &'static str,
+    /// The sprite sheet, relative to the assets folder.
+    pub image: &'static str,
+    pub tint: Color,
+    pub atlas: AtlasGrid,
+    /// Every [`AnimationState`] the player can be in has to be covered.
+    pub animations: fn() -> Vec<AnimationData>,
+    /// Occasionally played instead of some of the animations, e.g. to look around while idle.
+    pub variants: fn() -> Vec<AnimationVariants>,
+    /// Whether the sheet has its own frames facing left.
+    pub facing: FacingMode,
+}
+
+impl SkinDefinition {
+    pub fn atlas_layout(&self) -> TextureAtlasLayout {
+        self.atlas.layout()
+    }
+
+    /// The animations of the skin, with its variants and facing, checked against its sprite sheet.
+    pub fn animation(&self) -> Result<Animation, AnimationError> {
+        let atlas_len = self.atlas.frame_count();
+        Animation::try_new((self.animations)(), atlas_len)?
+            .try_with_variants((self.variants)(), atlas_len)?
+            .try_with_facing(self.facing, atlas_len)
+    }
+
+    /// The first frame of an animation, e.g. for a still image of the character.
+    pub fn first_frame(&self, state: AnimationState) -> usize {
+        (self.animations)()
+            .iter()
+            .find(|animation| animation.state == state)
+            .map_or(0, |animation| animation.atlas_index)
+    }
+}
+
+pub const SKINS: &[SkinDefinition] = &[
+    SkinDefinition {
+        name: "Ducky",
+        image: "images/hero.png",
+        tint: Color::WHITE,
+        atlas: AtlasGrid::new(UVec2::splat(16), 4, 5),
+        animations: ducky_animations,
+        variants: ducky_variants,
+        facing: FacingMode::Flip,
+    },
+    // The same sheet in a different color, as an example until there's more art.
+    SkinDefinition {
+        name: "Shadow Ducky",
+        image: "images/hero.png",
+        tint: Color::srgb(0.45, 0.4, 0.6),
+        atlas: AtlasGrid::new(UVec2::splat(16), 4, 5),
+        animations: ducky_animations,
+        variants: ducky_variants,
+        facing: FacingMode::Flip,
+    },
+];
+
+/// The index of the chosen skin in [`SKINS`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PlayerSkin(pub usize);
+
+impl PlayerSkin {
+    pub fn definition(self) -> &'static SkinDefinition {
+        SKINS.get(self.0).unwrap_or(&SKINS[0])
+    }
+}
+
+fn check_skins() {
+    for skin in SKINS {
+        if let Err(error) = skin.animation() {
+            error!(
+                "The {} skin doesn't fit its sprite sheet: {error}",
+                skin.name
+            );
+        }
+    }
+}
+
+fn load_player_skin(save_data: Res<SaveData>, mut skin: ResMut<PlayerSkin>) {
+    skin.0 = save_data.skin.min(SKINS.len() - 1);
+}
+
+fn save_player_skin(skin: Res<PlayerSkin>, mut save_data: ResMut<SaveData>) {
+    if save_data.skin != skin.0 {
+        save_data.skin = skin.0;
+    }
+}
+
+fn ducky_animations() -> Vec<AnimationData> {
+    let run = AnimationData {
+        frames: 6,
+        interval: Duration::from_millis(80),
+        state: AnimationState::Walking,
+        atlas_index: 0,
+        repeat: Repeat::Loop,
+    };
+    let idle = AnimationData {
+        frames: 4,
+        interval: Duration::from_millis(150),
+        state: AnimationState::Idle,
+        atlas_index: 6,
+        repeat: Repeat::Loop,
+    };
+    let fall = AnimationData {
+        frames: 3,
+        interval: Duration::from_millis(150),
+        state: AnimationState::Falling,
+        atlas_index: 10,
+        repeat: Repeat::Loop,
+    };
+    let jump = AnimationData {
+        frames: 3,
+        interval: Duration::from_millis(150),
+        state: AnimationState::Jumping,
+        atlas_index: 13,
+        repeat: Repeat::Loop,
+    };
+    // There's no dedicated hurt frame in the sheet, so this borrows the first death frame.
+    let hurt = AnimationData {
+        frames: 1,
+        interval: Duration::from_millis(150),
+        state: AnimationState::Hurt,
+        atlas_index: 16,
+        repeat: Repeat::OneShot,
+    };
+    // The sheet has no looking poses either, so these borrow a jumping and a falling frame.
+    let look_up = AnimationData {
+        frames: 1,
+        interval: Duration::from_millis(150),
+        state: AnimationState::LookUp,
+        atlas_index: 13,
+        repeat: Repeat::OneShot,
+    };
+    let look_down = AnimationData {
+        frames: 1,
+        interval: Duration::from_millis(150),
+        state: AnimationState::LookDown,
+        atlas_index: 10,
+        repeat: Repeat::OneShot,
+    };
+    // Nor a slam, so the ground pound holds the last falling frame.
+    let ground_pound = AnimationData {
+        frames: 1,
+        interval: Duration::from_millis(150),
+        state: AnimationState::GroundPound,
+        atlas_index: 12,
+        repeat: Repeat::OneShot,
+    };
+    let death = AnimationData {
+        frames: 3,
+        interval: Duration::from_millis(80),
+        state: AnimationState::Dying,
+        atlas_index: 16,
+        repeat: Repeat::OneShot,
+    };
+    vec![
+        run,
+        idle,
+        fall,
+        jump,
+        hurt,
+        look_up,
+        look_down,
+        ground_pound,
+        death,
+    ]
+}
+
+/// The sheet has no blinking frames, so the duck glances up or down now and then instead.
+fn ducky_variants() -> Vec<AnimationVariants> {
+    let glance_up = AnimationData {
+        frames: 1,
+        interval: Duration::from_millis(600),
+        state: AnimationState::Idle,
+        atlas_index: 13,
+        repeat: Repeat::OneShot,
+    };
+    let glance_down = AnimationData {
+        frames: 1,
+        interval: Duration::from_millis(600),
+        state: AnimationState::Idle,
+        atlas_index: 10,
+        repeat: Repeat::OneShot,
+    };
+    vec![AnimationVariants {
+        state: AnimationState::Idle,
+        delay: Duration::from_secs(4),
+        animations: vec![glance_up, glance_down],
+    }]
+}
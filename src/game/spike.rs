@@ -1,25 +1,26 @@
-use avian2d::prelude::{CollisionStart, RigidBody, Sensor};
+use avian2d::prelude::{CollisionStart, Sensor};
 use bevy::{
     ecs::{lifecycle::HookContext, world::DeferredWorld},
     prelude::*,
 };
 use bevy_ecs_ldtk::{EntityInstance, LdtkEntity, app::LdtkEntityAppExt, prelude::LdtkFields};
 
-use crate::{
-    game::{
-        colliders::ColliderBundle,
-        player::{Dead, Player},
-    },
-    menus::Menu,
+use crate::game::{
+    colliders::ColliderBundle,
+    draw_order::DrawLayer,
+    level::ResetLevel,
+    player::{DeathCause, KillPlayer, Player},
 };
 
 pub fn plugin(app: &mut App) {
     app.register_ldtk_entity::<SpikeBundle>("Spike");
     app.add_systems(Update, spike_rotation);
+    app.add_observer(hide_spikes);
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component, Reflect)]
 #[reflect(Component)]
+#[require(DrawLayer::Hazard)]
 #[component(on_add = on_spike_add)]
 pub struct Spike;
 
@@ -78,10 +79,16 @@ fn rotation_from_instance(instance: &EntityInstance) -> Rotation {
     }
 }
 
+/// Spikes are only revealed by killing a player, so a new attempt hides them again.
+fn hide_spikes(_: On<ResetLevel>, mut spikes: Query<&mut Visibility, With<Spike>>) {
+    for mut visibility in &mut spikes {
+        *visibility = Visibility::Hidden;
+    }
+}
+
 fn on_player_touched_spike(
     event: On<CollisionStart>,
     mut commands: Commands,
-    mut next_menu: ResMut<NextState<Menu>>,
     player_query: Query<Entity, With<Player>>,
 ) {
     // `colider1` and `body1` refer to the event target and its body.
@@ -91,12 +98,11 @@ fn on_player_touched_spike(
 
     for player_entity in player_query {
         if player_entity == other_entity {
-            next_menu.set(Menu::Death);
-            commands
-                .entity(player_entity)
-                .insert(Dead)
-                .remove::<RigidBody>();
             commands.entity(spike_entity).insert(Visibility::Visible);
+            commands.trigger(KillPlayer {
+                entity: player_entity,
+                cause: DeathCause::Spike,
+            });
         }
     }
 }
@@ -0,0 +1,116 @@
+//! Squashes and stretches the player sprite with its movement, so jumps and landings have
+//! more weight to them.
+//!
+//! Only the sprite's size changes. The transform stays untouched, so the collider does too.
+
+use avian2d::prelude::LinearVelocity;
+use bevy::prelude::*;
+use bevy_tnua::prelude::TnuaController;
+
+use crate::{
+    AppSystems, PausableSystems,
+    game::{
+        accessibility::ReducedMotion,
+        player::{Dead, MovementConfig, Player},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SquashStretchConfig>();
+    app.add_systems(
+        Update,
+        squash_and_stretch
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// The size of a frame in the player's sprite sheet.
+const FRAME_SIZE: Vec2 = Vec2::splat(16.0);
+
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct SquashStretchConfig {
+    /// How far the sprite deforms at most, as a fraction of its size.
+    pub intensity: f32,
+    /// The decay rate the sprite eases back to its normal shape with.
+    pub recovery: f32,
+}
+
+impl Default for SquashStretchConfig {
+    fn default() -> Self {
+        Self {
+            intensity: 0.25,
+            recovery: 12.0,
+        }
+    }
+}
+
+/// The current scale of the sprite, and whether it was in the air last frame.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct SquashStretch {
+    scale: Vec2,
+    airborne: bool,
+}
+
+impl Default for SquashStretch {
+    fn default() -> Self {
+        Self {
+            scale: Vec2::ONE,
+            airborne: false,
+        }
+    }
+}
+
+/// Fast vertical movement stretches the sprite and landing squashes it.
+/// The width always changes opposite to the height, so the sprite keeps its area.
+pub fn squash_and_stretch(
+    time: Res<Time>,
+    config: Res<SquashStretchConfig>,
+    movement: Res<MovementConfig>,
+    reduced_motion: Res<ReducedMotion>,
+    players: Query<
+        (
+            &mut Sprite,
+            &mut SquashStretch,
+            &LinearVelocity,
+            &TnuaController,
+            Has<Dead>,
+        ),
+        With<Player>,
+    >,
+) {
+    for (mut sprite, mut squash_stretch, velocity, controller, is_dead) in players {
+        if reduced_motion.0 || is_dead {
+            squash_stretch.scale = Vec2::ONE;
+            sprite.custom_size = None;
+            continue;
+        }
+
+        let airborne = controller.is_airborne().unwrap_or(true);
+        let landed = squash_stretch.airborne && !airborne;
+        squash_stretch.airborne = airborne;
+
+        if landed {
+            squash_stretch.scale = stretched(-config.intensity);
+        }
+        let target = if airborne {
+            let speed = (velocity.y.abs() / movement.max_fall_speed).min(1.0);
+            stretched(speed * config.intensity)
+        } else {
+            Vec2::ONE
+        };
+        squash_stretch
+            .scale
+            .smooth_nudge(&target, config.recovery, time.delta_secs());
+
+        sprite.custom_size = Some(FRAME_SIZE * squash_stretch.scale);
+    }
+}
+
+/// A scale that is `amount` taller, or shorter if negative, and correspondingly narrower.
+fn stretched(amount: f32) -> Vec2 {
+    let height = 1.0 + amount;
+    Vec2::new(1.0 / height, height)
+}
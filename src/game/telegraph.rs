@@ -0,0 +1,86 @@
+//! A warning that hazards give before they turn deadly, so there's a fair chance to get away.
+//!
+//! A hazard with a nonzero [`Telegraph`] flashes for that long once it's set off, and only hurts
+//! when [`TelegraphFinished`] is triggered afterwards. What happens then is up to the hazard,
+//! e.g. a mine explodes on whoever still stands on it.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{AppSystems, PausableSystems, game::accessibility::ReducedMotion};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            tick_telegraphs
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+            flash_telegraphs.in_set(AppSystems::Update),
+        ),
+    );
+    app.add_observer(end_flash);
+}
+
+/// How long each half of a flash takes.
+const FLASH_INTERVAL: f32 = 0.1;
+
+const FLASH_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+
+/// How long a hazard warns before it turns deadly. Without a warning it's deadly right away.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Telegraph(pub Duration);
+
+/// A hazard that is warning right now, until the timer finishes.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct Telegraphing(pub Timer);
+
+impl Telegraphing {
+    pub fn new(duration: Duration) -> Self {
+        Self(Timer::new(duration, TimerMode::Once))
+    }
+}
+
+/// Triggered on a hazard when its warning is over and it should turn deadly.
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct TelegraphFinished {
+    pub entity: Entity,
+}
+
+fn tick_telegraphs(
+    mut commands: Commands,
+    time: Res<Time>,
+    hazards: Query<(Entity, &mut Telegraphing)>,
+) {
+    for (hazard, mut telegraphing) in hazards {
+        if telegraphing.0.tick(time.delta()).is_finished() {
+            commands.entity(hazard).remove::<Telegraphing>();
+            commands.trigger(TelegraphFinished { entity: hazard });
+        }
+    }
+}
+
+/// With [`ReducedMotion`], the hazard stays tinted instead of flashing.
+fn flash_telegraphs(
+    reduced_motion: Res<ReducedMotion>,
+    hazards: Query<(&Telegraphing, &mut Sprite)>,
+) {
+    for (telegraphing, mut sprite) in hazards {
+        let flash = (telegraphing.0.elapsed_secs() / FLASH_INTERVAL) as u32 % 2 == 0;
+        sprite.color = if flash || reduced_motion.0 {
+            FLASH_COLOR
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+fn end_flash(remove: On<Remove, Telegraphing>, mut hazards: Query<&mut Sprite>) {
+    // This also runs when the hazard is despawned along with the level.
+    if let Ok(mut sprite) = hazards.get_mut(remove.entity) {
+        sprite.color = Color::WHITE;
+    }
+}
@@ -0,0 +1,160 @@
+//! On-screen buttons for playing on a touchscreen.
+//!
+//! They control the first player, alongside its keyboard bindings.
+//! The buttons show up as soon as the screen is touched, or when enabled in the settings.
+
+use bevy::prelude::*;
+
+use crate::{AppSystems, screens::Screen, theme::palette::BUTTON_BACKGROUND};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<TouchControls>();
+    app.init_resource::<TouchActions>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_touch_controls);
+    app.add_systems(
+        Update,
+        (
+            enable_on_touch,
+            apply_touch_controls_visibility.run_if(resource_changed::<TouchControls>),
+            read_touch_buttons,
+        )
+            .chain()
+            .in_set(AppSystems::RecordInput),
+    );
+}
+
+/// Whether the on-screen touch buttons are shown.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct TouchControls(pub bool);
+
+/// Which actions the touch buttons are holding down this frame.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct TouchActions {
+    pub left: bool,
+    pub right: bool,
+    pub jump: bool,
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum TouchButton {
+    Left,
+    Right,
+    Jump,
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+struct TouchControlsRoot;
+
+fn spawn_touch_controls(mut commands: Commands, touch_controls: Res<TouchControls>) {
+    commands.spawn((
+        Name::new("Touch Controls"),
+        TouchControlsRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            bottom: px(50),
+            padding: UiRect::horizontal(px(20)),
+            justify_content: JustifyContent::SpaceBetween,
+            ..default()
+        },
+        visibility(*touch_controls),
+        DespawnOnExit(Screen::Gameplay),
+        // The buttons are read from the touches directly, so they don't need picking.
+        Pickable::IGNORE,
+        children![
+            (
+                Name::new("Movement Buttons"),
+                Node {
+                    column_gap: px(20),
+                    ..default()
+                },
+                Pickable::IGNORE,
+                children![
+                    touch_button("<", TouchButton::Left),
+                    touch_button(">", TouchButton::Right),
+                ],
+            ),
+            touch_button("^", TouchButton::Jump),
+        ],
+    ));
+}
+
+fn touch_button(text: &'static str, button: TouchButton) -> impl Bundle {
+    (
+        Name::new("Touch Button"),
+        button,
+        Node {
+            width: px(80),
+            height: px(80),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BorderRadius::MAX,
+        BackgroundColor(BUTTON_BACKGROUND.with_alpha(0.4)),
+        Pickable::IGNORE,
+        children![(
+            Name::new("Touch Button Text"),
+            Text::new(text),
+            TextFont::from_font_size(40.0),
+            Pickable::IGNORE,
+        )],
+    )
+}
+
+fn visibility(touch_controls: TouchControls) -> Visibility {
+    if touch_controls.0 {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+/// A touch means there's a touchscreen, so the buttons are needed.
+fn enable_on_touch(touches: Res<Touches>, mut touch_controls: ResMut<TouchControls>) {
+    if touches.any_just_pressed() && !touch_controls.0 {
+        touch_controls.0 = true;
+    }
+}
+
+fn apply_touch_controls_visibility(
+    touch_controls: Res<TouchControls>,
+    mut roots: Query<&mut Visibility, With<TouchControlsRoot>>,
+) {
+    for mut root_visibility in &mut roots {
+        *root_visibility = visibility(*touch_controls);
+    }
+}
+
+/// Every finger is checked on its own, so moving and jumping at the same time works.
+fn read_touch_buttons(
+    touches: Res<Touches>,
+    touch_controls: Res<TouchControls>,
+    mut actions: ResMut<TouchActions>,
+    buttons: Query<(&TouchButton, &ComputedNode, &UiGlobalTransform)>,
+) {
+    *actions = TouchActions::default();
+    if !touch_controls.0 {
+        return;
+    }
+
+    for (button, node, transform) in &buttons {
+        // Touches are in logical pixels, while UI nodes are laid out in physical ones.
+        let pressed = touches.iter().any(|touch| {
+            node.contains_point(*transform, touch.position() / node.inverse_scale_factor())
+        });
+        if !pressed {
+            continue;
+        }
+        match button {
+            TouchButton::Left => actions.left = true,
+            TouchButton::Right => actions.right = true,
+            TouchButton::Jump => actions.jump = true,
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Invisible zones that announce when a player walks into them, for scripting a level.
+//!
+//! In LDtk these are `Trigger` entities, sized freely like force zones, with a string field
+//! `Id` and a bool field `Repeatable`. Entering one triggers [`TriggerActivated`] with its id,
+//! which tutorials, camera pans and the like observe to react. A zone only fires once,
+//! unless it's repeatable, in which case it fires every time a player enters it.
+//!
+//! A zone's optional string field `Message` is shown as a toast when it fires, which is enough
+//! for simple hints without any code.
+
+use avian2d::prelude::{CollisionStart, Sensor};
+use bevy::{
+    ecs::{lifecycle::HookContext, world::DeferredWorld},
+    prelude::*,
+};
+use bevy_ecs_ldtk::{EntityInstance, LdtkEntity, app::LdtkEntityAppExt, prelude::LdtkFields};
+
+use crate::{
+    game::{
+        colliders::ColliderBundle,
+        player::{Dead, Player},
+    },
+    theme::toast::ToastQueue,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_ldtk_entity::<TriggerZoneBundle>("Trigger");
+    app.add_observer(show_trigger_message);
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Component, Reflect)]
+#[reflect(Component)]
+#[component(on_add = on_trigger_zone_add)]
+pub struct TriggerZone {
+    /// What the observers of [`TriggerActivated`] recognize the zone by.
+    pub id: String,
+    /// Whether the zone fires again after the first time.
+    pub repeatable: bool,
+    /// Shown as a toast whenever the zone fires.
+    pub message: Option<String>,
+}
+
+/// Marks a trigger zone that fired and won't fire again.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Component, Reflect)]
+#[reflect(Component)]
+pub struct Consumed;
+
+/// Triggered when a player enters a [`TriggerZone`].
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct TriggerActivated {
+    pub id: String,
+    /// The zone's entity.
+    pub zone: Entity,
+    /// The player that entered it.
+    pub player: Entity,
+}
+
+pub fn on_trigger_zone_add(mut world: DeferredWorld, context: HookContext) {
+    let zone_entity = context.entity;
+    world
+        .commands()
+        .entity(zone_entity)
+        .observe(on_player_entered_trigger_zone);
+}
+
+#[derive(Clone, Debug, Default, Bundle, LdtkEntity)]
+pub struct TriggerZoneBundle {
+    #[with(trigger_zone_from_instance)]
+    trigger_zone: TriggerZone,
+
+    #[from_entity_instance]
+    collider_bundle: ColliderBundle,
+
+    sensor: Sensor,
+}
+
+fn trigger_zone_from_instance(instance: &EntityInstance) -> TriggerZone {
+    let id = instance
+        .get_maybe_string_field("Id")
+        .ok()
+        .cloned()
+        .flatten()
+        .unwrap_or_default();
+    if id.is_empty() {
+        warn!(
+            "Trigger {} has no Id, nothing can tell it apart",
+            instance.iid
+        );
+    }
+    TriggerZone {
+        id,
+        repeatable: instance
+            .get_bool_field("Repeatable")
+            .copied()
+            .unwrap_or(false),
+        message: instance
+            .get_maybe_string_field("Message")
+            .ok()
+            .cloned()
+            .flatten()
+            .filter(|message| !message.is_empty()),
+    }
+}
+
+fn on_player_entered_trigger_zone(
+    event: On<CollisionStart>,
+    mut commands: Commands,
+    zones: Query<&TriggerZone, Without<Consumed>>,
+    players: Query<(), (With<Player>, Without<Dead>)>,
+) {
+    let zone_entity = event.collider1;
+    let player = event.collider2;
+    let Ok(zone) = zones.get(zone_entity) else {
+        return;
+    };
+    if !players.contains(player) {
+        return;
+    }
+
+    if !zone.repeatable {
+        commands.entity(zone_entity).insert(Consumed);
+    }
+    debug!("Trigger zone \"{}\" activated", zone.id);
+    commands.trigger(TriggerActivated {
+        id: zone.id.clone(),
+        zone: zone_entity,
+        player,
+    });
+}
+
+fn show_trigger_message(
+    event: On<TriggerActivated>,
+    zones: Query<&TriggerZone>,
+    mut toasts: ResMut<ToastQueue>,
+) {
+    if let Ok(TriggerZone {
+        message: Some(message),
+        ..
+    }) = zones.get(event.zone)
+    {
+        toasts.push(message.clone());
+    }
+}
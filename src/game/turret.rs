@@ -0,0 +1,281 @@
+//! Turrets that keep firing projectiles in one direction.
+//!
+//! In LDtk these are `Turret` entities with an enum field `Direction` (`Up`, `Down`, `Left`
+//! or `Right`) and optional float fields `Interval` in seconds and `ProjectileSpeed`.
+//!
+//! Projectiles kill players the same way spikes do, and disappear when they hit a wall or
+//! after [`PROJECTILE_LIFETIME`].
+
+use std::time::Duration;
+
+use avian2d::prelude::{
+    Collider, CollisionEventsEnabled, CollisionStart, GravityScale, LinearVelocity, RigidBody,
+    Sensor,
+};
+use bevy::{
+    ecs::{lifecycle::HookContext, world::DeferredWorld},
+    prelude::*,
+};
+use bevy_ecs_ldtk::{EntityInstance, LdtkEntity, app::LdtkEntityAppExt, prelude::LdtkFields};
+
+use crate::{
+    AppSystems, PausableSystems,
+    game::{
+        assist::AssistMode,
+        colliders::{ColliderBundle, GameLayer},
+        draw_order::DrawLayer,
+        level::{LevelChanged, ResetLevel},
+        physics::Interpolated,
+        player::{Dead, DeathCause, KillPlayer, Player},
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_ldtk_entity::<TurretBundle>("Turret");
+    app.add_observer(reset_turrets);
+    app.add_observer(clear_projectiles);
+    app.add_systems(
+        Update,
+        (
+            (tick_turrets, tick_projectiles)
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+            fire_turrets
+                .in_set(AppSystems::Update)
+                .in_set(PausableSystems),
+        ),
+    );
+}
+
+/// How long a projectile flies before it disappears on its own.
+pub const PROJECTILE_LIFETIME: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum TurretDirection {
+    Up,
+    Down,
+    Left,
+    #[default]
+    Right,
+}
+
+impl TurretDirection {
+    fn vec(self) -> Vec2 {
+        match self {
+            TurretDirection::Up => Vec2::Y,
+            TurretDirection::Down => Vec2::NEG_Y,
+            TurretDirection::Left => Vec2::NEG_X,
+            TurretDirection::Right => Vec2::X,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Component, Reflect)]
+#[reflect(Component)]
+#[require(DrawLayer::Hazard)]
+#[component(on_add = on_turret_add)]
+pub struct Turret {
+    /// The time between two shots.
+    pub interval: Duration,
+    pub projectile_speed: f32,
+    pub direction: TurretDirection,
+}
+
+impl Default for Turret {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(2),
+            projectile_speed: 80.0,
+            direction: TurretDirection::default(),
+        }
+    }
+}
+
+/// Counts down to a [`Turret`]'s next shot.
+#[derive(Clone, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct TurretCooldown(Timer);
+
+pub fn on_turret_add(mut world: DeferredWorld, context: HookContext) {
+    let turret_entity = context.entity;
+    let Some(interval) = world
+        .get::<Turret>(turret_entity)
+        .map(|turret| turret.interval)
+    else {
+        return;
+    };
+    world
+        .commands()
+        .entity(turret_entity)
+        .insert(TurretCooldown(Timer::new(interval, TimerMode::Repeating)));
+}
+
+#[derive(Clone, Debug, Default, Bundle, LdtkEntity)]
+pub struct TurretBundle {
+    #[with(turret_from_instance)]
+    turret: Turret,
+
+    #[sprite_sheet]
+    sprite_sheet: Sprite,
+
+    #[from_entity_instance]
+    collider_bundle: ColliderBundle,
+}
+
+fn turret_from_instance(instance: &EntityInstance) -> Turret {
+    let default = Turret::default();
+    let direction = match instance.get_enum_field("Direction").map(|s| s.as_str()) {
+        Ok("Up") => TurretDirection::Up,
+        Ok("Down") => TurretDirection::Down,
+        Ok("Left") => TurretDirection::Left,
+        _ => TurretDirection::Right,
+    };
+    let interval = instance
+        .get_float_field("Interval")
+        .map(|&seconds| Duration::from_secs_f32(seconds))
+        .unwrap_or(default.interval);
+    let projectile_speed = instance
+        .get_float_field("ProjectileSpeed")
+        .copied()
+        .unwrap_or(default.projectile_speed);
+    Turret {
+        interval,
+        projectile_speed,
+        direction,
+    }
+}
+
+#[derive(Clone, Debug, Component, Reflect)]
+#[reflect(Component)]
+#[require(Interpolated, DrawLayer::Hazard)]
+#[component(on_add = on_projectile_add)]
+pub struct Projectile {
+    lifetime: Timer,
+}
+
+impl Default for Projectile {
+    fn default() -> Self {
+        Self {
+            lifetime: Timer::new(PROJECTILE_LIFETIME, TimerMode::Once),
+        }
+    }
+}
+
+pub fn on_projectile_add(mut world: DeferredWorld, context: HookContext) {
+    let projectile_entity = context.entity;
+    world
+        .commands()
+        .entity(projectile_entity)
+        .observe(on_projectile_hit);
+}
+
+fn projectile(position: Vec2, velocity: Vec2) -> impl Bundle {
+    (
+        Name::new("Projectile"),
+        Projectile::default(),
+        Sprite::from_color(Color::srgb(1.0, 0.3, 0.2), Vec2::splat(4.0)),
+        Transform::from_translation(position.extend(0.0)),
+        // Dynamic instead of kinematic, since kinematic bodies never touch the static walls.
+        RigidBody::Dynamic,
+        GravityScale(0.0),
+        Collider::circle(2.0),
+        Sensor,
+        CollisionEventsEnabled,
+        GameLayer::Projectile.layers(),
+        LinearVelocity(velocity),
+        DespawnOnExit(Screen::Gameplay),
+    )
+}
+
+fn tick_turrets(
+    time: Res<Time>,
+    assist_mode: Res<AssistMode>,
+    turrets: Query<&mut TurretCooldown>,
+) {
+    let delta = time.delta().mul_f32(assist_mode.hazard_speed());
+    for mut cooldown in turrets {
+        cooldown.0.tick(delta);
+    }
+}
+
+fn fire_turrets(
+    mut commands: Commands,
+    assist_mode: Res<AssistMode>,
+    turrets: Query<(&Turret, &TurretCooldown, &GlobalTransform)>,
+) {
+    for (turret, cooldown, transform) in turrets {
+        if !cooldown.0.just_finished() {
+            continue;
+        }
+        let direction = turret.direction.vec();
+        // Start outside of the turret's own collider.
+        let position = transform.translation().truncate() + direction * 12.0;
+        let speed = turret.projectile_speed * assist_mode.hazard_speed();
+        commands.spawn(projectile(position, direction * speed));
+    }
+}
+
+fn tick_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    assist_mode: Res<AssistMode>,
+    projectiles: Query<(Entity, &mut Projectile)>,
+) {
+    // Slower projectiles live longer, so they still fly as far.
+    let delta = time.delta().mul_f32(assist_mode.hazard_speed());
+    for (entity, mut projectile) in projectiles {
+        projectile.lifetime.tick(delta);
+        if projectile.lifetime.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn on_projectile_hit(
+    event: On<CollisionStart>,
+    mut commands: Commands,
+    player_query: Query<Entity, (With<Player>, Without<Dead>)>,
+    bodies: Query<&RigidBody, Without<Sensor>>,
+) {
+    let projectile_entity = event.collider1;
+    let other_entity = event.collider2;
+
+    // A projectile can hit a player and a wall at once, so it may already be gone.
+    if player_query.contains(other_entity) {
+        commands.entity(projectile_entity).try_despawn();
+        commands.trigger(KillPlayer {
+            entity: other_entity,
+            cause: DeathCause::Projectile,
+        });
+    } else if bodies.get(other_entity) == Ok(&RigidBody::Static) {
+        // Walls, and anything else solid that doesn't move.
+        commands.entity(projectile_entity).try_despawn();
+    }
+}
+
+/// Clears the air and restarts the turrets, so every attempt at a level is the same.
+fn reset_turrets(
+    _: On<ResetLevel>,
+    mut commands: Commands,
+    turrets: Query<&mut TurretCooldown>,
+    projectiles: Query<Entity, With<Projectile>>,
+) {
+    for mut cooldown in turrets {
+        cooldown.0.reset();
+    }
+    for projectile in projectiles {
+        commands.entity(projectile).despawn();
+    }
+}
+
+/// Projectiles live outside of the level, so they'd keep flying through the next one.
+fn clear_projectiles(
+    _: On<LevelChanged>,
+    mut commands: Commands,
+    projectiles: Query<Entity, With<Projectile>>,
+) {
+    for projectile in projectiles {
+        commands.entity(projectile).despawn();
+    }
+}
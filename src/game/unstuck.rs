@@ -0,0 +1,113 @@
+//! Frees players that got wedged in the level's geometry.
+//!
+//! The player's rounded collider can catch on the seams of the merged wall colliders and end
+//! up inside a wall instead of against it. When a player stays that deep in a wall for too
+//! long they're pushed back out, and a warning with their position is logged so the geometry
+//! can be fixed. Walking into a wall only touches it, which doesn't count.
+
+use std::time::Duration;
+
+use avian2d::prelude::{CollisionLayers, Collisions, LinearVelocity};
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+use bevy_tnua::TnuaUserControlsSystems;
+
+use crate::{
+    game::{
+        colliders::GameLayer,
+        player::{Dead, Player, PlayerIndex},
+    },
+    gameplay_frozen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<StuckRecovery>();
+    app.add_systems(
+        FixedUpdate,
+        free_stuck_players
+            .after(TnuaUserControlsSystems)
+            .run_if(not(gameplay_frozen)),
+    );
+}
+
+/// When a player counts as stuck, and how hard they get pushed out.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct StuckRecovery {
+    /// Players overlapping terrain by more than this many pixels are stuck in it.
+    pub min_penetration: f32,
+    /// How long they have to be stuck before getting pushed out.
+    pub delay: Duration,
+    /// The speed they're pushed out of the terrain with.
+    pub nudge: f32,
+}
+
+impl Default for StuckRecovery {
+    fn default() -> Self {
+        Self {
+            min_penetration: 2.0,
+            delay: Duration::from_secs(1),
+            nudge: 120.0,
+        }
+    }
+}
+
+fn free_stuck_players(
+    time: Res<Time>,
+    recovery: Res<StuckRecovery>,
+    collisions: Collisions,
+    layers: Query<&CollisionLayers>,
+    mut stuck_for: Local<EntityHashMap<Duration>>,
+    players: Query<
+        (Entity, &PlayerIndex, &GlobalTransform, &mut LinearVelocity),
+        (With<Player>, Without<Dead>),
+    >,
+) {
+    stuck_for.retain(|player, _| players.contains(*player));
+
+    for (player, index, transform, mut velocity) in players {
+        // The deepest overlap with terrain, and the way out of it.
+        let deepest = collisions
+            .collisions_with(player)
+            .filter(|pair| {
+                let other = if pair.collider1 == player {
+                    pair.collider2
+                } else {
+                    pair.collider1
+                };
+                layers
+                    .get(other)
+                    .is_ok_and(|layers| layers.memberships.has_all(GameLayer::Terrain))
+            })
+            .flat_map(|pair| {
+                // Normals point from the first collider to the second.
+                let sign = if pair.collider1 == player { -1.0 } else { 1.0 };
+                pair.manifolds.iter().flat_map(move |manifold| {
+                    manifold
+                        .points
+                        .iter()
+                        .map(move |point| (point.penetration, manifold.normal * sign))
+                })
+            })
+            .max_by(|(a, _), (b, _)| a.total_cmp(b));
+        let Some((_, way_out)) =
+            deepest.filter(|&(penetration, _)| penetration > recovery.min_penetration)
+        else {
+            stuck_for.remove(&player);
+            continue;
+        };
+
+        let duration = stuck_for.entry(player).or_default();
+        *duration += time.delta();
+        if *duration < recovery.delay {
+            continue;
+        }
+        *duration = Duration::ZERO;
+
+        let position = transform.translation().truncate();
+        warn!(
+            "Player {} got stuck in a wall at {position}, pushing them out",
+            index.0
+        );
+        velocity.0 = way_out * recovery.nudge;
+    }
+}
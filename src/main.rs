@@ -5,16 +5,20 @@
 
 mod asset_tracking;
 mod audio;
+mod camera;
 #[cfg(feature = "dev")]
 mod dev_tools;
 mod game;
 mod menus;
+mod save;
 mod screens;
+#[cfg(test)]
+mod testing;
 mod theme;
 
 use bevy::{asset::AssetMetaCheck, prelude::*};
 
-use crate::game::player::Player;
+use crate::screens::Screen;
 
 fn main() -> AppExit {
     App::new().add_plugins(AppPlugin).run()
@@ -52,39 +56,49 @@ impl Plugin for AppPlugin {
         app.add_plugins((
             asset_tracking::plugin,
             audio::plugin,
+            camera::plugin,
             game::plugin,
             #[cfg(feature = "dev")]
             dev_tools::plugin,
             menus::plugin,
+            save::plugin,
             screens::plugin,
             theme::plugin,
         ));
 
-        // Order new `AppSystems` variants by adding them here:
-        app.configure_sets(
-            Update,
-            (
-                AppSystems::TickTimers,
-                AppSystems::RecordInput,
-                AppSystems::Update,
-            )
-                .chain(),
-        );
+        configure_sets(app);
 
         app.insert_resource(ClearColor(BACKGROUND_COLOR));
 
-        // Set up the `Pause` state.
-        app.init_state::<Pause>();
-        app.configure_sets(Update, PausableSystems.run_if(in_state(Pause(false))));
-
-        // Spawn the main camera.
-        app.add_systems(Startup, spawn_camera);
+        app.add_systems(OnExit(Screen::Gameplay), unfreeze_gameplay);
     }
 }
 
+/// Orders the [`AppSystems`] and sets up the `Pause` state and [`GameplayFrozen`], which
+/// [`PausableSystems`] depend on. The headless test app shares this with [`AppPlugin`].
+pub(crate) fn configure_sets(app: &mut App) {
+    // Order new `AppSystems` variants by adding them here:
+    app.configure_sets(
+        Update,
+        (
+            AppSystems::TickTimers,
+            AppSystems::RecordInput,
+            AppSystems::Update,
+        )
+            .chain(),
+    );
+
+    // Set up the `Pause` state.
+    app.init_state::<Pause>();
+    app.init_resource::<GameplayFrozen>();
+    app.configure_sets(
+        Update,
+        PausableSystems.run_if(in_state(Pause(false)).and(not(gameplay_frozen))),
+    );
+}
+
 /// High-level groupings of systems for the app in the `Update` schedule.
-/// When adding a new variant, make sure to order it in the `configure_sets`
-/// call above.
+/// When adding a new variant, make sure to order it in [`configure_sets`].
 #[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
 enum AppSystems {
     /// Tick timers.
@@ -103,27 +117,29 @@ struct Pause(pub bool);
 #[derive(SystemSet, Copy, Clone, Eq, PartialEq, Hash, Debug)]
 struct PausableSystems;
 
-fn spawn_camera(mut commands: Commands) {
-    commands.spawn((
-        Name::new("Camera"),
-        Camera2d,
-        Transform::from_scale(Vec3::splat(0.3)),
-    ));
+/// Stops gameplay without opening a menu, e.g. for a scripted moment.
+///
+/// Input, physics, [`PausableSystems`] and animations stop, while the camera keeps following
+/// the players. Hazards can't be touched either, since their collisions come from the physics.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct GameplayFrozen(pub bool);
+
+impl GameplayFrozen {
+    pub fn freeze(&mut self) {
+        self.0 = true;
+    }
+
+    pub fn unfreeze(&mut self) {
+        self.0 = false;
+    }
+}
+
+pub fn gameplay_frozen(frozen: Res<GameplayFrozen>) -> bool {
+    frozen.0
 }
 
-const CAMERA_DECAY_RATE: f32 = 4.;
-
-fn follow_camera(
-    mut camera: Single<&mut Transform, With<Camera2d>>,
-    player: Single<&GlobalTransform, (With<Player>, Without<Camera2d>)>,
-    time: Res<Time>,
-) {
-    let Vec3 { x, y, .. } = player.translation();
-    let direction = Vec3::new(x, y, camera.translation.z);
-
-    // Applies a smooth effect to camera movement using stable interpolation
-    // between the camera position and the player position on the x and y axes.
-    camera
-        .translation
-        .smooth_nudge(&direction, CAMERA_DECAY_RATE, time.delta_secs());
+/// A scripted moment shouldn't outlast leaving the game.
+fn unfreeze_gameplay(mut frozen: ResMut<GameplayFrozen>) {
+    frozen.unfreeze();
 }
@@ -0,0 +1,74 @@
+//! Lists every achievement and whether it's unlocked.
+
+use bevy::{ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{game::achievements::Achievement, menus::Menu, save::SaveData, theme::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Achievements), spawn_achievements_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Achievements).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_achievements_menu(mut commands: Commands, save_data: Res<SaveData>) {
+    let rows: Vec<_> = Achievement::ALL
+        .into_iter()
+        .flat_map(|achievement| {
+            let status = if save_data.achievements.contains(&achievement) {
+                "Unlocked"
+            } else {
+                "Locked"
+            };
+            [
+                format!("{}: {}", achievement.name(), achievement.description()),
+                status.to_string(),
+            ]
+        })
+        .collect();
+
+    commands.spawn((
+        widget::ui_root("Achievements Menu"),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Achievements),
+        children![
+            widget::header("Achievements"),
+            (
+                Name::new("Achievements Grid"),
+                Node {
+                    display: Display::Grid,
+                    row_gap: px(10),
+                    column_gap: px(30),
+                    grid_template_columns: vec![
+                        RepeatedGridTrack::px(1, 600.0),
+                        RepeatedGridTrack::px(1, 150.0),
+                    ],
+                    ..default()
+                },
+                Children::spawn(SpawnIter(rows.into_iter().enumerate().map(|(i, text)| {
+                    (
+                        widget::label(text),
+                        Node {
+                            justify_self: if i.is_multiple_of(2) {
+                                JustifySelf::Start
+                            } else {
+                                JustifySelf::End
+                            },
+                            ..default()
+                        },
+                    )
+                }))),
+            ),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
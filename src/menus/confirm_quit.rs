@@ -0,0 +1,70 @@
+//! The confirmation dialog shown before quitting to the title screen.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    menus::{
+        Menu,
+        pause::{pause_physics, resume_physics},
+    },
+    screens::Screen,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ConfirmQuitReturn>();
+
+    app.add_systems(
+        OnEnter(Menu::ConfirmQuit),
+        (spawn_confirm_quit_menu, pause_physics),
+    );
+    app.add_systems(OnExit(Menu::ConfirmQuit), resume_physics);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::ConfirmQuit).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+/// The menu to return to if quitting is cancelled.
+#[derive(Resource, Debug, Default)]
+struct ConfirmQuitReturn(Menu);
+
+fn spawn_confirm_quit_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Confirm Quit Menu"),
+        GlobalZIndex(3),
+        DespawnOnExit(Menu::ConfirmQuit),
+        children![
+            widget::header("Quit to title?"),
+            widget::button("Yes", confirm_quit),
+            widget::button("No", cancel_quit),
+        ],
+    ));
+}
+
+/// Opens the confirmation dialog, remembering the current menu so cancelling can return to it.
+pub fn open_confirm_quit(
+    _: On<Pointer<Click>>,
+    menu: Res<State<Menu>>,
+    mut confirm_quit_return: ResMut<ConfirmQuitReturn>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    confirm_quit_return.0 = *menu.get();
+    next_menu.set(Menu::ConfirmQuit);
+}
+
+fn confirm_quit(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
+
+fn cancel_quit(
+    _: On<Pointer<Click>>,
+    confirm_quit_return: Res<ConfirmQuitReturn>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    next_menu.set(confirm_quit_return.0);
+}
+
+fn go_back(confirm_quit_return: Res<ConfirmQuitReturn>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(confirm_quit_return.0);
+}
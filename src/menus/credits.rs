@@ -8,7 +8,12 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Credits), spawn_credits_menu);
     app.add_systems(
         Update,
-        go_back.run_if(in_state(Menu::Credits).and(input_just_pressed(KeyCode::Escape))),
+        (
+            go_back.run_if(input_just_pressed(KeyCode::Escape)),
+            auto_scroll_credits,
+            skip_to_end.run_if(input_just_pressed(KeyCode::Space)),
+        )
+            .run_if(in_state(Menu::Credits)),
     );
 }
 
@@ -18,15 +23,61 @@ fn spawn_credits_menu(mut commands: Commands) {
         GlobalZIndex(2),
         DespawnOnExit(Menu::Credits),
         children![
-            widget::header("Created by"),
-            created_by(),
-            widget::header("Assets"),
-            assets(),
+            (
+                Name::new("Credits Scroll"),
+                CreditsScroll,
+                Node {
+                    height: percent(70),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: px(20),
+                    overflow: Overflow::scroll_y(),
+                    ..default()
+                },
+                children![
+                    widget::header("Created by"),
+                    created_by(),
+                    widget::header("Assets"),
+                    assets(),
+                ],
+            ),
+            widget::button("Skip", skip_to_end_on_click),
             widget::button("Back", go_back_on_click),
         ],
     ));
 }
 
+/// Marks the node whose content scrolls by on its own.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct CreditsScroll;
+
+/// How fast the credits scroll, in logical pixels per second.
+const CREDITS_SCROLL_SPEED: f32 = 30.0;
+
+/// The UI layout clamps the scroll position, so scrolling stops by itself at the end.
+fn auto_scroll_credits(
+    time: Res<Time>,
+    mut scroll: Single<&mut ScrollPosition, With<CreditsScroll>>,
+) {
+    scroll.y += CREDITS_SCROLL_SPEED * time.delta_secs();
+}
+
+fn skip_to_end_on_click(
+    _: On<Pointer<Click>>,
+    scroll: Single<&mut ScrollPosition, With<CreditsScroll>>,
+) {
+    scroll_to_end(scroll.into_inner());
+}
+
+fn skip_to_end(scroll: Single<&mut ScrollPosition, With<CreditsScroll>>) {
+    scroll_to_end(scroll.into_inner());
+}
+
+fn scroll_to_end(mut scroll: Mut<ScrollPosition>) {
+    scroll.y = f32::MAX;
+}
+
 fn created_by() -> impl Bundle {
     grid(vec![
         ["Jokler", "Everything"],
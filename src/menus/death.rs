@@ -5,9 +5,11 @@ use std::time::Duration;
 use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
 use crate::{
-    game::player::{PlayerSpawn, SpawnPlayer},
-    menus::Menu,
-    screens::Screen,
+    game::{
+        level::ResetLevel,
+        player::{DeathCount, LastDeathCause, RespawnPlayers},
+    },
+    menus::{Menu, confirm_quit::open_confirm_quit},
     theme::widget,
 };
 
@@ -25,7 +27,12 @@ pub(super) fn plugin(app: &mut App) {
 #[derive(Clone, Copy, Debug, Component, Reflect)]
 struct VisibleAt(Duration);
 
-fn spawn_death_menu(mut commands: Commands, time: Res<Time>) {
+fn spawn_death_menu(
+    mut commands: Commands,
+    time: Res<Time>,
+    death_count: Res<DeathCount>,
+    last_death_cause: Res<LastDeathCause>,
+) {
     commands.spawn((
         Visibility::Hidden,
         VisibleAt(time.elapsed() + Duration::from_millis(500)),
@@ -33,9 +40,10 @@ fn spawn_death_menu(mut commands: Commands, time: Res<Time>) {
         GlobalZIndex(2),
         DespawnOnExit(Menu::Death),
         children![
-            widget::header("You Died!"),
+            widget::header(last_death_cause.0.message()),
+            widget::label(format!("Deaths: {}", death_count.0)),
             widget::button("Restart", restart),
-            widget::button("Quit to title", quit_to_title),
+            widget::button("Quit to title", open_confirm_quit),
         ],
     ));
 }
@@ -52,27 +60,14 @@ fn make_visible(
     }
 }
 
-fn restart(
-    _: On<Pointer<Click>>,
-    mut commands: Commands,
-    player_spawner_entity: Single<Entity, With<PlayerSpawn>>,
-    mut next_menu: ResMut<NextState<Menu>>,
-) -> Result {
-    commands.trigger(SpawnPlayer(player_spawner_entity.entity()));
+fn restart(_: On<Pointer<Click>>, mut commands: Commands, mut next_menu: ResMut<NextState<Menu>>) {
+    commands.trigger(ResetLevel);
+    commands.trigger(RespawnPlayers);
     next_menu.set(Menu::None);
-
-    Ok(())
 }
 
-fn quit_to_title(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
-    next_screen.set(Screen::Title);
-}
-
-fn go_back(
-    mut commands: Commands,
-    player_spawner_entity: Single<Entity, With<PlayerSpawn>>,
-    mut next_menu: ResMut<NextState<Menu>>,
-) {
-    commands.trigger(SpawnPlayer(player_spawner_entity.entity()));
+fn go_back(mut commands: Commands, mut next_menu: ResMut<NextState<Menu>>) {
+    commands.trigger(ResetLevel);
+    commands.trigger(RespawnPlayers);
     next_menu.set(Menu::None);
 }
@@ -17,6 +17,7 @@ fn spawn_main_menu(mut commands: Commands) {
         children![
             widget::button("Play", enter_loading_or_gameplay_screen),
             widget::button("Settings", open_settings_menu),
+            widget::button("Achievements", open_achievements_menu),
             widget::button("Credits", open_credits_menu),
             widget::button("Exit", exit_app),
         ],
@@ -24,6 +25,7 @@ fn spawn_main_menu(mut commands: Commands) {
         children![
             widget::button("Play", enter_loading_or_gameplay_screen),
             widget::button("Settings", open_settings_menu),
+            widget::button("Achievements", open_achievements_menu),
             widget::button("Credits", open_credits_menu),
         ],
     ));
@@ -45,6 +47,10 @@ fn open_settings_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Men
     next_menu.set(Menu::Settings);
 }
 
+fn open_achievements_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Achievements);
+}
+
 fn open_credits_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Credits);
 }
@@ -1,5 +1,7 @@
 //! The game's menus and transitions between them.
 
+mod achievements;
+pub mod confirm_quit;
 mod credits;
 mod death;
 mod main;
@@ -12,11 +14,13 @@ pub(super) fn plugin(app: &mut App) {
     app.init_state::<Menu>();
 
     app.add_plugins((
+        confirm_quit::plugin,
         credits::plugin,
         main::plugin,
         settings::plugin,
         pause::plugin,
         death::plugin,
+        achievements::plugin,
     ));
 }
 
@@ -26,7 +30,10 @@ pub enum Menu {
     None,
     Main,
     Credits,
+    Achievements,
     Settings,
     Pause,
     Death,
+    Victory,
+    ConfirmQuit,
 }
@@ -3,7 +3,15 @@
 use avian2d::prelude::*;
 use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
-use crate::{menus::Menu, screens::Screen, theme::widget};
+use crate::{
+    GameplayFrozen,
+    game::{
+        level::ResetLevel,
+        player::{Player, PracticeMode, RespawnPlayers},
+    },
+    menus::{Menu, confirm_quit::open_confirm_quit},
+    theme::widget,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Pause), (spawn_pause_menu, pause_physics));
@@ -12,6 +20,10 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         go_back.run_if(in_state(Menu::Pause).and(input_just_pressed(KeyCode::Escape))),
     );
+    app.add_systems(
+        Update,
+        update_practice_mode_label.run_if(in_state(Menu::Pause)),
+    );
 }
 
 fn spawn_pause_menu(mut commands: Commands) {
@@ -22,18 +34,22 @@ fn spawn_pause_menu(mut commands: Commands) {
         children![
             widget::header("Game paused"),
             widget::button("Continue", close_menu),
+            widget::button("Restart Level", restart_level),
+            practice_mode_widget(),
             widget::button("Settings", open_settings_menu),
-            widget::button("Quit to title", quit_to_title),
+            widget::button("Quit to title", open_confirm_quit),
         ],
     ));
 }
 
-fn pause_physics(mut time: ResMut<Time<Physics>>) {
+pub(super) fn pause_physics(mut time: ResMut<Time<Physics>>) {
     time.pause();
 }
 
-fn resume_physics(mut time: ResMut<Time<Physics>>) {
-    time.unpause();
+pub(super) fn resume_physics(mut time: ResMut<Time<Physics>>, frozen: Res<GameplayFrozen>) {
+    if !frozen.0 {
+        time.unpause();
+    }
 }
 
 fn open_settings_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
@@ -44,8 +60,52 @@ fn close_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::None);
 }
 
-fn quit_to_title(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
-    next_screen.set(Screen::Title);
+fn restart_level(
+    _: On<Pointer<Click>>,
+    mut commands: Commands,
+    players: Query<Entity, With<Player>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    // The current player has to be gone before `SpawnPlayer` runs,
+    // otherwise `on_spawn_player` refuses to spawn a second one.
+    for player in &players {
+        commands.entity(player).despawn();
+    }
+    commands.trigger(ResetLevel);
+    commands.trigger(RespawnPlayers);
+    next_menu.set(Menu::None);
+}
+
+fn practice_mode_widget() -> impl Bundle {
+    (
+        Name::new("Practice Mode Widget"),
+        Node {
+            align_items: AlignItems::Center,
+            column_gap: px(10),
+            ..default()
+        },
+        children![
+            widget::label("Practice Mode"),
+            widget::button_small("<", toggle_practice_mode),
+            (widget::label(""), PracticeModeLabel),
+            widget::button_small(">", toggle_practice_mode),
+        ],
+    )
+}
+
+fn toggle_practice_mode(_: On<Pointer<Click>>, mut practice_mode: ResMut<PracticeMode>) {
+    practice_mode.0 = !practice_mode.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct PracticeModeLabel;
+
+fn update_practice_mode_label(
+    practice_mode: Res<PracticeMode>,
+    mut label: Single<&mut Text, With<PracticeModeLabel>>,
+) {
+    label.0 = if practice_mode.0 { "On" } else { "Off" }.to_string();
 }
 
 fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
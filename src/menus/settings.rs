@@ -2,9 +2,32 @@
 //!
 //! Additional settings and accessibility options should go here.
 
-use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*};
+use bevy::{
+    audio::Volume,
+    ecs::system::IntoObserverSystem,
+    input::{
+        common_conditions::input_just_pressed,
+        mouse::{MouseScrollUnit, MouseWheel},
+    },
+    prelude::*,
+};
 
-use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{
+    camera::PixelSnap,
+    game::{
+        accessibility::{HazardOutlines, ReducedMotion},
+        assist::AssistMode,
+        minimap::Minimap,
+        physics::PhysicsQuality,
+        player::{AutoRun, JumpTrigger, MAX_PLAYERS, PlayerCount, RespawnMode, RespawnTransition},
+        rewind::RewindCharges,
+        skin::{PlayerSkin, SKINS},
+        touch_controls::TouchControls,
+    },
+    menus::Menu,
+    screens::Screen,
+    theme::{focus::FocusedButton, prelude::*, scale::InterfaceScale},
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
@@ -17,7 +40,12 @@ pub(super) fn plugin(app: &mut App) {
 
     app.add_systems(
         Update,
-        update_global_volume_label.run_if(in_state(Menu::Settings)),
+        (
+            update_setting_values,
+            scroll_settings,
+            scroll_to_focused_setting.run_if(resource_changed::<FocusedButton>),
+        )
+            .run_if(in_state(Menu::Settings)),
     );
 }
 
@@ -34,52 +62,221 @@ fn spawn_settings_menu(mut commands: Commands) {
     ));
 }
 
+/// Marks the node the settings rows scroll in, since they don't all fit on small screens.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SettingsScroll;
+
+/// How far one notch of the mouse wheel scrolls the settings, in logical pixels.
+const SCROLL_LINE_HEIGHT: f32 = 40.0;
+
 fn settings_grid() -> impl Bundle {
     (
         Name::new("Settings Grid"),
+        SettingsScroll,
         Node {
-            display: Display::Grid,
+            max_height: percent(60),
+            flex_direction: FlexDirection::Column,
             row_gap: px(10),
-            column_gap: px(30),
-            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            overflow: Overflow::scroll_y(),
             ..default()
         },
         children![
-            (
-                widget::label("Master Volume"),
-                Node {
-                    justify_self: JustifySelf::End,
-                    ..default()
-                }
+            setting_row(
+                "Master Volume",
+                global_volume_value,
+                STEP,
+                lower_global_volume,
+                raise_global_volume,
+            ),
+            setting_row(
+                "On Death",
+                respawn_mode_value,
+                CYCLE,
+                toggle_respawn_mode,
+                toggle_respawn_mode,
+            ),
+            setting_row(
+                "Respawn Effect",
+                respawn_transition_value,
+                CYCLE,
+                toggle_respawn_transition,
+                toggle_respawn_transition,
+            ),
+            setting_row(
+                "Players",
+                player_count_value,
+                STEP,
+                lower_player_count,
+                raise_player_count,
             ),
-            global_volume_widget(),
+            setting_row(
+                "Holding Jump",
+                jump_trigger_value,
+                CYCLE,
+                toggle_jump_trigger,
+                toggle_jump_trigger,
+            ),
+            setting_row(
+                "Auto-Run",
+                auto_run_value,
+                CYCLE,
+                toggle_auto_run,
+                toggle_auto_run,
+            ),
+            setting_row(
+                "Character",
+                player_skin_value,
+                CYCLE,
+                previous_player_skin,
+                next_player_skin,
+            ),
+            setting_row(
+                "Touch Controls",
+                touch_controls_value,
+                CYCLE,
+                toggle_touch_controls,
+                toggle_touch_controls,
+            ),
+            setting_row(
+                "Camera Movement",
+                pixel_snap_value,
+                CYCLE,
+                toggle_pixel_snap,
+                toggle_pixel_snap,
+            ),
+            setting_row(
+                "Physics Quality",
+                physics_quality_value,
+                CYCLE,
+                lower_physics_quality,
+                raise_physics_quality,
+            ),
+            setting_row(
+                "UI Scale",
+                interface_scale_value,
+                CYCLE,
+                lower_interface_scale,
+                raise_interface_scale,
+            ),
+            setting_row(
+                "Reduced Motion",
+                reduced_motion_value,
+                CYCLE,
+                toggle_reduced_motion,
+                toggle_reduced_motion,
+            ),
+            setting_row(
+                "Hazard Outlines",
+                hazard_outlines_value,
+                CYCLE,
+                toggle_hazard_outlines,
+                toggle_hazard_outlines,
+            ),
+            setting_row(
+                "Minimap",
+                minimap_value,
+                CYCLE,
+                toggle_minimap,
+                toggle_minimap,
+            ),
+            setting_row(
+                "Assist Mode",
+                assist_mode_value,
+                CYCLE,
+                toggle_assist_mode,
+                toggle_assist_mode,
+            ),
+            setting_row("Rewind", rewind_value, CYCLE, toggle_rewind, toggle_rewind),
         ],
     )
 }
 
-fn global_volume_widget() -> impl Bundle {
+/// The buttons of a setting that steps through numbers.
+const STEP: [&str; 2] = ["-", "+"];
+/// The buttons of a setting that cycles through options.
+const CYCLE: [&str; 2] = ["<", ">"];
+
+/// A row of the settings grid: the setting's name, then its current value between two
+/// buttons that change it. `value` describes the current value, and is kept up to date by
+/// [`update_setting_values`].
+///
+/// Settings with only two options can pass the same action for both buttons.
+fn setting_row<E1, B1, M1, E2, B2, M2>(
+    name: &'static str,
+    value: fn(&World) -> String,
+    buttons: [&'static str; 2],
+    previous: impl IntoObserverSystem<E1, B1, M1>,
+    next: impl IntoObserverSystem<E2, B2, M2>,
+) -> impl Bundle
+where
+    E1: EntityEvent,
+    B1: Bundle,
+    E2: EntityEvent,
+    B2: Bundle,
+{
     (
-        Name::new("Global Volume Widget"),
+        Name::new(format!("{name} Setting")),
         Node {
-            justify_self: JustifySelf::Start,
+            display: Display::Grid,
+            column_gap: px(30),
+            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
             ..default()
         },
         children![
-            widget::button_small("-", lower_global_volume),
             (
-                Name::new("Current Volume"),
+                widget::label(name),
                 Node {
-                    padding: UiRect::horizontal(px(10)),
-                    justify_content: JustifyContent::Center,
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            (
+                Name::new("Setting Widget"),
+                Node {
+                    justify_self: JustifySelf::Start,
                     ..default()
                 },
-                children![(widget::label(""), GlobalVolumeLabel)],
+                children![
+                    widget::button_small(buttons[0], previous),
+                    (
+                        Name::new("Current Value"),
+                        Node {
+                            padding: UiRect::horizontal(px(10)),
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        children![(widget::label(""), SettingValue(value))],
+                    ),
+                    widget::button_small(buttons[1], next),
+                ],
             ),
-            widget::button_small("+", raise_global_volume),
         ],
     )
 }
 
+/// The value of a setting that is either on or off.
+fn on_off(on: bool) -> String {
+    if on { "On" } else { "Off" }.to_string()
+}
+
+/// Describes the current value of a setting, see [`setting_row`].
+#[derive(Component)]
+struct SettingValue(fn(&World) -> String);
+
+fn update_setting_values(world: &mut World) {
+    let mut labels = world.query::<(Entity, &SettingValue)>();
+    let values: Vec<_> = labels
+        .iter(world)
+        .map(|(entity, value)| (entity, (value.0)(world)))
+        .collect();
+    for (entity, value) in values {
+        if let Some(mut text) = world.get_mut::<Text>(entity) {
+            text.set_if_neq(Text(value));
+        }
+    }
+}
+
 const MIN_VOLUME: f32 = 0.0;
 const MAX_VOLUME: f32 = 3.0;
 
@@ -93,16 +290,235 @@ fn raise_global_volume(_: On<Pointer<Click>>, mut global_volume: ResMut<GlobalVo
     global_volume.volume = Volume::Linear(linear);
 }
 
-#[derive(Component, Reflect)]
-#[reflect(Component)]
-struct GlobalVolumeLabel;
+fn global_volume_value(world: &World) -> String {
+    let percent = 100.0 * world.resource::<GlobalVolume>().volume.to_linear();
+    format!("{percent:3.0}%")
+}
+
+fn toggle_respawn_mode(_: On<Pointer<Click>>, mut respawn_mode: ResMut<RespawnMode>) {
+    *respawn_mode = match *respawn_mode {
+        RespawnMode::Menu => RespawnMode::Instant,
+        RespawnMode::Instant => RespawnMode::Menu,
+    };
+}
+
+fn respawn_mode_value(world: &World) -> String {
+    match world.resource::<RespawnMode>() {
+        RespawnMode::Menu => "Show menu",
+        RespawnMode::Instant => "Respawn instantly",
+    }
+    .to_string()
+}
+
+fn toggle_respawn_transition(
+    _: On<Pointer<Click>>,
+    mut respawn_transition: ResMut<RespawnTransition>,
+) {
+    *respawn_transition = match *respawn_transition {
+        RespawnTransition::Animated => RespawnTransition::Instant,
+        RespawnTransition::Instant => RespawnTransition::Animated,
+    };
+}
+
+fn respawn_transition_value(world: &World) -> String {
+    match world.resource::<RespawnTransition>() {
+        RespawnTransition::Animated => "Grow in",
+        RespawnTransition::Instant => "Appear at once",
+    }
+    .to_string()
+}
+
+fn lower_player_count(_: On<Pointer<Click>>, mut player_count: ResMut<PlayerCount>) {
+    player_count.0 = player_count.0.saturating_sub(1).max(1);
+}
+
+fn raise_player_count(_: On<Pointer<Click>>, mut player_count: ResMut<PlayerCount>) {
+    player_count.0 = (player_count.0 + 1).min(MAX_PLAYERS);
+}
+
+fn player_count_value(world: &World) -> String {
+    world.resource::<PlayerCount>().0.to_string()
+}
+
+fn toggle_jump_trigger(_: On<Pointer<Click>>, mut jump_trigger: ResMut<JumpTrigger>) {
+    *jump_trigger = match *jump_trigger {
+        JumpTrigger::Hold => JumpTrigger::Tap,
+        JumpTrigger::Tap => JumpTrigger::Hold,
+    };
+}
+
+fn jump_trigger_value(world: &World) -> String {
+    match world.resource::<JumpTrigger>() {
+        JumpTrigger::Hold => "Keeps jumping",
+        JumpTrigger::Tap => "Jumps once",
+    }
+    .to_string()
+}
+
+fn toggle_auto_run(_: On<Pointer<Click>>, mut auto_run: ResMut<AutoRun>) {
+    auto_run.0 = !auto_run.0;
+}
+
+fn auto_run_value(world: &World) -> String {
+    on_off(world.resource::<AutoRun>().0)
+}
+
+fn previous_player_skin(_: On<Pointer<Click>>, mut skin: ResMut<PlayerSkin>) {
+    skin.0 = (skin.0 + SKINS.len() - 1) % SKINS.len();
+}
+
+fn next_player_skin(_: On<Pointer<Click>>, mut skin: ResMut<PlayerSkin>) {
+    skin.0 = (skin.0 + 1) % SKINS.len();
+}
+
+fn player_skin_value(world: &World) -> String {
+    world.resource::<PlayerSkin>().definition().name.to_string()
+}
+
+fn toggle_touch_controls(_: On<Pointer<Click>>, mut touch_controls: ResMut<TouchControls>) {
+    touch_controls.0 = !touch_controls.0;
+}
+
+fn touch_controls_value(world: &World) -> String {
+    on_off(world.resource::<TouchControls>().0)
+}
+
+fn toggle_pixel_snap(_: On<Pointer<Click>>, mut pixel_snap: ResMut<PixelSnap>) {
+    pixel_snap.0 = !pixel_snap.0;
+}
+
+fn pixel_snap_value(world: &World) -> String {
+    if world.resource::<PixelSnap>().0 {
+        "Pixel perfect"
+    } else {
+        "Smooth"
+    }
+    .to_string()
+}
+
+fn lower_physics_quality(_: On<Pointer<Click>>, mut quality: ResMut<PhysicsQuality>) {
+    *quality = match *quality {
+        PhysicsQuality::Low | PhysicsQuality::Medium => PhysicsQuality::Low,
+        PhysicsQuality::High => PhysicsQuality::Medium,
+    };
+}
+
+fn raise_physics_quality(_: On<Pointer<Click>>, mut quality: ResMut<PhysicsQuality>) {
+    *quality = match *quality {
+        PhysicsQuality::Low => PhysicsQuality::Medium,
+        PhysicsQuality::Medium | PhysicsQuality::High => PhysicsQuality::High,
+    };
+}
+
+fn physics_quality_value(world: &World) -> String {
+    match world.resource::<PhysicsQuality>() {
+        PhysicsQuality::Low => "Low",
+        PhysicsQuality::Medium => "Medium",
+        PhysicsQuality::High => "High",
+    }
+    .to_string()
+}
 
-fn update_global_volume_label(
-    global_volume: Res<GlobalVolume>,
-    mut label: Single<&mut Text, With<GlobalVolumeLabel>>,
+/// The UI layout clamps the scroll position, so this can't scroll past either end.
+fn scroll_settings(
+    mut mouse_wheel: MessageReader<MouseWheel>,
+    mut scroll: Single<&mut ScrollPosition, With<SettingsScroll>>,
 ) {
-    let percent = 100.0 * global_volume.volume.to_linear();
-    label.0 = format!("{percent:3.0}%");
+    for wheel in mouse_wheel.read() {
+        scroll.y -= match wheel.unit {
+            MouseScrollUnit::Line => wheel.y * SCROLL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => wheel.y,
+        };
+    }
+}
+
+/// Scrolls the settings so that the button focused with the keyboard or a gamepad is in view.
+fn scroll_to_focused_setting(
+    focused: Res<FocusedButton>,
+    parents: Query<&ChildOf>,
+    buttons: Query<(&UiGlobalTransform, &ComputedNode)>,
+    scroll: Single<
+        (
+            Entity,
+            &UiGlobalTransform,
+            &ComputedNode,
+            &mut ScrollPosition,
+        ),
+        With<SettingsScroll>,
+    >,
+) {
+    let (grid, grid_transform, grid_node, mut scroll) = scroll.into_inner();
+    let Some(button) = focused.0 else {
+        return;
+    };
+    if !parents.iter_ancestors(button).any(|parent| parent == grid) {
+        return;
+    }
+    let Ok((transform, node)) = buttons.get(button) else {
+        return;
+    };
+    // Both are centered on their translation, in physical pixels that grow downwards.
+    let top = transform.translation.y
+        - node.size().y / 2.0
+        - (grid_transform.translation.y - grid_node.size().y / 2.0);
+    let bottom = top + node.size().y;
+    if top < 0.0 {
+        scroll.y += top * grid_node.inverse_scale_factor();
+    } else if bottom > grid_node.size().y {
+        scroll.y += (bottom - grid_node.size().y) * grid_node.inverse_scale_factor();
+    }
+}
+
+fn lower_interface_scale(_: On<Pointer<Click>>, mut scale: ResMut<InterfaceScale>) {
+    *scale = scale.previous();
+}
+
+fn raise_interface_scale(_: On<Pointer<Click>>, mut scale: ResMut<InterfaceScale>) {
+    *scale = scale.next();
+}
+
+fn interface_scale_value(world: &World) -> String {
+    world.resource::<InterfaceScale>().name().to_string()
+}
+
+fn toggle_reduced_motion(_: On<Pointer<Click>>, mut reduced_motion: ResMut<ReducedMotion>) {
+    reduced_motion.0 = !reduced_motion.0;
+}
+
+fn reduced_motion_value(world: &World) -> String {
+    on_off(world.resource::<ReducedMotion>().0)
+}
+
+fn toggle_hazard_outlines(_: On<Pointer<Click>>, mut hazard_outlines: ResMut<HazardOutlines>) {
+    hazard_outlines.0 = !hazard_outlines.0;
+}
+
+fn hazard_outlines_value(world: &World) -> String {
+    on_off(world.resource::<HazardOutlines>().0)
+}
+
+fn toggle_minimap(_: On<Pointer<Click>>, mut minimap: ResMut<Minimap>) {
+    minimap.0 = !minimap.0;
+}
+
+fn minimap_value(world: &World) -> String {
+    on_off(world.resource::<Minimap>().0)
+}
+
+fn toggle_assist_mode(_: On<Pointer<Click>>, mut assist_mode: ResMut<AssistMode>) {
+    assist_mode.enabled = !assist_mode.enabled;
+}
+
+fn assist_mode_value(world: &World) -> String {
+    on_off(world.resource::<AssistMode>().enabled)
+}
+
+fn toggle_rewind(_: On<Pointer<Click>>, mut rewind_charges: ResMut<RewindCharges>) {
+    rewind_charges.enabled = !rewind_charges.enabled;
+}
+
+fn rewind_value(world: &World) -> String {
+    on_off(world.resource::<RewindCharges>().enabled)
 }
 
 fn go_back_on_click(
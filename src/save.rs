@@ -0,0 +1,105 @@
+//! Data that is kept between runs of the game.
+//!
+//! Native builds store it as RON next to the executable's working directory.
+//! On the web nothing is written, so the data only lasts for the session.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        achievements::Achievement,
+        ghost::Ghosts,
+        physics::PhysicsQuality,
+        player::{JumpTrigger, RespawnTransition},
+    },
+    theme::scale::InterfaceScale,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(SaveData::load());
+    app.add_systems(
+        Update,
+        write_save_data.run_if(resource_changed::<SaveData>.and(not(resource_added::<SaveData>))),
+    );
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct SaveData {
+    /// The fastest time the game has been completed in.
+    pub best_time: Option<Duration>,
+    /// The fastest run through each level, replayed as a ghost.
+    pub ghosts: Ghosts,
+    /// See [`ReducedMotion`](crate::game::accessibility::ReducedMotion).
+    pub reduced_motion: bool,
+    /// See [`HazardOutlines`](crate::game::accessibility::HazardOutlines).
+    pub hazard_outlines: bool,
+    /// See [`Minimap`](crate::game::minimap::Minimap).
+    pub minimap: bool,
+    /// See [`PlayerSkin`](crate::game::skin::PlayerSkin).
+    pub skin: usize,
+    /// See [`AssistMode`](crate::game::assist::AssistMode).
+    pub assist_mode: bool,
+    /// See [`RewindCharges`](crate::game::rewind::RewindCharges).
+    pub rewind: bool,
+    /// See [`JumpTrigger`].
+    pub jump_trigger: JumpTrigger,
+    /// See [`AutoRun`](crate::game::player::AutoRun).
+    pub auto_run: bool,
+    /// See [`PhysicsQuality`].
+    pub physics_quality: PhysicsQuality,
+    /// See [`RespawnTransition`].
+    pub respawn_transition: RespawnTransition,
+    /// See [`InterfaceScale`].
+    pub interface_scale: InterfaceScale,
+    /// The most coins found in each level, by the level's position in the LDtk project.
+    pub coin_records: BTreeMap<usize, usize>,
+    /// The most coins found in a whole run.
+    pub best_total_coins: Option<usize>,
+    /// Every [`Achievement`] unlocked so far.
+    pub achievements: BTreeSet<Achievement>,
+}
+
+#[cfg(not(target_family = "wasm"))]
+const SAVE_PATH: &str = "save.ron";
+
+impl SaveData {
+    #[cfg(not(target_family = "wasm"))]
+    fn load() -> Self {
+        let Ok(contents) = std::fs::read_to_string(SAVE_PATH) else {
+            return Self::default();
+        };
+        ron::from_str(&contents).unwrap_or_else(|error| {
+            warn!("Ignoring invalid save data in {SAVE_PATH}: {error}");
+            Self::default()
+        })
+    }
+
+    #[cfg(target_family = "wasm")]
+    fn load() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn write_save_data(save_data: Res<SaveData>) {
+    let contents = match ron::ser::to_string_pretty(&*save_data, default()) {
+        Ok(contents) => contents,
+        Err(error) => {
+            error!("Failed to serialize save data: {error}");
+            return;
+        }
+    };
+    if let Err(error) = std::fs::write(SAVE_PATH, contents) {
+        error!("Failed to write save data to {SAVE_PATH}: {error}");
+    }
+}
+
+#[cfg(target_family = "wasm")]
+fn write_save_data() {}
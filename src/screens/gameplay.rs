@@ -19,6 +19,8 @@ pub(super) fn plugin(app: &mut App) {
             close_menu.run_if(
                 in_state(Screen::Gameplay)
                     .and(not(in_state(Menu::None)))
+                    // Quitting is only confirmed or canceled from its own menu.
+                    .and(not(in_state(Menu::ConfirmQuit)))
                     .and(input_just_pressed(KeyCode::KeyP)),
             ),
         ),
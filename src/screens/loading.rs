@@ -3,25 +3,55 @@
 
 use bevy::prelude::*;
 
-use crate::{asset_tracking::ResourceHandles, screens::Screen, theme::prelude::*};
+use crate::{
+    asset_tracking::ResourceHandles,
+    screens::Screen,
+    theme::{prelude::*, widget::ProgressBarFill},
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Loading), spawn_loading_screen);
 
     app.add_systems(
         Update,
-        enter_gameplay_screen.run_if(in_state(Screen::Loading).and(all_assets_loaded)),
+        (
+            update_loading_bar,
+            show_loading_error.run_if(any_asset_failed),
+            enter_gameplay_screen.run_if(all_assets_loaded),
+        )
+            .run_if(in_state(Screen::Loading)),
     );
 }
 
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+struct LoadingLabel;
+
 fn spawn_loading_screen(mut commands: Commands) {
     commands.spawn((
         widget::ui_root("Loading Screen"),
         DespawnOnExit(Screen::Loading),
-        children![widget::label("Loading...")],
+        children![
+            (widget::label("Loading..."), LoadingLabel),
+            widget::progress_bar(),
+        ],
     ));
 }
 
+fn update_loading_bar(
+    resource_handles: Res<ResourceHandles>,
+    mut fills: Query<&mut Node, With<ProgressBarFill>>,
+) {
+    for mut node in &mut fills {
+        node.width = percent(resource_handles.progress() * 100.0);
+    }
+}
+
+/// Some assets will never finish loading, so say so instead of loading forever.
+fn show_loading_error(mut label: Single<&mut Text, With<LoadingLabel>>) {
+    label.0 = "Failed to load the game's assets.\nPlease try reloading.".to_string();
+}
+
 fn enter_gameplay_screen(mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Gameplay);
 }
@@ -29,3 +59,7 @@ fn enter_gameplay_screen(mut next_screen: ResMut<NextState<Screen>>) {
 fn all_assets_loaded(resource_handles: Res<ResourceHandles>) -> bool {
     resource_handles.is_all_done()
 }
+
+fn any_asset_failed(resource_handles: Res<ResourceHandles>) -> bool {
+    resource_handles.has_failed()
+}
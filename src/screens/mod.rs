@@ -9,6 +9,8 @@ use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Screen>();
+    app.insert_resource(StartupConfig::from_env());
+    app.add_systems(Startup, skip_title.run_if(title_skipped));
 
     app.add_plugins((
         gameplay::plugin,
@@ -27,3 +29,39 @@ pub enum Screen {
     Gameplay,
     Victory,
 }
+
+/// Where the game starts, e.g. to boot straight into the level that's being worked on.
+///
+/// By default the game starts on the title screen and plays from the first level.
+/// Both can be changed with the `START_LEVEL` (a level's position in the LDtk project,
+/// counting from 0) and `SKIP_TITLE` environment variables.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct StartupConfig {
+    /// The level every game starts in.
+    pub initial_level: usize,
+    /// Whether to go straight to loading the game instead of showing the title screen.
+    pub skip_title: bool,
+}
+
+impl StartupConfig {
+    fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Ok(level) = std::env::var("START_LEVEL") {
+            match level.parse() {
+                Ok(level) => config.initial_level = level,
+                Err(error) => warn!("Ignoring START_LEVEL={level}: {error}"),
+            }
+        }
+        config.skip_title = std::env::var("SKIP_TITLE").is_ok_and(|value| value != "0");
+        config
+    }
+}
+
+pub fn title_skipped(config: Res<StartupConfig>) -> bool {
+    config.skip_title
+}
+
+fn skip_title(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Loading);
+}
@@ -2,28 +2,106 @@
 
 use bevy::prelude::*;
 
-use crate::{asset_tracking::LoadResource, audio::sound_effect, screens::Screen, theme::widget};
+use crate::{
+    asset_tracking::LoadResource,
+    audio::sound_effect,
+    game::{
+        coin::{CoinSummary, RunCoins, record_coins},
+        goal::Fanfare,
+        run_timer::{RunTimer, format_duration, record_best_time},
+    },
+    menus::{Menu, confirm_quit::open_confirm_quit},
+    save::SaveData,
+    screens::Screen,
+    theme::widget,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.load_resource::<VictoryAssets>();
-    app.add_systems(OnEnter(Screen::Victory), spawn_victory_menu);
+    app.add_systems(
+        OnEnter(Screen::Victory),
+        open_victory_menu
+            .after(record_best_time)
+            .after(record_coins),
+    );
+    app.add_systems(OnExit(Screen::Victory), close_menu);
+    app.add_systems(OnEnter(Menu::Victory), spawn_victory_menu);
     app.add_systems(OnEnter(Screen::Victory), start_victory_music);
 }
 
-fn spawn_victory_menu(mut commands: Commands) {
-    commands.spawn((
-        widget::ui_root("Victory Menu"),
-        GlobalZIndex(2),
-        DespawnOnExit(Screen::Victory),
-        children![
-            widget::header("You Win!"),
-            widget::button("Quit to title", quit_to_title),
-        ],
-    ));
+fn open_victory_menu(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Victory);
+}
+
+fn close_menu(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::None);
+}
+
+fn spawn_victory_menu(
+    mut commands: Commands,
+    run_timer: Res<RunTimer>,
+    run_coins: Res<RunCoins>,
+    save_data: Res<SaveData>,
+) {
+    commands
+        .spawn((
+            widget::ui_root("Victory Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::Victory),
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header("You Win!"));
+            parent.spawn(widget::label(format!(
+                "Time: {}",
+                format_duration(run_timer.stopwatch.elapsed())
+            )));
+            if run_timer.new_best {
+                parent.spawn(widget::header("New best!"));
+            } else if let Some(best_time) = save_data.best_time {
+                parent.spawn(widget::label(format!(
+                    "Best: {}",
+                    format_duration(best_time)
+                )));
+            }
+            if let Some(summary) = &run_coins.summary {
+                spawn_coin_table(parent, summary);
+            }
+            parent.spawn(widget::button("Quit to title", open_confirm_quit));
+        });
 }
 
-fn quit_to_title(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
-    next_screen.set(Screen::Title);
+/// The coins found in each level and in total, next to the records they're up against.
+fn spawn_coin_table(parent: &mut ChildSpawnerCommands, summary: &CoinSummary) {
+    fn record(found: usize, previous: Option<usize>) -> String {
+        match previous {
+            Some(best) if found <= best => format!("best {best}"),
+            Some(_) => "new best!".to_string(),
+            None => "first record".to_string(),
+        }
+    }
+
+    parent
+        .spawn((
+            Name::new("Coin Table"),
+            Node {
+                display: Display::Grid,
+                row_gap: px(4),
+                column_gap: px(30),
+                grid_template_columns: RepeatedGridTrack::auto(2),
+                ..default()
+            },
+        ))
+        .with_children(|table| {
+            for &(level, found, previous) in &summary.levels {
+                table.spawn(widget::label(format!("Level {}: {found} coins", level + 1)));
+                table.spawn(widget::label(record(found, previous)));
+            }
+            table.spawn(widget::label(format!("Total: {} coins", summary.total)));
+            table.spawn(widget::label(record(
+                summary.total,
+                summary.previous_best_total,
+            )));
+        });
 }
 
 #[derive(Resource, Asset, Clone, Reflect)]
@@ -42,7 +120,15 @@ impl FromWorld for VictoryAssets {
     }
 }
 
-fn start_victory_music(mut commands: Commands, victory_assets: Res<VictoryAssets>) {
+/// A final goal already plays its fanfare on the way here.
+fn start_victory_music(
+    mut commands: Commands,
+    victory_assets: Res<VictoryAssets>,
+    fanfares: Query<(), With<Fanfare>>,
+) {
+    if !fanfares.is_empty() {
+        return;
+    }
     commands.spawn((
         Name::new("Victory Music"),
         sound_effect(victory_assets.victory_sound.clone()),
@@ -0,0 +1,14 @@
+//! Helpers for tests that run parts of the game in an [`App`].
+
+use bevy::{prelude::*, state::app::StatesPlugin};
+
+/// A headless app without rendering, audio or windows, otherwise set up like
+/// [`AppPlugin`](crate::AppPlugin).
+///
+/// Add the plugins or systems under test, then step it with [`App::update`].
+pub fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default(), StatesPlugin));
+    crate::configure_sets(&mut app);
+    app
+}
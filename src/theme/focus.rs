@@ -0,0 +1,157 @@
+//! Keyboard and gamepad navigation between buttons.
+//!
+//! The arrow keys or the D-pad move the focus to the nearest button in that direction,
+//! and Enter or the gamepad's south button clicks the focused button.
+
+use std::time::Duration;
+
+use bevy::{
+    camera::NormalizedRenderTarget,
+    picking::{
+        backend::HitData,
+        pointer::{Location, PointerButton, PointerId},
+    },
+    prelude::*,
+    window::{PrimaryWindow, WindowRef},
+};
+
+use crate::theme::palette::FOCUS_OUTLINE;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<FocusedButton>();
+    app.add_systems(
+        Update,
+        (
+            clear_lost_focus,
+            move_focus,
+            activate_focused_button,
+            apply_focus_outline.run_if(resource_changed::<FocusedButton>),
+        )
+            .chain(),
+    );
+}
+
+/// The button that navigation starts from and that gets activated.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct FocusedButton(pub Option<Entity>);
+
+/// Drops the focus once its button is gone, e.g. because its menu was closed.
+fn clear_lost_focus(mut focused: ResMut<FocusedButton>, buttons: Query<&InheritedVisibility>) {
+    if let Some(button) = focused.0
+        && !buttons.get(button).is_ok_and(|visibility| visibility.get())
+    {
+        focused.0 = None;
+    }
+}
+
+fn move_focus(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut focused: ResMut<FocusedButton>,
+    buttons: Query<(Entity, &UiGlobalTransform, &InheritedVisibility), With<Button>>,
+) {
+    let pressed = |key: KeyCode, button: GamepadButton| {
+        keyboard.just_pressed(key) || gamepads.iter().any(|gamepad| gamepad.just_pressed(button))
+    };
+    // UI coordinates grow downwards.
+    let direction = if pressed(KeyCode::ArrowUp, GamepadButton::DPadUp) {
+        Vec2::NEG_Y
+    } else if pressed(KeyCode::ArrowDown, GamepadButton::DPadDown) {
+        Vec2::Y
+    } else if pressed(KeyCode::ArrowLeft, GamepadButton::DPadLeft) {
+        Vec2::NEG_X
+    } else if pressed(KeyCode::ArrowRight, GamepadButton::DPadRight) {
+        Vec2::X
+    } else {
+        return;
+    };
+
+    let visible_buttons = buttons
+        .iter()
+        .filter(|(_, _, visibility)| visibility.get())
+        .map(|(entity, transform, _)| (entity, transform.translation));
+
+    let current = focused
+        .0
+        .and_then(|entity| buttons.get(entity).ok())
+        .map(|(_, transform, _)| transform.translation);
+    let Some(current) = current else {
+        // Start with the top left button, whichever way was pressed.
+        focused.0 = visible_buttons
+            .min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+            .map(|(entity, _)| entity);
+        return;
+    };
+
+    // Prefer buttons that are straight ahead over ones that are closer but off to the side.
+    let score = |position: Vec2| {
+        let offset = position - current;
+        let ahead = offset.dot(direction);
+        let aside = offset.perp_dot(direction).abs();
+        (ahead > 0.0).then_some(ahead + 2.0 * aside)
+    };
+    if let Some((entity, _)) = visible_buttons
+        .filter_map(|(entity, position)| score(position).map(|score| (entity, score)))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+    {
+        focused.0 = Some(entity);
+    }
+}
+
+/// Clicks the focused button, so it runs the same action as with a mouse.
+fn activate_focused_button(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    focused: Res<FocusedButton>,
+    buttons: Query<(&UiGlobalTransform, &ComputedNode)>,
+    window: Single<Entity, With<PrimaryWindow>>,
+    camera: Single<Entity, With<Camera2d>>,
+) {
+    let activated = keyboard.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter])
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::South));
+    if !activated {
+        return;
+    }
+    let Some(button) = focused.0 else {
+        return;
+    };
+    let Ok((transform, node)) = buttons.get(button) else {
+        return;
+    };
+    let Some(window) = WindowRef::Primary.normalize(Some(*window)) else {
+        return;
+    };
+
+    commands.trigger(Pointer::new(
+        PointerId::Mouse,
+        Location {
+            target: NormalizedRenderTarget::Window(window),
+            position: transform.translation * node.inverse_scale_factor(),
+        },
+        Click {
+            button: PointerButton::Primary,
+            hit: HitData::new(*camera, 0.0, None, None),
+            duration: Duration::ZERO,
+        },
+        button,
+    ));
+}
+
+fn apply_focus_outline(
+    mut commands: Commands,
+    focused: Res<FocusedButton>,
+    outlined: Query<Entity, (With<Button>, With<Outline>)>,
+) {
+    for button in &outlined {
+        commands.entity(button).remove::<Outline>();
+    }
+    if let Some(button) = focused.0 {
+        commands
+            .entity(button)
+            .insert(Outline::new(px(3), px(2), FOCUS_OUTLINE));
+    }
+}
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::{asset_tracking::LoadResource, audio::sound_effect};
+use crate::{
+    asset_tracking::LoadResource,
+    audio::{SoundKind, sound_effect},
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(Update, apply_interaction_palette);
@@ -67,7 +70,10 @@ fn play_on_hover_sound_effect(
     };
 
     if interaction_query.contains(trigger.entity) {
-        commands.spawn(sound_effect(interaction_assets.hover.clone()));
+        commands.spawn((
+            sound_effect(interaction_assets.hover.clone()),
+            SoundKind::Interface,
+        ));
     }
 }
 
@@ -82,6 +88,9 @@ fn play_on_click_sound_effect(
     };
 
     if interaction_query.contains(trigger.entity) {
-        commands.spawn(sound_effect(interaction_assets.click.clone()));
+        commands.spawn((
+            sound_effect(interaction_assets.click.clone()),
+            SoundKind::Interface,
+        ));
     }
 }
@@ -3,8 +3,11 @@
 // Unused utilities may trigger this lints undesirably.
 #![allow(dead_code)]
 
+pub mod focus;
 pub mod interaction;
 pub mod palette;
+pub mod scale;
+pub mod toast;
 pub mod widget;
 
 #[allow(unused_imports)]
@@ -15,5 +18,10 @@ pub mod prelude {
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(interaction::plugin);
+    app.add_plugins((
+        focus::plugin,
+        interaction::plugin,
+        scale::plugin,
+        toast::plugin,
+    ));
 }
@@ -14,3 +14,6 @@ pub const BUTTON_BACKGROUND: Color = Color::srgb(0.275, 0.400, 0.750);
 pub const BUTTON_HOVERED_BACKGROUND: Color = Color::srgb(0.384, 0.600, 0.820);
 /// #3d4999
 pub const BUTTON_PRESSED_BACKGROUND: Color = Color::srgb(0.239, 0.286, 0.600);
+
+/// #fcfbcc
+pub const FOCUS_OUTLINE: Color = Color::srgb(0.988, 0.984, 0.800);
@@ -0,0 +1,124 @@
+//! How large the menus and the HUD are drawn.
+//!
+//! Every UI node goes through Bevy's [`UiScale`], so nothing else needs to know about it. The
+//! scale is applied again whenever the window is resized, which includes the canvas following
+//! its parent on the web, so automatic scaling keeps up live.
+
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowResized},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::save::SaveData;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<InterfaceScale>();
+    app.add_systems(Startup, load_interface_scale);
+    app.add_systems(
+        Update,
+        (
+            apply_interface_scale
+                .run_if(resource_changed::<InterfaceScale>.or(on_message::<WindowResized>)),
+            save_interface_scale.run_if(resource_changed::<InterfaceScale>),
+        ),
+    );
+}
+
+/// The window height the UI is laid out for. [`InterfaceScale::Auto`] scales it to fit others.
+const REFERENCE_HEIGHT: f32 = 720.0;
+
+/// The smallest and largest automatic scale, so text stays readable on tiny windows and
+/// doesn't get huge on big displays.
+const MIN_AUTO_SCALE: f32 = 0.5;
+const MAX_AUTO_SCALE: f32 = 2.0;
+
+/// How large the UI is drawn, persisted in the [`SaveData`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub enum InterfaceScale {
+    Small,
+    #[default]
+    Normal,
+    Large,
+    Huge,
+    /// Follows the window's height.
+    Auto,
+}
+
+impl InterfaceScale {
+    /// The fixed sizes from smallest to largest, with [`InterfaceScale::Auto`] after them since
+    /// it can end up at any of them.
+    const ALL: [InterfaceScale; 5] = [
+        InterfaceScale::Small,
+        InterfaceScale::Normal,
+        InterfaceScale::Large,
+        InterfaceScale::Huge,
+        InterfaceScale::Auto,
+    ];
+
+    /// The scale for a window `window_height` logical pixels tall.
+    pub fn factor(self, window_height: f32) -> f32 {
+        match self {
+            InterfaceScale::Auto => {
+                (window_height / REFERENCE_HEIGHT).clamp(MIN_AUTO_SCALE, MAX_AUTO_SCALE)
+            }
+            InterfaceScale::Small => 0.75,
+            InterfaceScale::Normal => 1.0,
+            InterfaceScale::Large => 1.25,
+            InterfaceScale::Huge => 1.5,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            InterfaceScale::Auto => "Fit window",
+            InterfaceScale::Small => "75%",
+            InterfaceScale::Normal => "100%",
+            InterfaceScale::Large => "125%",
+            InterfaceScale::Huge => "150%",
+        }
+    }
+
+    /// The choice before this one, or `self` if there's none.
+    pub fn previous(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|&scale| scale == self)
+            .unwrap_or(0);
+        Self::ALL[index.saturating_sub(1)]
+    }
+
+    /// The choice after this one, or `self` if there's none.
+    pub fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|&scale| scale == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1).min(Self::ALL.len() - 1)]
+    }
+}
+
+fn load_interface_scale(save_data: Res<SaveData>, mut scale: ResMut<InterfaceScale>) {
+    *scale = save_data.interface_scale;
+}
+
+fn save_interface_scale(scale: Res<InterfaceScale>, mut save_data: ResMut<SaveData>) {
+    if save_data.interface_scale != *scale {
+        save_data.interface_scale = *scale;
+    }
+}
+
+fn apply_interface_scale(
+    scale: Res<InterfaceScale>,
+    window: Option<Single<&Window, With<PrimaryWindow>>>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let Some(window) = window else {
+        return;
+    };
+    let factor = scale.factor(window.height());
+    if ui_scale.0 != factor {
+        ui_scale.0 = factor;
+    }
+}
@@ -0,0 +1,91 @@
+//! Short messages that slide in at the top right corner and go away on their own.
+//!
+//! Push text into the [`ToastQueue`] and the toasts are shown one at a time.
+
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::prelude::*;
+
+use crate::{game::accessibility::ReducedMotion, theme::widget};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ToastQueue>();
+    app.add_systems(Update, (show_next_toast, animate_toasts).chain());
+}
+
+/// How many toasts can wait at once. Anything pushed beyond that is dropped.
+pub const MAX_QUEUED_TOASTS: usize = 5;
+
+/// How long a toast stays, including sliding in and out.
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+const SLIDE_SECS: f32 = 0.25;
+
+/// The messages waiting to be shown.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct ToastQueue {
+    pending: VecDeque<String>,
+    /// The message on screen right now.
+    shown: Option<String>,
+}
+
+impl ToastQueue {
+    /// Queues `text`, unless the same message is already shown or waiting.
+    pub fn push(&mut self, text: impl Into<String>) {
+        let text = text.into();
+        if self.shown.as_ref() == Some(&text)
+            || self.pending.contains(&text)
+            || self.pending.len() >= MAX_QUEUED_TOASTS
+        {
+            return;
+        }
+        self.pending.push_back(text);
+    }
+}
+
+/// A toast that is on screen, and how long it has been there.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+struct ActiveToast(Timer);
+
+fn show_next_toast(
+    mut commands: Commands,
+    mut queue: ResMut<ToastQueue>,
+    active: Query<(), With<ActiveToast>>,
+) {
+    if !active.is_empty() {
+        return;
+    }
+    queue.shown = queue.pending.pop_front();
+    if let Some(text) = &queue.shown {
+        commands.spawn((
+            widget::toast(text.clone()),
+            ActiveToast(Timer::new(TOAST_DURATION, TimerMode::Once)),
+        ));
+    }
+}
+
+/// Uses the real time, so toasts also go away while the game is paused.
+fn animate_toasts(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    reduced_motion: Res<ReducedMotion>,
+    toasts: Query<(Entity, &mut ActiveToast, &mut Node)>,
+) {
+    for (entity, mut toast, mut node) in toasts {
+        if toast.0.tick(time.delta()).is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let shown = if reduced_motion.0 {
+            1.0
+        } else {
+            let elapsed = toast.0.elapsed_secs();
+            let remaining = toast.0.remaining_secs();
+            (elapsed.min(remaining) / SLIDE_SECS).clamp(0.0, 1.0)
+        };
+        let t = EaseFunction::SmoothStep.sample_clamped(shown);
+        node.right =
+            px(widget::TOAST_MARGIN - (widget::TOAST_WIDTH + widget::TOAST_MARGIN) * (1.0 - t));
+    }
+}
@@ -48,6 +48,62 @@ pub fn label(text: impl Into<String>) -> impl Bundle {
     )
 }
 
+/// The width of a [`toast`].
+pub const TOAST_WIDTH: f32 = 360.0;
+/// The space between a [`toast`] and the edges of the window.
+pub const TOAST_MARGIN: f32 = 10.0;
+
+/// A short message in the top right corner, above the menus.
+/// Push messages into the [`ToastQueue`](crate::theme::toast::ToastQueue) to show them.
+pub fn toast(text: impl Into<String>) -> impl Bundle {
+    (
+        Name::new("Toast"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(TOAST_MARGIN),
+            right: px(TOAST_MARGIN),
+            width: px(TOAST_WIDTH),
+            padding: UiRect::all(px(10)),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BackgroundColor(BUTTON_PRESSED_BACKGROUND),
+        BorderRadius::all(px(8)),
+        GlobalZIndex(3),
+        Pickable::IGNORE,
+        children![label(text)],
+    )
+}
+
+/// A horizontal bar that fills up from the left.
+/// Set the width of its [`ProgressBarFill`] to show the progress.
+pub fn progress_bar() -> impl Bundle {
+    (
+        Name::new("Progress Bar"),
+        Node {
+            width: px(380),
+            height: px(20),
+            ..default()
+        },
+        BackgroundColor(BUTTON_PRESSED_BACKGROUND),
+        children![(
+            Name::new("Progress Bar Fill"),
+            ProgressBarFill,
+            Node {
+                width: percent(0),
+                height: percent(100),
+                ..default()
+            },
+            BackgroundColor(BUTTON_HOVERED_BACKGROUND),
+        )],
+    )
+}
+
+/// The filled part of a [`progress_bar`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct ProgressBarFill;
+
 /// A large rounded button with text and an action defined as an [`Observer`].
 pub fn button<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
 where